@@ -5,7 +5,7 @@ use reqwest::Client;
 use tokio::{runtime::Runtime, time::Instant};
 
 use std::{
-    io::{Error, ErrorKind},
+    io::Error,
     time::Duration
 };
 
@@ -37,7 +37,7 @@ fn benchmark(c: &mut Criterion) {
         tokio::spawn(async {
             let mut app = App::new().without_body_limit();
             app.map_get("/", || async { "Hello, World!" });
-            app.map_get("/err", || async { Error::new(ErrorKind::Other, "error") });
+            app.map_get("/err", || async { Error::other("error") });
             app.map_err(|err| async move { status!(500, err.to_string()) });
             _ = app.run().await;
         });
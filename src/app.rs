@@ -1,31 +1,49 @@
 ﻿//! Main application entry point
 
-use self::pipeline::{Pipeline, PipelineBuilder};
-use hyper_util::{rt::TokioIo, server::graceful::GracefulShutdown};
+use self::{
+    pipeline::{Pipeline, PipelineBuilder},
+    router::RequiredHeaders,
+    shutdown::ShutdownConfig,
+    stats::StatsHandle
+};
+use hyper::header::HeaderName;
+use hyper_util::{rt::TokioIo, server::graceful::{GracefulShutdown, Watcher}};
+use tokio_util::sync::CancellationToken;
 use std::net::IpAddr;
 
 use crate::{
-    http::request::request_body_limit::RequestBodyLimit,
-    server::Server
+    error::ErrorFormat,
+    http::endpoints::args::{conn_info::ConnInfo, json::UnprocessableStatus, subdomain::BaseDomain, uri_parts::UriSettings},
+    http::StatusCode,
+    http::request::{
+        request_body_limit::RequestBodyLimit,
+        request_timeout::RequestTimeout,
+        canonical_host::CanonicalHost
+    },
+    server::{proxy_protocol, Server}
 };
 
 use std::{
     future::Future,
     io::Error,
     net::SocketAddr,
-    sync::{Arc, Weak}
+    sync::{Arc, Weak},
+    time::Duration
 };
 
 use tokio::{
     io::self,
     net::{TcpListener, TcpStream},
     signal,
-    sync::watch
+    sync::{watch, Semaphore}
 };
 
 #[cfg(feature = "di")]
 use crate::di::{Container, ContainerBuilder};
 
+#[cfg(feature = "multipart")]
+use crate::http::endpoints::args::multipart::MultipartLimits;
+
 #[cfg(feature = "tls")]
 use tokio_rustls::TlsAcceptor;
 
@@ -35,12 +53,26 @@ use crate::tls::TlsConfig;
 #[cfg(feature = "tracing")]
 use crate::tracing::TracingConfig;
 
+#[cfg(any(
+    feature = "compression-brotli",
+    feature = "compression-gzip",
+    feature = "compression-zstd",
+    feature = "compression-full"
+))]
+use crate::middleware::compress::CompressionConfig;
+
+pub use self::http_config::HttpConfig;
+
 pub mod router;
+pub mod stats;
+pub mod http_config;
 pub(crate) mod pipeline;
 pub(crate) mod scope;
+pub(crate) mod shutdown;
 
-pub(super) const GRACEFUL_SHUTDOWN_TIMEOUT: u64 = 10;
+const PROXY_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
 const DEFAULT_PORT: u16 = 7878;
+const MIN_IO_BUFFER_SIZE: usize = 8192;
 
 /// The web application used to configure the HTTP pipeline, and routes.
 ///
@@ -68,6 +100,15 @@ pub struct App {
     #[cfg(feature = "tracing")]
     pub(super) tracing_config: Option<TracingConfig>,
     
+    /// Compression configuration options
+    #[cfg(any(
+        feature = "compression-brotli",
+        feature = "compression-gzip",
+        feature = "compression-zstd",
+        feature = "compression-full"
+    ))]
+    pub(super) compression_config: CompressionConfig,
+    
     /// Request/Middleware pipeline builder
     pub(super) pipeline: PipelineBuilder,
     
@@ -77,7 +118,92 @@ pub struct App {
     /// Request body limit
     /// 
     /// Default: 5 MB
-    body_limit: RequestBodyLimit
+    body_limit: RequestBodyLimit,
+    
+    /// Maximum number of bytes of unread request body that are drained after the response is sent
+    /// 
+    /// Default: disabled
+    body_drain_limit: Option<usize>,
+    
+    /// Request handling timeout
+    /// 
+    /// Default: disabled
+    request_timeout: RequestTimeout,
+    
+    /// Request counters
+    stats: StatsHandle,
+    
+    /// Maximum number of concurrently served connections
+    /// 
+    /// Default: unlimited
+    max_connections: Option<usize>,
+    
+    /// Connection I/O buffer size
+    /// 
+    /// Default: hyper's default
+    io_buffer_size: Option<usize>,
+    
+    /// HTTP connection settings
+    http_config: HttpConfig,
+    
+    /// Base domain that request subdomains are parsed against
+    /// 
+    /// Default: none
+    base_domain: Option<BaseDomain>,
+    
+    /// Specifies whether incoming connections start with the PROXY protocol header
+    /// 
+    /// Default: `false`
+    proxy_protocol: bool,
+    
+    /// Specifies whether consecutive slashes in request paths are merged before routing
+    /// 
+    /// Default: `false`
+    merge_slashes: bool,
+    
+    /// Specifies whether catch-all route parameters keep consecutive slashes when they are merged
+    /// 
+    /// Default: `false`
+    preserve_catch_all_slashes: bool,
+    
+    /// Request header that the correlation ID is read from
+    /// 
+    /// Default: disabled
+    correlation_id_header: Option<HeaderName>,
+    
+    /// Request headers that are required by all routes
+    /// 
+    /// Default: none
+    required_headers: RequiredHeaders,
+    
+    /// Host that requests to its `www` or apex alternative are redirected to
+    /// 
+    /// Default: none
+    canonical_host: Option<CanonicalHost>,
+    
+    /// Specifies whether the `Forwarded` and `X-Forwarded-*` headers are trusted
+    /// 
+    /// Default: `false`
+    trusted_proxy: bool,
+    
+    /// Status code of responses to well-formed payloads that don't match the expected type
+    /// 
+    /// Default: 422 Unprocessable Entity
+    unprocessable_status: UnprocessableStatus,
+    
+    /// Limits of multipart field names and part headers
+    /// 
+    /// Default: 256 bytes for field names, 8 KB for part headers
+    #[cfg(feature = "multipart")]
+    pub(super) multipart_limits: MultipartLimits,
+    
+    /// Graceful shutdown settings
+    /// 
+    /// Default: `Ctrl+C` trigger, 10 seconds to finish in-flight requests
+    shutdown: ShutdownConfig,
+    
+    /// Format of framework-generated error responses
+    pub(super) error_format: ErrorFormat
 }
 
 /// Wraps a socket
@@ -122,14 +248,72 @@ pub(crate) struct AppInstance {
     #[cfg(feature = "di")]
     container: Container,
     
-    /// Graceful shutdown utilities
-    pub(super) graceful_shutdown: GracefulShutdown,
+    /// Cancelled when the shutdown timeout elapses, to drop connections that are still open
+    pub(super) shutdown_token: CancellationToken,
     
     /// Request body limit
     pub(super) body_limit: RequestBodyLimit,
     
+    /// Maximum number of bytes of unread request body that are drained after the response is sent
+    pub(super) body_drain_limit: Option<usize>,
+    
+    /// Request handling timeout
+    pub(super) request_timeout: RequestTimeout,
+    
     /// Request/Middleware pipeline
     pipeline: Pipeline,
+    
+    /// Request counters
+    pub(super) stats: StatsHandle,
+    
+    /// Connection I/O buffer size
+    pub(super) io_buffer_size: Option<usize>,
+    
+    /// HTTP connection settings
+    pub(super) http_config: HttpConfig,
+    
+    /// Base domain that request subdomains are parsed against
+    pub(super) base_domain: Option<BaseDomain>,
+    
+    /// Specifies whether incoming connections start with the PROXY protocol header
+    pub(super) proxy_protocol: bool,
+    
+    /// Specifies whether consecutive slashes in request paths are merged before routing
+    pub(super) merge_slashes: bool,
+    
+    /// Specifies whether catch-all route parameters keep consecutive slashes when they are merged
+    pub(super) preserve_catch_all_slashes: bool,
+    
+    /// Request header that the correlation ID is read from
+    pub(super) correlation_id_header: Option<HeaderName>,
+    
+    /// Request headers that are required by all routes
+    pub(super) required_headers: RequiredHeaders,
+    
+    /// Host that requests to its `www` or apex alternative are redirected to
+    pub(super) canonical_host: Option<CanonicalHost>,
+    
+    /// Settings that are used to resolve the scheme and authority of requests
+    pub(super) uri_settings: UriSettings,
+    
+    /// Status code of responses to well-formed payloads that don't match the expected type
+    pub(super) unprocessable_status: UnprocessableStatus,
+    
+    /// Limits of multipart field names and part headers
+    #[cfg(feature = "multipart")]
+    pub(super) multipart_limits: MultipartLimits,
+    
+    /// Compression levels of the compression middleware
+    #[cfg(any(
+        feature = "compression-brotli",
+        feature = "compression-gzip",
+        feature = "compression-zstd",
+        feature = "compression-full"
+    ))]
+    pub(super) compression_config: CompressionConfig,
+    
+    /// Format of framework-generated error responses
+    pub(super) error_format: ErrorFormat,
 }
 
 impl TryFrom<App> for AppInstance {
@@ -146,8 +330,38 @@ impl TryFrom<App> for AppInstance {
         };
         let app_instance = Self {
             body_limit: app.body_limit,
-            pipeline: app.pipeline.build(),
-            graceful_shutdown: GracefulShutdown::new(),
+            body_drain_limit: app.body_drain_limit,
+            request_timeout: app.request_timeout,
+            stats: app.stats,
+            io_buffer_size: app.io_buffer_size,
+            http_config: app.http_config,
+            base_domain: app.base_domain,
+            proxy_protocol: app.proxy_protocol,
+            merge_slashes: app.merge_slashes,
+            preserve_catch_all_slashes: app.preserve_catch_all_slashes,
+            correlation_id_header: app.correlation_id_header,
+            required_headers: app.required_headers,
+            canonical_host: app.canonical_host,
+            uri_settings: UriSettings {
+                #[cfg(feature = "tls")]
+                tls: acceptor.is_some(),
+                #[cfg(not(feature = "tls"))]
+                tls: false,
+                trusted_proxy: app.trusted_proxy
+            },
+            unprocessable_status: app.unprocessable_status,
+            #[cfg(feature = "multipart")]
+            multipart_limits: app.multipart_limits,
+            #[cfg(any(
+                feature = "compression-brotli",
+                feature = "compression-gzip",
+                feature = "compression-zstd",
+                feature = "compression-full"
+            ))]
+            compression_config: app.compression_config,
+            error_format: app.error_format,
+            pipeline: app.pipeline.build(app.error_format),
+            shutdown_token: CancellationToken::new(),
             #[cfg(feature = "di")]
             container: app.container.build(),
             #[cfg(feature = "tls")]
@@ -159,16 +373,20 @@ impl TryFrom<App> for AppInstance {
 
 impl AppInstance {
     /// Gracefully shutdown current instance
+    /// 
+    /// Signals all connections to close once their requests in flight are served 
+    /// and waits for them within the `timeout`, then drops the connections that are still open
     #[inline]
-    async fn shutdown(self) {
+    async fn shutdown(&self, graceful_shutdown: GracefulShutdown, timeout: Duration) {
         tokio::select! {
-            _ = self.graceful_shutdown.shutdown() => {
+            _ = graceful_shutdown.shutdown() => {
                 #[cfg(feature = "tracing")]
                 tracing::info!("shutting down the server...");
             },
-            _ = tokio::time::sleep(std::time::Duration::from_secs(GRACEFUL_SHUTDOWN_TIMEOUT)) => {
+            _ = tokio::time::sleep(timeout) => {
                 #[cfg(feature = "tracing")]
                 tracing::warn!("timed out wait for all connections to close");
+                self.shutdown_token.cancel();
             }
         }
     }
@@ -198,9 +416,35 @@ impl App {
             tls_config: None,
             #[cfg(feature = "tracing")]
             tracing_config: None,
+            #[cfg(any(
+                feature = "compression-brotli",
+                feature = "compression-gzip",
+                feature = "compression-zstd",
+                feature = "compression-full"
+            ))]
+            compression_config: Default::default(),
             pipeline:PipelineBuilder::new(),
             connection: Default::default(),
             body_limit: Default::default(),
+            body_drain_limit: None,
+            request_timeout: Default::default(),
+            stats: Default::default(),
+            max_connections: None,
+            io_buffer_size: None,
+            http_config: Default::default(),
+            base_domain: None,
+            proxy_protocol: false,
+            merge_slashes: false,
+            preserve_catch_all_slashes: false,
+            correlation_id_header: None,
+            required_headers: Default::default(),
+            canonical_host: None,
+            trusted_proxy: false,
+            unprocessable_status: Default::default(),
+            #[cfg(feature = "multipart")]
+            multipart_limits: Default::default(),
+            shutdown: Default::default(),
+            error_format: Default::default(),
         }
     }
 
@@ -218,7 +462,10 @@ impl App {
         self
     }
     
-    /// Sets a specific HTTP request body limit (in bytes)
+    /// Sets a specific HTTP request body limit (in bytes), requests with a larger body 
+    /// are rejected with `413 Payload Too Large`
+    /// 
+    /// It can be overridden for a specific route with [`RouteBuilder::body_limit`](crate::routing::RouteBuilder::body_limit).
     /// 
     /// Default: 5 MB
     pub fn with_body_limit(mut self, limit: usize) -> Self {
@@ -231,6 +478,364 @@ impl App {
         self.body_limit = RequestBodyLimit::Disabled;
         self
     }
+    
+    /// Sets the maximum size (in bytes) of unread request body that is drained 
+    /// if the request handler responds without reading the whole body, e.g. on early rejection of an upload
+    /// 
+    /// The remainder is read and discarded in background, so the HTTP/1 connection can be reused 
+    /// for the next request. If the remainder exceeds the `limit`, the connection is closed instead.
+    /// 
+    /// Default: disabled, only the data that has already been received is discarded, 
+    /// otherwise the connection is closed
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_body_drain_limit(64 * 1024);
+    /// ```
+    pub fn with_body_drain_limit(mut self, limit: usize) -> Self {
+        self.body_drain_limit = Some(limit);
+        self
+    }
+
+    /// Sets a request handling timeout for all routes, including streaming the response body.
+    /// 
+    /// Requests whose response isn't ready in time get `504 Gateway Timeout`,
+    /// response bodies that aren't fully sent in time are aborted. In both cases the request's 
+    /// [`CancellationToken`](crate::CancellationToken) is cancelled, so cooperative handlers could stop their work.
+    /// 
+    /// Server-Sent Events streams are not aborted once their head is sent, unless a timeout is set 
+    /// for the route explicitly. It can be overridden or disabled per route, e.g. for other long-lived streams.
+    /// 
+    /// Default: disabled
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use std::time::Duration;
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_request_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = RequestTimeout::Total(timeout);
+        self
+    }
+
+    /// Sets the maximum number of concurrently served connections
+    /// 
+    /// Once the limit is reached, new connections are not accepted 
+    /// and wait in the listener's backlog until one of the served connections is closed.
+    /// 
+    /// Default: unlimited
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_max_connections(10_000);
+    /// ```
+    pub fn with_max_connections(mut self, limit: usize) -> Self {
+        self.max_connections = Some(limit);
+        self
+    }
+
+    /// Sets the maximum size (in bytes) of the connection I/O buffers
+    /// 
+    /// For HTTP/1 it limits the read buffer, which also bounds the size of request headers, 
+    /// for HTTP/2 it limits the send buffer of each stream.
+    /// 
+    /// Default: hyper's default (~400 KB)
+    /// 
+    /// # Panics
+    /// If the `size` is less than 8192 bytes
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_io_buffer_size(1024 * 1024);
+    /// ```
+    pub fn with_io_buffer_size(mut self, size: usize) -> Self {
+        assert!(size >= MIN_IO_BUFFER_SIZE, "I/O buffer size must be at least {MIN_IO_BUFFER_SIZE} bytes, got: {size}");
+        self.io_buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the base domain that request subdomains are parsed against
+    /// 
+    /// The subdomain of the `Host` header, e.g. `tenant1` for `tenant1.example.com`,
+    /// is added to the request extensions and can be read with the [`Subdomain`](crate::Subdomain) extractor 
+    /// or by middlewares, e.g. to resolve the current tenant.
+    /// 
+    /// Default: none
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_base_domain("example.com");
+    /// ```
+    pub fn with_base_domain(mut self, domain: &str) -> Self {
+        self.base_domain = Some(BaseDomain::new(domain));
+        self
+    }
+
+    /// Enables the PROXY protocol (v1 and v2) for incoming connections
+    /// 
+    /// When the server is behind an L4 load balancer, e.g. HAProxy or AWS NLB, 
+    /// the original client address is read from the PROXY header that precedes the connection data, 
+    /// and reported by [`ConnInfo`](crate::ConnInfo) instead of the load balancer's one.
+    /// Connections without a valid PROXY header are rejected.
+    /// 
+    /// Default: disabled
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_proxy_protocol();
+    /// ```
+    pub fn with_proxy_protocol(mut self) -> Self {
+        self.proxy_protocol = true;
+        self
+    }
+
+    /// Specifies whether consecutive slashes in request paths are merged before routing
+    /// 
+    /// If enabled, a request to `/a//b` is routed as `/a/b`, and handlers see the merged path.
+    /// 
+    /// Default: `false`
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_merge_slashes(true);
+    /// ```
+    pub fn with_merge_slashes(mut self, merge: bool) -> Self {
+        self.merge_slashes = merge;
+        self
+    }
+
+    /// Specifies whether catch-all route parameters keep consecutive slashes 
+    /// if they are merged with [`App::with_merge_slashes`]
+    /// 
+    /// If enabled, a request to `/files//a//b` is still routed as `/files/a/b`, 
+    /// but `{*path}` of the `/files/{*path}` route is bound to `a//b`.
+    /// 
+    /// Default: `false`
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new()
+    ///    .with_merge_slashes(true)
+    ///    .with_preserved_catch_all_slashes(true);
+    /// ```
+    pub fn with_preserved_catch_all_slashes(mut self, preserve: bool) -> Self {
+        self.preserve_catch_all_slashes = preserve;
+        self
+    }
+
+    /// Sets the canonical host, so requests to its `www` or apex alternative 
+    /// are redirected to it with `301 Moved Permanently`
+    /// 
+    /// E.g. for `example.com` requests to `www.example.com/x` are redirected to `example.com/x`,
+    /// and for `www.example.com` it's the other way around. The scheme, port, path and query 
+    /// are preserved, requests to other hosts are not affected.
+    /// 
+    /// Default: none
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_canonical_host("example.com");
+    /// ```
+    pub fn with_canonical_host(mut self, host: &str) -> Self {
+        self.canonical_host = Some(CanonicalHost::new(host));
+        self
+    }
+
+    /// Enables the correlation ID propagation
+    /// 
+    /// The correlation ID is read from the `header` of each request and is available 
+    /// during the request handling via [`CorrelationId::current`](crate::http::CorrelationId::current), 
+    /// e.g. to attach it to the calls to downstream services.
+    /// 
+    /// Default: disabled
+    /// 
+    /// # Panics
+    /// If the `header` is not a valid lowercase header name
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_correlation_id("x-correlation-id");
+    /// ```
+    pub fn with_correlation_id(mut self, header: &'static str) -> Self {
+        self.correlation_id_header = Some(HeaderName::from_static(header));
+        self
+    }
+
+    /// Sets a list of request headers that are required by all routes
+    /// 
+    /// Requests without any of these headers are rejected with `400 Bad Request` 
+    /// that names the missing header. Use [`RouteBuilder::require_headers`](crate::routing::RouteBuilder::require_headers) 
+    /// to require headers for specific routes.
+    /// 
+    /// Default: none
+    /// 
+    /// # Panics
+    /// If any of the `headers` is not a valid header name
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_required_headers(["x-api-version"]);
+    /// ```
+    pub fn with_required_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>
+    {
+        self.required_headers = RequiredHeaders::new(headers);
+        self
+    }
+
+    /// Enables the trusted proxy mode
+    /// 
+    /// When the server is behind a reverse proxy, the scheme and host of the original request 
+    /// are read from the `Forwarded`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers 
+    /// by [`UriParts`](crate::UriParts). Only their last value is used, i.e. the one appended 
+    /// by the proxy in front of the server. Enable it only if the server is reachable through that proxy alone, 
+    /// otherwise clients are able to spoof these headers.
+    /// 
+    /// Default: disabled
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_trusted_proxy();
+    /// ```
+    pub fn with_trusted_proxy(mut self) -> Self {
+        self.trusted_proxy = true;
+        self
+    }
+
+    /// Sets the status code of responses to syntactically valid JSON payloads 
+    /// that can't be deserialized into the expected type, e.g. because of a missing field
+    /// 
+    /// Malformed JSON is always rejected with `400 Bad Request`.
+    /// 
+    /// Default: `422 Unprocessable Entity`
+    /// 
+    /// # Panics
+    /// If the `status` is not a client error status code (`400..=499`)
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_unprocessable_status(400);
+    /// ```
+    pub fn with_unprocessable_status(mut self, status: u16) -> Self {
+        let status = StatusCode::from_u16(status)
+            .ok()
+            .filter(StatusCode::is_client_error)
+            .unwrap_or_else(|| panic!("unprocessable status must be a client error status code, got: {status}"));
+        self.unprocessable_status = UnprocessableStatus(status);
+        self
+    }
+
+    /// Sets a custom shutdown trigger that is awaited along with `Ctrl+C`
+    /// 
+    /// Once either of them completes, the server stops accepting new connections 
+    /// and waits for the in-flight requests to finish.
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use std::sync::Arc;
+    ///use tokio::sync::Notify;
+    ///use volga::App;
+    ///
+    ///let notify = Arc::new(Notify::new());
+    ///let trigger = notify.clone();
+    ///let app = App::new().with_shutdown_signal(async move { trigger.notified().await });
+    ///
+    ///// somewhere else
+    ///notify.notify_one();
+    /// ```
+    pub fn with_shutdown_signal<F>(mut self, signal: F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static
+    {
+        self.shutdown.set_signal(signal);
+        self
+    }
+
+    /// Sets the time that in-flight requests are given to finish on shutdown, 
+    /// the connections that are still open afterward are dropped
+    /// 
+    /// Default: 10 seconds
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use std::time::Duration;
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_shutdown_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown.timeout = timeout;
+        self
+    }
+
+    /// Registers a callback that runs once on shutdown, e.g. to flush metrics or close pools
+    /// 
+    /// It runs after the server has stopped accepting connections and the in-flight requests 
+    /// have finished or the shutdown timeout has elapsed, but before [`App::run`] returns.
+    /// Multiple callbacks run one by one in the order of registration.
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let mut app = App::new();
+    ///app.on_shutdown(|| async {
+    ///    println!("cleaning up...");
+    ///});
+    /// ```
+    pub fn on_shutdown<F, Fut>(&mut self, hook: F) -> &mut Self
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+    {
+        self.shutdown.add_hook(hook);
+        self
+    }
+
+    /// Returns a handle to the request counters of this `App`
+    /// 
+    /// The handle is cheap to clone and stays valid while the `App` is running.
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new();
+    ///let stats = app.stats_handle();
+    ///
+    ///assert_eq!(stats.in_flight(), 0);
+    /// ```
+    pub fn stats_handle(&self) -> StatsHandle {
+        self.stats.clone()
+    }
 
     /// Runs the `App`
     #[cfg(feature = "middleware")]
@@ -246,7 +851,10 @@ impl App {
     }
     
     #[inline]
-    async fn run_internal(self) -> io::Result<()> {
+    async fn run_internal(mut self) -> io::Result<()> {
+        #[cfg(feature = "di")]
+        self.container.init_async().await?;
+        
         let socket = self.connection.socket;
         let tcp_listener = TcpListener::bind(socket).await?;
         #[cfg(feature = "tracing")]
@@ -259,17 +867,23 @@ impl App {
             };
             #[cfg(not(feature = "tls"))]
             tracing::info!("listening on: http://{socket}");
+            
+            self.log_startup_summary();
         }
 
+        let mut shutdown = std::mem::take(&mut self.shutdown);
         let (shutdown_tx, shutdown_rx) = watch::channel::<()>(());
         let shutdown_tx = Arc::new(shutdown_tx);
-        Self::shutdown_signal(shutdown_rx);
+        Self::shutdown_signal(shutdown_rx, shutdown.take_signal());
 
         #[cfg(feature = "tls")]
         let redirection_config = self.tls_config
             .as_ref()
             .map(|config| config.https_redirection_config.clone());
         
+        let connection_limit = self.max_connections
+            .map(|limit| Arc::new(Semaphore::new(limit)));
+        
         let app_instance: Arc<AppInstance> = Arc::new(self.try_into()?);
         
         #[cfg(feature = "tls")]
@@ -278,37 +892,82 @@ impl App {
                 Self::run_https_redirection_middleware(
                     socket,
                     redirection_config.http_port,
+                    shutdown.timeout,
                     shutdown_tx.clone());
             }
         }
 
+        let graceful_shutdown = GracefulShutdown::new();
         loop {
+            let permit = match &connection_limit {
+                Some(limit) => tokio::select! {
+                    Ok(permit) = limit.clone().acquire_owned() => Some(permit),
+                    _ = shutdown_tx.closed() => break,
+                },
+                None => None
+            };
+            
             let (stream, _) = tokio::select! {
                 Ok(connection) = tcp_listener.accept() => connection,
                 _ = shutdown_tx.closed() => break,
             };
             
             let instance = Arc::downgrade(&app_instance);
-            tokio::spawn(Self::handle_connection(stream, instance));
+            let watcher = graceful_shutdown.watcher();
+            tokio::spawn(async move {
+                Self::handle_connection(stream, instance, watcher).await;
+                drop(permit);
+            });
         }
     
         drop(tcp_listener);
 
-        if let Some(app_instance) = Arc::into_inner(app_instance) {
-            app_instance.shutdown().await;
-        }
+        app_instance.shutdown(graceful_shutdown, shutdown.timeout).await;
+        drop(app_instance);
+        shutdown.run_hooks().await;
         Ok(())
     }
     
+    /// Logs the effective configuration of the `App`
+    #[cfg(feature = "tracing")]
+    fn log_startup_summary(&self) {
+        #[cfg(feature = "tls")]
+        let tls = self.tls_config.is_some();
+        #[cfg(not(feature = "tls"))]
+        let tls = false;
+        
+        let body_limit = match self.body_limit {
+            RequestBodyLimit::Enabled(limit) => Some(limit),
+            RequestBodyLimit::Disabled => None
+        };
+        
+        tracing::info!(
+            address = %self.connection.socket,
+            tls,
+            middlewares = ?self.pipeline.middleware_names(),
+            routes = self.routes().len(),
+            body_limit,
+            max_connections = self.max_connections,
+            error_format = ?self.error_format,
+            "startup configuration"
+        );
+    }
+    
     #[inline]
-    fn shutdown_signal(shutdown_rx: watch::Receiver<()>) {
+    fn shutdown_signal(
+        shutdown_rx: watch::Receiver<()>, 
+        custom_signal: impl Future<Output = ()> + Send + 'static
+    ) {
         tokio::spawn(async move {
-            match signal::ctrl_c().await {
-                Ok(_) => (),
-                #[cfg(feature = "tracing")]
-                Err(err) => tracing::error!("unable to listen for shutdown signal: {}", err),
-                #[cfg(not(feature = "tracing"))]
-                Err(_) => ()
+            tokio::select! {
+                result = signal::ctrl_c() => match result {
+                    Ok(_) => (),
+                    #[cfg(feature = "tracing")]
+                    Err(err) => tracing::error!("unable to listen for shutdown signal: {}", err),
+                    #[cfg(not(feature = "tracing"))]
+                    Err(_) => ()
+                },
+                _ = custom_signal => ()
             }
             #[cfg(feature = "tracing")]
             tracing::trace!("shutdown signal received, not accepting new requests");
@@ -317,9 +976,13 @@ impl App {
     }
 
     #[inline]
-    async fn handle_connection(stream: TcpStream, app_instance: Weak<AppInstance>) {
+    async fn handle_connection(mut stream: TcpStream, app_instance: Weak<AppInstance>, watcher: Watcher) {
+        let Some(conn_info) = Self::read_conn_info(&mut stream, &app_instance).await else {
+            return;
+        };
+        
         #[cfg(not(feature = "tls"))]
-        Server::new(TokioIo::new(stream)).serve(app_instance).await;
+        Server::new(TokioIo::new(stream)).serve(app_instance, conn_info, watcher).await;
         
         #[cfg(feature = "tls")]
         if let Some(acceptor) = app_instance.upgrade().and_then(|app| app.acceptor()) {
@@ -332,12 +995,43 @@ impl App {
                 }
             };
             let io = TokioIo::new(stream);
-            Server::new(io).serve(app_instance).await;
+            Server::new(io).serve(app_instance, conn_info, watcher).await;
         } else {
             let io = TokioIo::new(stream);
-            Server::new(io).serve(app_instance).await;
+            Server::new(io).serve(app_instance, conn_info, watcher).await;
         };
     }
+    
+    /// Reads the connection addresses, from the PROXY header if the PROXY protocol is enabled
+    /// 
+    /// Returns `None` if the connection should be rejected
+    #[inline]
+    async fn read_conn_info(stream: &mut TcpStream, app_instance: &Weak<AppInstance>) -> Option<ConnInfo> {
+        let remote_addr = stream.peer_addr().ok()?;
+        let local_addr = stream.local_addr().ok()?;
+        
+        let proxy_protocol = app_instance
+            .upgrade()
+            .is_some_and(|app| app.proxy_protocol);
+        if !proxy_protocol {
+            return Some(ConnInfo::new(remote_addr, local_addr));
+        }
+        
+        match tokio::time::timeout(PROXY_HEADER_TIMEOUT, proxy_protocol::read_header(stream)).await {
+            Ok(Ok(Some(header))) => Some(ConnInfo::new(header.source, header.destination)),
+            Ok(Ok(None)) => Some(ConnInfo::new(remote_addr, local_addr)),
+            Ok(Err(_err)) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("rejected connection from {remote_addr}: {_err}");
+                None
+            },
+            Err(_) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("rejected connection from {remote_addr}: PROXY protocol header timed out");
+                None
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -386,4 +1080,17 @@ mod tests {
 
         assert_eq!(app.connection.socket, SocketAddr::from(([127, 0, 0, 1], 5001)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn it_sets_io_buffer_size() {
+        let app = App::new().with_io_buffer_size(16 * 1024);
+
+        assert_eq!(app.io_buffer_size, Some(16 * 1024));
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_io_buffer_size_is_too_small() {
+        _ = App::new().with_io_buffer_size(1024);
+    }
+}
@@ -0,0 +1,148 @@
+﻿//! HTTP/1 and HTTP/2 connection settings
+
+use crate::App;
+
+#[cfg(feature = "http2")]
+use {hyper::server::conn::http2, hyper_util::rt::TokioExecutor};
+
+/// Represents HTTP connection settings
+/// 
+/// The HTTP/2 limits protect the server from abusive clients, e.g. from the HTTP/2 Rapid Reset attack
+/// ([CVE-2023-44487](https://nvd.nist.gov/vuln/detail/CVE-2023-44487)), by closing 
+/// the connection with `GOAWAY` once they are exceeded.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct HttpConfig {
+    /// Maximum number of streams that are reset by the client before the server accepts them
+    /// 
+    /// Default: hyper's default (20)
+    #[cfg(feature = "http2")]
+    max_reset_streams: Option<usize>,
+    
+    /// Maximum number of streams that are reset by the server because of client errors
+    /// 
+    /// Default: hyper's default (1024)
+    #[cfg(feature = "http2")]
+    max_local_error_reset_streams: Option<usize>,
+    
+    /// Maximum number of requests served over a single HTTP/1 connection
+    /// 
+    /// Default: unlimited
+    max_requests_per_connection: Option<usize>,
+}
+
+impl HttpConfig {
+    /// Creates a default HTTP connection configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Sets the maximum number of streams that the client may reset before the server accepts them,
+    /// the connection is closed once it's exceeded
+    /// 
+    /// Default: hyper's default (20)
+    #[cfg(feature = "http2")]
+    pub fn with_max_reset_streams(mut self, max: usize) -> Self {
+        self.max_reset_streams = Some(max);
+        self
+    }
+    
+    /// Sets the maximum number of streams that the server may reset because of client errors, 
+    /// e.g. malformed frames, the connection is closed once it's exceeded
+    /// 
+    /// Default: hyper's default (1024)
+    #[cfg(feature = "http2")]
+    pub fn with_max_local_error_reset_streams(mut self, max: usize) -> Self {
+        self.max_local_error_reset_streams = Some(max);
+        self
+    }
+    
+    /// Sets the maximum number of requests served over a single HTTP/1 connection, 
+    /// which also bounds the number of requests a client may pipeline.
+    /// 
+    /// The last allowed request is responded with `Connection: close` and the connection is closed 
+    /// afterward, so the client has to open a new one to send the rest of the pipelined requests.
+    /// 
+    /// It has no effect on HTTP/2 connections.
+    /// 
+    /// # Panics
+    /// If `max` is zero
+    /// 
+    /// Default: unlimited
+    pub fn with_max_requests_per_connection(mut self, max: usize) -> Self {
+        assert!(max > 0, "The maximum number of requests per connection must be greater than zero");
+        self.max_requests_per_connection = Some(max);
+        self
+    }
+    
+    /// Checks whether the connection must be closed after the `served` number of requests
+    #[inline]
+    #[cfg_attr(feature = "http2", allow(dead_code))]
+    pub(crate) fn is_connection_exhausted(&self, served: usize) -> bool {
+        self.max_requests_per_connection.is_some_and(|max| served >= max)
+    }
+    
+    /// Applies the settings to the HTTP/2 connection builder
+    #[cfg(feature = "http2")]
+    pub(crate) fn apply(&self, builder: &mut http2::Builder<TokioExecutor>) {
+        if let Some(max) = self.max_reset_streams {
+            builder.max_pending_accept_reset_streams(max);
+        }
+        if let Some(max) = self.max_local_error_reset_streams {
+            builder.max_local_error_reset_streams(max);
+        }
+    }
+}
+
+impl App {
+    /// Configures web server with specified HTTP connection settings
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    /// 
+    /// let app = App::new()
+    ///     .with_http_config(|config| config.with_max_requests_per_connection(100));
+    /// ```
+    pub fn with_http_config<F>(mut self, config: F) -> Self
+    where
+        F: FnOnce(HttpConfig) -> HttpConfig
+    {
+        self.http_config = config(self.http_config);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HttpConfig;
+
+    #[test]
+    #[cfg(feature = "http2")]
+    fn it_creates_default_config() {
+        let config = HttpConfig::new();
+
+        assert_eq!(config.max_reset_streams, None);
+        assert_eq!(config.max_local_error_reset_streams, None);
+        assert_eq!(config.max_requests_per_connection, None);
+    }
+
+    #[test]
+    #[cfg(feature = "http2")]
+    fn it_sets_reset_stream_limits() {
+        let config = HttpConfig::new()
+            .with_max_reset_streams(10)
+            .with_max_local_error_reset_streams(100);
+
+        assert_eq!(config.max_reset_streams, Some(10));
+        assert_eq!(config.max_local_error_reset_streams, Some(100));
+    }
+
+    #[test]
+    fn it_checks_max_requests_per_connection() {
+        let config = HttpConfig::new().with_max_requests_per_connection(3);
+
+        assert!(!config.is_connection_exhausted(2));
+        assert!(config.is_connection_exhausted(3));
+        assert!(!HttpConfig::new().is_connection_exhausted(usize::MAX));
+    }
+}
@@ -1,30 +1,35 @@
-﻿use std::sync::Arc;
+﻿use std::{collections::HashMap, sync::Arc};
+use hyper::{HeaderMap, Uri};
 use crate::{
+    app::router::host::request_host,
     error::{
-        ErrorFunc, 
-        handler::{PipelineErrorHandler, WeakErrorHandler, default_error_handler}
+        ErrorFormat, 
+        handler::{PipelineErrorHandler, WeakErrorHandler, call_weak_err_handler}
     },
-    http::endpoints::Endpoints
+    http::{endpoints::Endpoints, response::mapper::PipelineResponseMapper},
+    HttpResult
 };
 
 #[cfg(feature = "middleware")]
-use crate::{
-    middleware::{Middlewares, HttpContext, Next},
-    HttpResult
-};
+use crate::middleware::{Middlewares, HttpContext, Next};
 
 pub(crate) struct PipelineBuilder {
     #[cfg(feature = "middleware")]
     middlewares: Middlewares,
     endpoints: Endpoints,
-    error_handler: PipelineErrorHandler
+    hosts: HashMap<String, Endpoints>,
+    current_host: Option<String>,
+    error_handler: Option<PipelineErrorHandler>,
+    response_mappers: Vec<PipelineResponseMapper>
 }
 
 pub(crate) struct Pipeline {
     #[cfg(feature = "middleware")]
     start: Option<Next>,
     endpoints: Endpoints,
-    error_handler: PipelineErrorHandler
+    hosts: HashMap<String, Endpoints>,
+    error_handler: PipelineErrorHandler,
+    response_mappers: Vec<PipelineResponseMapper>
 }
 
 impl PipelineBuilder {
@@ -33,7 +38,10 @@ impl PipelineBuilder {
         Self {
             middlewares: Middlewares::new(),
             endpoints: Endpoints::new(),
-            error_handler: ErrorFunc(default_error_handler).into()
+            hosts: HashMap::new(),
+            current_host: None,
+            error_handler: None,
+            response_mappers: Vec::new()
         }
     }
 
@@ -41,25 +49,34 @@ impl PipelineBuilder {
     pub(super) fn new() -> Self {
         Self { 
             endpoints: Endpoints::new(),
-            error_handler: ErrorFunc(default_error_handler).into()
+            hosts: HashMap::new(),
+            current_host: None,
+            error_handler: None,
+            response_mappers: Vec::new()
         }
     }
 
     #[cfg(feature = "middleware")]
-    pub(super) fn build(self) -> Pipeline {
+    pub(super) fn build(self, error_format: ErrorFormat) -> Pipeline {
         let start = self.middlewares.compose();
         Pipeline {
             endpoints: self.endpoints,
-            error_handler: self.error_handler,
+            hosts: self.hosts,
+            error_handler: self.error_handler
+                .unwrap_or_else(|| error_format.error_handler()),
+            response_mappers: self.response_mappers,
             start
         }
     }
 
     #[cfg(not(feature = "middleware"))]
-    pub(super) fn build(self) -> Pipeline {
+    pub(super) fn build(self, error_format: ErrorFormat) -> Pipeline {
         Pipeline { 
             endpoints: self.endpoints,
+            hosts: self.hosts,
             error_handler: self.error_handler
+                .unwrap_or_else(|| error_format.error_handler()),
+            response_mappers: self.response_mappers
         }
     }
 
@@ -68,24 +85,62 @@ impl PipelineBuilder {
         !self.middlewares.is_empty()
     }
 
+    /// Returns the names of registered middlewares, 
+    /// excluding the request handler middleware registered on `run()`
+    #[cfg(feature = "tracing")]
+    pub(super) fn middleware_names(&self) -> &[&'static str] {
+        let names = self.middlewares.names();
+        names.strip_suffix(&["endpoints"]).unwrap_or(names)
+    }
+
     #[cfg(feature = "middleware")]
     pub(crate) fn middlewares_mut(&mut self) -> &mut Middlewares {
         &mut self.middlewares
     }
 
+    /// Returns the default endpoints followed by the endpoints of each host
+    pub(super) fn all_endpoints(&self) -> impl Iterator<Item = (Option<&str>, &Endpoints)> {
+        std::iter::once((None, &self.endpoints))
+            .chain(self.hosts
+                .iter()
+                .map(|(host, endpoints)| (Some(host.as_str()), endpoints)))
+    }
+
+    /// Returns the endpoints of the host that routes are currently mapped for, 
+    /// or the default ones if there is no such host
     pub(super) fn endpoints_mut(&mut self) -> &mut Endpoints {
-        &mut self.endpoints
+        match &self.current_host {
+            Some(host) => self.hosts
+                .entry(host.clone())
+                .or_insert_with(Endpoints::new),
+            None => &mut self.endpoints
+        }
+    }
+
+    /// Sets the host that routes are mapped for, returns the previous one
+    pub(super) fn set_host(&mut self, host: Option<String>) -> Option<String> {
+        std::mem::replace(&mut self.current_host, host)
     }
     
     pub(crate) fn set_error_handler(&mut self, handler: PipelineErrorHandler) {
-        self.error_handler = handler;
+        self.error_handler = Some(handler);
+    }
+
+    pub(crate) fn add_response_mapper(&mut self, mapper: PipelineResponseMapper) {
+        self.response_mappers.push(mapper);
     }
 }
 
 impl Pipeline {
+    /// Returns the endpoints mapped for the request host, or the default ones if there are no such
     #[inline]
-    pub(crate) fn endpoints(&self) -> &Endpoints {
-        &self.endpoints
+    pub(crate) fn endpoints(&self, uri: &Uri, headers: &HeaderMap) -> &Endpoints {
+        if self.hosts.is_empty() {
+            return &self.endpoints;
+        }
+        request_host(uri, headers)
+            .and_then(|host| self.hosts.get(&host))
+            .unwrap_or(&self.endpoints)
     }
 
     #[inline]
@@ -98,6 +153,30 @@ impl Pipeline {
         self.start.is_some()
     }
 
+    /// Applies global response mappers to every response, including the error ones
+    /// 
+    /// An error, either of the request handling or of a mapper, is turned into a response 
+    /// by the error handler first, so each of the following mappers gets a response to map.
+    pub(crate) async fn map_response(&self, response: HttpResult, uri: &Uri) -> HttpResult {
+        if self.response_mappers.is_empty() {
+            return response;
+        }
+        let mut response = self.handle_err(response, uri).await?;
+        for mapper in &self.response_mappers {
+            response = self.handle_err(mapper.call(response).await, uri).await?;
+        }
+        Ok(response)
+    }
+
+    /// Turns an error result into a response with the error handler
+    #[inline]
+    async fn handle_err(&self, response: HttpResult, uri: &Uri) -> HttpResult {
+        match response {
+            Err(err) => call_weak_err_handler(self.error_handler(), uri, err).await,
+            ok => ok
+        }
+    }
+
     #[cfg(feature = "middleware")]
     pub(crate) async fn execute(&self, ctx: HttpContext) -> HttpResult {
         let next = &self.start;
@@ -1,11 +1,83 @@
-﻿use hyper::Method;
-use crate::App;
-use crate::http::IntoResponse;
+﻿use hyper::{header::{HeaderName, CONTENT_TYPE}, HeaderMap, Method};
+use std::{
+    ops::{Deref, DerefMut},
+    time::Duration
+};
+use crate::{App, error::Error};
+use self::concurrency::ConcurrencyLimiter;
+use self::target::RouteTarget;
+use crate::http::{
+    IntoResponse, 
+    request::{request_timeout::RequestTimeout, request_body_limit::RequestBodyLimit}
+};
 use crate::http::endpoints::{
     args::FromRequest,
     handlers::{Func, GenericHandler}
 };
 
+pub mod concurrency;
+pub(crate) mod host;
+#[cfg(feature = "macros")]
+pub mod attribute;
+
+/// Describes one or more route patterns that a request handler can be mapped to
+/// 
+/// Implemented for a single pattern (`&str`, `String`) as well as for arrays, 
+/// slices and vectors of patterns.
+pub trait RoutePatterns {
+    /// Returns an iterator over the route patterns
+    fn patterns(&self) -> impl Iterator<Item = &str>;
+}
+
+impl RoutePatterns for &str {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(*self)
+    }
+}
+
+impl RoutePatterns for String {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.as_str())
+    }
+}
+
+impl RoutePatterns for &String {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.as_str())
+    }
+}
+
+impl<const N: usize> RoutePatterns for [&str; N] {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.iter().copied()
+    }
+}
+
+impl RoutePatterns for &[&str] {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.iter().copied()
+    }
+}
+
+impl RoutePatterns for Vec<&str> {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.iter().copied()
+    }
+}
+
+impl RoutePatterns for Vec<String> {
+    #[inline]
+    fn patterns(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(String::as_str)
+    }
+}
+
 /// Routes mapping 
 impl App {
     /// Maps a group of request handlers combined by `prefix`
@@ -55,21 +127,82 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_get<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    /// 
+    /// The same handler can be mapped to several patterns at once:
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get(["/hello", "/hi"], || async {
+    ///    ok!("Hello World!")
+    /// });
+    ///# app.run().await
+    ///# }
+    /// ```
+    /// 
+    /// Route parameters can be constrained with `{name:constraint}`, so the segments 
+    /// that don't satisfy the constraint don't match the route and result in `404 Not Found`. 
+    /// The supported constraints are `integer`, `number`, `uuid`, `alpha` and `alphanumeric`.
+    /// 
+    /// If several parameters are mapped at the same position, a static segment is matched first, 
+    /// then the constrained ones in the order listed above and then the unconstrained one.
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/users/{id:integer}", |id: i64| async move {
+    ///    ok!("User {id}")
+    /// });
+    /// app.map_get("/users/{name}", |name: String| async move {
+    ///    ok!("User {name}")
+    /// });
+    ///# app.run().await
+    ///# }
+    /// ```
+    /// 
+    /// The last segment can be a catch-all parameter `{*name}` that binds the rest of the path, 
+    /// including slashes. It has the lowest precedence, so any other matching route wins over it.
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// // GET /files/docs/report.txt binds `path` to `docs/report.txt`
+    /// app.map_get("/files/{*path}", |path: String| async move {
+    ///    ok!("File {path}")
+    /// });
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn map_get<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static
     {
         let handler = Func::new(handler);
         let endpoints = self.pipeline.endpoints_mut();
-        endpoints.map_route(Method::GET, pattern, handler.clone());
-        
-        let head = Method::HEAD;
-        if !endpoints.contains(&head, pattern) { 
-            endpoints.map_route(head, pattern, handler.clone());
+        let mut routes = Vec::new();
+        for pattern in pattern.patterns() {
+            endpoints.map_route(Method::GET, pattern, handler.clone());
+            routes.push((Method::GET, pattern.to_owned()));
+
+            let head = Method::HEAD;
+            if !endpoints.contains(&head, pattern) {
+                endpoints.map_route(head.clone(), pattern, handler.clone());
+                routes.push((head, pattern.to_owned()));
+            }
         }
-        self
+        RouteBuilder::new(self, routes)
     }
 
     /// Adds a request handler that matches HTTP POST requests for the specified pattern.
@@ -89,17 +222,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_post<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_post<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::POST, pattern, handler);
-        self
+        self.map_route(Method::POST, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP PUT requests for the specified pattern.
@@ -118,17 +248,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_put<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_put<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::PUT, pattern, handler);
-        self
+        self.map_route(Method::PUT, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP PATCH requests for the specified pattern.
@@ -147,17 +274,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_patch<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_patch<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::PATCH, pattern, handler);
-        self
+        self.map_route(Method::PATCH, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP DELETE requests for the specified pattern.
@@ -176,17 +300,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_delete<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_delete<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::DELETE, pattern, handler);
-        self
+        self.map_route(Method::DELETE, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP HEAD requests for the specified pattern.
@@ -205,17 +326,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_head<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_head<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::HEAD, pattern, handler);
-        self
+        self.map_route(Method::HEAD, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP OPTIONS requests for the specified pattern.
@@ -234,17 +352,14 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_options<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_options<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
     where
+        P: RoutePatterns,
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
         Args: FromRequest + Send + Sync + 'static,
     {
-        let handler = Func::new(handler);
-        self.pipeline
-            .endpoints_mut()
-            .map_route(Method::OPTIONS, pattern, handler);
-        self
+        self.map_route(Method::OPTIONS, pattern, handler)
     }
 
     /// Adds a request handler that matches HTTP TRACE requests for the specified pattern.
@@ -263,7 +378,81 @@ impl App {
     ///# app.run().await
     ///# }
     /// ```
-    pub fn map_trace<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+    pub fn map_trace<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
+    where
+        P: RoutePatterns,
+        F: GenericHandler<Args, Output = R>,
+        R: IntoResponse + 'static,
+        Args: FromRequest + Send + Sync + 'static,
+    {
+        self.map_route(Method::TRACE, pattern, handler)
+    }
+
+    /// Adds a request handler that matches HTTP CONNECT requests for the specified pattern.
+    /// 
+    /// CONNECT requests usually have an authority-form target, e.g. `example.com:443`, 
+    /// which is matched by the root `/` pattern.
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, status};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_connect("/", || async {
+    ///    status!(405)
+    /// });
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn map_connect<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_>
+    where
+        P: RoutePatterns,
+        F: GenericHandler<Args, Output = R>,
+        R: IntoResponse + 'static,
+        Args: FromRequest + Send + Sync + 'static,
+    {
+        self.map_route(Method::CONNECT, pattern, handler)
+    }
+}
+
+/// Describes a mapped route
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// HTTP Verb the route is mapped to
+    pub method: Method,
+    
+    /// Route pattern, e.g. `/user/{id}`
+    pub pattern: String,
+    
+    /// Host the route is mapped for with [`App::host`], 
+    /// or `None` if it is one of the default routes
+    pub host: Option<String>
+}
+
+impl App {
+    /// Adds a request handler that is called for requests to unmapped routes starting with the `prefix`.
+    /// 
+    /// If several fallback prefixes match, the longest one wins. 
+    /// Requests that don't match any prefix get the default `404 Not Found` response.
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, status};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_fallback_for("/api", || async {
+    ///    status!(404, { "error": "resource not found" })
+    /// });
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn map_fallback_for<F, R, Args>(&mut self, prefix: &str, handler: F) -> &mut Self
     where
         F: GenericHandler<Args, Output = R>,
         R: IntoResponse + 'static,
@@ -272,7 +461,429 @@ impl App {
         let handler = Func::new(handler);
         self.pipeline
             .endpoints_mut()
-            .map_route(Method::TRACE, pattern, handler);
+            .map_fallback(prefix, handler);
+        self
+    }
+    
+    /// Returns a list of all mapped routes, including the ones mapped for specific hosts,
+    /// sorted by host, route pattern and HTTP Verb, the default routes come first
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/hello", || async {
+    ///    ok!("Hello World!")
+    /// });
+    /// 
+    /// for route in app.routes() {
+    ///     println!("{} {}", route.method, route.pattern);
+    /// }
+    /// ```
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let mut routes = self.pipeline
+            .all_endpoints()
+            .flat_map(|(host, endpoints)| endpoints.routes(host))
+            .collect::<Vec<_>>();
+        routes.sort_by(|a, b| a.host
+            .cmp(&b.host)
+            .then_with(|| a.pattern.cmp(&b.pattern))
+            .then_with(|| a.method.as_str().cmp(b.method.as_str())));
+        routes
+    }
+    
+    #[inline]
+    fn map_route<P, F, R, Args>(&mut self, method: Method, pattern: P, handler: F) -> RouteBuilder<'_>
+    where
+        P: RoutePatterns,
+        F: GenericHandler<Args, Output = R>,
+        R: IntoResponse + 'static,
+        Args: FromRequest + Send + Sync + 'static,
+    {
+        let handler = Func::new(handler);
+        let endpoints = self.pipeline.endpoints_mut();
+        let mut routes = Vec::new();
+        for pattern in pattern.patterns() {
+            endpoints.map_route(method.clone(), pattern, handler.clone());
+            routes.push((method.clone(), pattern.to_owned()));
+        }
+        RouteBuilder::new(self, routes)
+    }
+}
+
+mod target {
+    use crate::App;
+
+    /// Describes the one that maps routes, either [`App`] or [`RouteGroup`](super::RouteGroup)
+    /// 
+    /// The trait is public only to be used in the bounds of [`RouteBuilder`](super::RouteBuilder), 
+    /// it is not reachable outside the crate, so it can't be implemented for other types.
+    pub trait RouteTarget {
+        /// Returns the app the routes are mapped into
+        fn app_mut(&mut self) -> &mut App;
+    }
+}
+
+/// Represents a just mapped route that could be further configured
+/// 
+/// It is returned by the `map_*` methods of both [`App`] and [`RouteGroup`] 
+/// and dereferences to the one that mapped the route, so the route mapping calls can still be chained.
+pub struct RouteBuilder<'a, G: RouteTarget = App> {
+    target: &'a mut G,
+    routes: Vec<(Method, String)>
+}
+
+impl<'a, G: RouteTarget> RouteBuilder<'a, G> {
+    /// Creates a new route builder for the mapped `routes`
+    fn new(target: &'a mut G, routes: Vec<(Method, String)>) -> Self {
+        Self { target, routes }
+    }
+    
+    /// Attaches a typed metadata value to the route, 
+    /// replacing the previously attached value of the same type if any.
+    /// 
+    /// The metadata can be read in middleware via 
+    /// [`HttpContext::route_metadata`](crate::middleware::HttpContext::route_metadata)
+    /// or in request handler via [`HttpRequest::route_metadata`](crate::HttpRequest::route_metadata).
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    /// #[derive(Clone)]
+    /// struct AuthLevel(u8);
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/admin", || async {
+    ///    ok!("Hello Admin!")
+    /// })
+    /// .with_metadata(AuthLevel(2));
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn with_metadata<T: Clone + Send + Sync + 'static>(self, value: T) -> Self {
+        let endpoints = self.target.app_mut().pipeline.endpoints_mut();
+        for (method, pattern) in self.routes.iter() {
+            if let Some(metadata) = endpoints.metadata_mut(method, pattern) {
+                metadata.insert(value.clone());
+            }
+        }
+        self
+    }
+    
+    /// Restricts the request content types accepted by the route. 
+    /// 
+    /// Requests without `Content-Type` header or with a content type that isn't in the list 
+    /// are rejected with `415 Unsupported Media Type` before the request body is read.
+    /// A wildcard subtype (e.g. `text/*`) is also supported.
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, Json, ok};
+    ///# #[derive(serde::Deserialize)]
+    ///# struct User;
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_post("/user", |user: Json<User>| async move {
+    ///    ok!()
+    /// })
+    /// .accepts(["application/json"]);
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn accepts<I, S>(self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>
+    {
+        let content_types = content_types
+            .into_iter()
+            .map(|content_type| content_type.as_ref().trim().to_ascii_lowercase())
+            .collect();
+        self.with_metadata(AcceptedContentTypes(content_types))
+    }
+    
+    /// Sets a list of request headers that are required by the route
+    /// 
+    /// Requests without any of these headers are rejected with `400 Bad Request` 
+    /// that names the missing header. It's checked in addition to the headers 
+    /// configured with [`App::with_required_headers`].
+    /// 
+    /// # Panics
+    /// If any of the `headers` is not a valid header name
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/report", || async {
+    ///    ok!("Report")
+    /// })
+    /// .require_headers(["x-api-version"]);
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn require_headers<I, S>(self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>
+    {
+        self.with_metadata(RequiredHeaders::new(headers))
+    }
+    
+    /// Sets a request handling timeout for the route, including streaming the response body,
+    /// overriding the one configured with [`App::with_request_timeout`].
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/report", || async {
+    ///    ok!("Report")
+    /// })
+    /// .timeout(Duration::from_secs(60));
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.with_metadata(RequestTimeout::Total(timeout))
+    }
+    
+    /// Sets a time-to-first-byte timeout for the route, so only the time until the response head 
+    /// is ready is limited while streaming the response body afterward is not.
+    /// 
+    /// It overrides the timeout configured with [`App::with_request_timeout`].
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use volga::{App, HttpRequest, stream};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_post("/echo", |req: HttpRequest| async move {
+    ///    stream!(req.into_boxed_body())
+    /// })
+    /// .ttfb_timeout(Duration::from_secs(5));
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn ttfb_timeout(self, timeout: Duration) -> Self {
+        self.with_metadata(RequestTimeout::FirstByte(timeout))
+    }
+    
+    /// Disables the timeout configured with [`App::with_request_timeout`] for the route, 
+    /// e.g. for long-lived streams.
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use volga::{App, HttpRequest, stream};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new().with_request_timeout(Duration::from_secs(30));
+    /// 
+    /// app.map_get("/events", |req: HttpRequest| async move {
+    ///    stream!(req.into_boxed_body(), [("content-type", "text/event-stream")])
+    /// })
+    /// .no_timeout();
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn no_timeout(self) -> Self {
+        self.with_metadata(RequestTimeout::Disabled)
+    }
+    
+    /// Sets a request body limit (in bytes) for the route, requests with a larger body 
+    /// are rejected with `413 Payload Too Large`.
+    /// 
+    /// It overrides the limit configured with [`App::with_body_limit`].
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, File, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_post("/upload", |file: File| async move {
+    ///    file.save_as("example.txt").await?;
+    ///    ok!()
+    /// })
+    /// .body_limit(100 * 1024 * 1024);
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn body_limit(self, limit: usize) -> Self {
+        self.with_metadata(RequestBodyLimit::Enabled(limit))
+    }
+    
+    /// Disables the request body limit configured with [`App::with_body_limit`] for the route
+    pub fn no_body_limit(self) -> Self {
+        self.with_metadata(RequestBodyLimit::Disabled)
+    }
+    
+    /// Limits the number of requests that are handled by the route concurrently,
+    /// requests over the limit are rejected with `503 Service Unavailable`
+    /// 
+    /// # Panics
+    /// If `max` is zero
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/report", || async {
+    ///    ok!("Report")
+    /// })
+    /// .max_concurrency(4);
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn max_concurrency(self, max: usize) -> Self {
+        self.with_concurrency_limiter(ConcurrencyLimiter::new(max))
+    }
+    
+    /// Limits the number of requests that are handled by the route concurrently,
+    /// requests over the limit wait for a free slot up to `timeout` 
+    /// and are rejected with `503 Service Unavailable` afterward
+    /// 
+    /// # Panics
+    /// If `max` is zero
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_get("/report", || async {
+    ///    ok!("Report")
+    /// })
+    /// .max_concurrency_with_timeout(4, Duration::from_secs(1));
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn max_concurrency_with_timeout(self, max: usize, timeout: Duration) -> Self {
+        self.with_concurrency_limiter(ConcurrencyLimiter::new(max).with_timeout(timeout))
+    }
+    
+    /// Limits the number of requests that are handled by the route concurrently 
+    /// with the configured [`ConcurrencyLimiter`]
+    /// 
+    /// If the route is mapped to several patterns, the limit is shared between them.
+    pub fn with_concurrency_limiter(self, limiter: ConcurrencyLimiter) -> Self {
+        self.with_metadata(limiter)
+    }
+}
+
+/// Holds a list of request content types accepted by a route
+#[derive(Clone)]
+pub(crate) struct AcceptedContentTypes(Vec<String>);
+
+impl AcceptedContentTypes {
+    /// Checks whether the request `Content-Type` header is in the list of accepted content types
+    pub(crate) fn is_accepted(&self, headers: &HeaderMap) -> bool {
+        let Some(content_type) = headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok()) else {
+            return false;
+        };
+        
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim();
+        
+        self.0.iter().any(|accepted| match accepted.strip_suffix("/*") {
+            Some("*") => true,
+            Some(prefix) => essence
+                .split_once('/')
+                .is_some_and(|(media_type, _)| media_type.eq_ignore_ascii_case(prefix)),
+            None => accepted.eq_ignore_ascii_case(essence)
+        })
+    }
+}
+
+/// Holds a list of request headers required by a route or the whole app
+#[derive(Clone, Default)]
+pub(crate) struct RequiredHeaders(Vec<HeaderName>);
+
+impl RequiredHeaders {
+    /// Creates a list of required headers
+    /// 
+    /// # Panics
+    /// If any of the `headers` is not a valid header name
+    pub(crate) fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>
+    {
+        let headers = headers
+            .into_iter()
+            .map(|name| HeaderName::try_from(name.as_ref().trim())
+                .unwrap_or_else(|_| panic!("invalid header name: {}", name.as_ref())))
+            .collect();
+        Self(headers)
+    }
+    
+    /// Returns an error that names the first required header that is missing in the request `headers`
+    pub(crate) fn check(&self, headers: &HeaderMap) -> Result<(), Error> {
+        match self.0.iter().find(|name| !headers.contains_key(*name)) {
+            Some(name) => Err(Error::client_error(format!("Missing required header: {name}"))),
+            None => Ok(())
+        }
+    }
+}
+
+impl<G: RouteTarget> Deref for RouteBuilder<'_, G> {
+    type Target = G;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.target
+    }
+}
+
+impl<G: RouteTarget> DerefMut for RouteBuilder<'_, G> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.target
+    }
+}
+
+impl RouteTarget for App {
+    #[inline]
+    fn app_mut(&mut self) -> &mut App {
         self
     }
 }
@@ -283,6 +894,13 @@ pub struct RouteGroup<'a> {
     prefix: &'a str,
 }
 
+impl RouteTarget for RouteGroup<'_> {
+    #[inline]
+    fn app_mut(&mut self) -> &mut App {
+        self.app
+    }
+}
+
 macro_rules! define_route_group_methods({$($method:ident)*} => {
     impl <'a> RouteGroup<'a> {
         /// Creates a new route group
@@ -292,15 +910,22 @@ macro_rules! define_route_group_methods({$($method:ident)*} => {
             
         $(
         #[doc = concat!("See [`App::", stringify!($method), "`] for more details.")]
-        pub fn $method<F, R, Args>(&mut self, pattern: &str, handler: F) -> &mut Self
+        /// 
+        /// Returns a [`RouteBuilder`] that configures the mapped route 
+        /// and dereferences to the group, so the next routes are still mapped with its prefix.
+        pub fn $method<P, F, R, Args>(&mut self, pattern: P, handler: F) -> RouteBuilder<'_, Self>
         where
+            P: RoutePatterns,
             F: GenericHandler<Args, Output = R>,
             R: IntoResponse + 'static,
             Args: FromRequest + Send + Sync + 'static
         {
-            let pattern = [self.prefix, pattern].concat();
-            self.app.$method(&pattern, handler);
-            self
+            let patterns: Vec<String> = pattern
+                .patterns()
+                .map(|pattern| [self.prefix, pattern].concat())
+                .collect();
+            let routes = self.app.$method(patterns, handler).routes;
+            RouteBuilder::new(self, routes)
         }
         )*
         }
@@ -315,4 +940,59 @@ define_route_group_methods! {
     map_head
     map_options
     map_trace
+    map_connect
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::CONTENT_TYPE, HeaderMap};
+    use super::{AcceptedContentTypes, RequiredHeaders};
+    
+    fn headers(content_type: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn it_accepts_listed_content_type() {
+        let accepted = AcceptedContentTypes(vec!["application/json".into()]);
+        
+        assert!(accepted.is_accepted(&headers("application/json")));
+        assert!(accepted.is_accepted(&headers("Application/JSON; charset=utf-8")));
+    }
+
+    #[test]
+    fn it_rejects_not_listed_content_type() {
+        let accepted = AcceptedContentTypes(vec!["application/json".into()]);
+
+        assert!(!accepted.is_accepted(&headers("text/plain")));
+        assert!(!accepted.is_accepted(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn it_accepts_wildcard_content_type() {
+        let accepted = AcceptedContentTypes(vec!["text/*".into()]);
+
+        assert!(accepted.is_accepted(&headers("text/plain")));
+        assert!(accepted.is_accepted(&headers("text/csv")));
+        assert!(!accepted.is_accepted(&headers("application/json")));
+    }
+
+    #[test]
+    fn it_names_missing_required_header() {
+        let required = RequiredHeaders::new(["Content-Type", "x-api-version"]);
+
+        let err = required.check(&headers("text/plain")).unwrap_err();
+
+        assert_eq!(err.status, 400);
+        assert_eq!(err.to_string(), "Missing required header: x-api-version");
+    }
+
+    #[test]
+    fn it_passes_if_required_headers_are_present() {
+        let required = RequiredHeaders::new(["content-type"]);
+
+        assert!(required.check(&headers("text/plain")).is_ok());
+    }
 }
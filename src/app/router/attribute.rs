@@ -0,0 +1,43 @@
+﻿//! Attribute routing
+
+use crate::App;
+
+/// Describes a request handler declared with a route attribute, e.g. `#[get("/hello")]`, 
+/// that maps itself to the [`App`]
+/// 
+/// It's implemented by the route attributes and rarely needs to be implemented manually.
+pub trait MapRoute {
+    /// Maps the request handler to the `app`
+    fn map(self, app: &mut App);
+}
+
+/// Maps request handlers declared with route attributes to the [`App`]
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{App, HttpResult, Json, ok, register_handlers};
+/// use volga::routing::{get, post};
+///
+/// #[get("/hello/{name}")]
+/// async fn hello(name: String) -> HttpResult {
+///     ok!("Hello {name}!")
+/// }
+///
+/// #[post("/echo")]
+/// async fn echo(body: Json<serde_json::Value>) -> HttpResult {
+///     ok!(body.into_inner())
+/// }
+///
+///# #[tokio::main]
+///# async fn main() -> std::io::Result<()> {
+/// let mut app = App::new();
+/// register_handlers!(app, hello, echo);
+///# app.run().await
+///# }
+/// ```
+#[macro_export]
+macro_rules! register_handlers {
+    ($app:expr, $($handler:expr),+ $(,)?) => {
+        $( $crate::routing::MapRoute::map($handler, &mut $app); )+
+    };
+}
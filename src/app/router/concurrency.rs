@@ -0,0 +1,140 @@
+﻿//! Per-route concurrency limiting
+
+use hyper::StatusCode;
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::{OwnedSemaphorePermit, Semaphore}, time};
+
+/// Limits the number of requests that are handled by a route concurrently
+/// 
+/// Requests that exceed the limit are rejected with `503 Service Unavailable` by default,
+/// instead of being queued. Optionally, they can wait for a free slot for a limited time.
+/// 
+/// The slot is released once the request handler returns the response, 
+/// as well as if it panics or the client disconnects.
+/// 
+/// # Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use volga::{App, ok, routing::ConcurrencyLimiter};
+///
+///# #[tokio::main]
+///# async fn main() -> std::io::Result<()> {
+/// let mut app = App::new();
+/// 
+/// app.map_get("/report", || async {
+///    ok!("Report")
+/// })
+/// .with_concurrency_limiter(ConcurrencyLimiter::new(4)
+///     .with_timeout(Duration::from_secs(1))
+///     .with_rejection_status(429));
+///# app.run().await
+///# }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    timeout: Option<Duration>,
+    rejection_status: StatusCode
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a new limiter that allows up to `max` concurrent requests
+    /// 
+    /// # Panics
+    /// If `max` is zero
+    pub fn new(max: usize) -> Self {
+        assert!(max > 0, "max concurrency must be greater than zero");
+        Self {
+            semaphore: Arc::new(Semaphore::new(max)),
+            timeout: None,
+            rejection_status: StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
+    
+    /// Sets the time that a request waits for a free slot before it's rejected
+    /// 
+    /// Default: requests are rejected immediately
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    
+    /// Sets the status code of responses to rejected requests
+    /// 
+    /// Default: `503 Service Unavailable`
+    /// 
+    /// # Panics
+    /// If the `status` is not a client or server error status code (`400..=599`)
+    pub fn with_rejection_status(mut self, status: u16) -> Self {
+        self.rejection_status = StatusCode::from_u16(status)
+            .ok()
+            .filter(|status| status.is_client_error() || status.is_server_error())
+            .unwrap_or_else(|| panic!("rejection status must be an error status code, got: {status}"));
+        self
+    }
+    
+    /// Returns the status code of responses to rejected requests
+    #[inline]
+    pub(crate) fn rejection_status(&self) -> StatusCode {
+        self.rejection_status
+    }
+    
+    /// Acquires a slot for the request, returns `None` if there is no free slot
+    pub(crate) async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        let semaphore = self.semaphore.clone();
+        match self.timeout {
+            None => semaphore.try_acquire_owned().ok(),
+            Some(timeout) => time::timeout(timeout, semaphore.acquire_owned())
+                .await
+                .ok()
+                .and_then(Result::ok)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::ConcurrencyLimiter;
+
+    #[tokio::test]
+    async fn it_rejects_requests_over_limit() {
+        let limiter = ConcurrencyLimiter::new(1);
+
+        let permit = limiter.acquire().await;
+
+        assert!(permit.is_some());
+        assert!(limiter.acquire().await.is_none());
+
+        drop(permit);
+
+        assert!(limiter.acquire().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_free_slot() {
+        let limiter = ConcurrencyLimiter::new(1).with_timeout(Duration::from_millis(500));
+        let permit = limiter.acquire().await;
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            drop(permit);
+        });
+
+        assert!(limiter.acquire().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_request_if_wait_times_out() {
+        let limiter = ConcurrencyLimiter::new(1).with_timeout(Duration::from_millis(50));
+        let _permit = limiter.acquire().await;
+
+        assert!(limiter.acquire().await.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_rejection_status_is_not_error() {
+        _ = ConcurrencyLimiter::new(1).with_rejection_status(200);
+    }
+}
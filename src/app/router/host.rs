@@ -0,0 +1,87 @@
+﻿//! Host-based virtual routing
+
+use hyper::{header::HOST, http::uri::Authority, HeaderMap, Uri};
+use crate::App;
+
+impl App {
+    /// Maps a set of routes that are matched only for requests to the `host`
+    /// 
+    /// The host is resolved from the `Host` header, or the `:authority` for HTTP/2, 
+    /// before path routing, ignoring the port and letter case. Requests to hosts 
+    /// that have no routes mapped this way are routed with the default routes, i.e. the ones mapped outside of `host`.
+    /// If the path isn't mapped for the matched host, the default routes are not tried.
+    /// 
+    /// Route groups, fallbacks and route settings can be used within the `map` closure as usual, 
+    /// while middlewares are shared by all hosts. [`App::routes`] lists the routes of all hosts, 
+    /// each tagged with its [`RouteInfo::host`](crate::routing::RouteInfo::host).
+    /// 
+    /// # Examples
+    /// ```no_run
+    /// use volga::{App, ok};
+    ///
+    ///# #[tokio::main]
+    ///# async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.host("api.example.com", |api| {
+    ///     api.map_get("/users", || async { ok!("users") });
+    /// });
+    /// app.host("admin.example.com", |admin| {
+    ///     admin.map_get("/users", || async { ok!("admin users") });
+    /// });
+    /// app.map_get("/", || async { ok!("home") });
+    ///# app.run().await
+    ///# }
+    /// ```
+    pub fn host<F>(&mut self, host: &str, map: F) -> &mut Self
+    where
+        F: FnOnce(&mut App)
+    {
+        let previous = self.pipeline.set_host(Some(normalize(host)));
+        map(self);
+        self.pipeline.set_host(previous);
+        self
+    }
+}
+
+/// Reads the normalized request host, without the port, from the `Host` header or the [`Uri`] authority
+#[inline]
+pub(crate) fn request_host(uri: &Uri, headers: &HeaderMap) -> Option<String> {
+    let host = headers
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .or_else(|| uri.authority().map(Authority::as_str))?;
+    let authority = host.parse::<Authority>().ok()?;
+    Some(normalize(authority.host()))
+}
+
+/// Trims the trailing dot and converts the `host` to lowercase
+#[inline]
+pub(crate) fn normalize(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::HOST, HeaderMap, Uri};
+    use super::request_host;
+
+    #[test]
+    fn it_reads_host_from_header_ignoring_port_and_case() {
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, "API.Example.com.:8080".parse().unwrap());
+
+        let host = request_host(&Uri::from_static("/users"), &headers);
+
+        assert_eq!(host.as_deref(), Some("api.example.com"));
+    }
+
+    #[test]
+    fn it_reads_host_from_uri_authority() {
+        let uri = Uri::from_static("https://admin.example.com/users");
+
+        let host = request_host(&uri, &HeaderMap::new());
+
+        assert_eq!(host.as_deref(), Some("admin.example.com"));
+    }
+}
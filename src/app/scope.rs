@@ -1,20 +1,29 @@
 ﻿use tokio_util::sync::CancellationToken;
 use futures_util::future::BoxFuture;
-use std::sync::Weak;
+use std::sync::{Arc, Weak, atomic::{AtomicUsize, Ordering}};
+use tokio::time::{self, Instant};
 
 use hyper::{
-    header::{HeaderValue, CONTENT_LENGTH, ALLOW}, 
+    header::{HeaderValue, CONTENT_LENGTH, CONTENT_TYPE, ALLOW, LOCATION}, 
     body::{Body, SizeHint, Incoming}, 
     Request, 
     service::Service, 
     Method, 
-    HeaderMap
+    HeaderMap,
+    Uri
 };
 
 use crate::{
-    app::AppInstance, 
+    app::{AppInstance, router::{AcceptedContentTypes, RequiredHeaders, concurrency::ConcurrencyLimiter}}, 
     error::{Error, handler::call_weak_err_handler}, 
-    http::endpoints::RouteOption,
+    http::{
+        endpoints::{args::{conn_info::ConnInfo, received_at::ReceivedAt, uri_parts::UriParts}, route::{PathArguments, RouteMetadata}, RouteOption}, 
+        request::{request_timeout::{RequestTimeout, TimeoutBody}, request_body_limit::RequestBodyLimit, merge_slashes::merge_slashes, canonical_host::CanonicalHost},
+        body::GuardedBody,
+        CorrelationId,
+        StatusCode,
+        sse::SSE_CONTENT_TYPE
+    },
     HttpResponse, HttpRequest, HttpBody, HttpResult,
     status
 };
@@ -26,7 +35,10 @@ use crate::middleware::HttpContext;
 #[derive(Clone)]
 pub(crate) struct Scope {
     pub(crate) shared: Weak<AppInstance>,
-    pub(crate) cancellation_token: CancellationToken
+    pub(crate) cancellation_token: CancellationToken,
+    pub(crate) conn_info: ConnInfo,
+    /// Number of requests received over the current connection
+    requests: Arc<AtomicUsize>
 }
 
 impl Service<Request<Incoming>> for Scope {
@@ -36,26 +48,34 @@ impl Service<Request<Incoming>> for Scope {
 
     #[inline]
     fn call(&self, request: Request<Incoming>) -> Self::Future {
+        let served = self.requests.fetch_add(1, Ordering::Relaxed) + 1;
         Box::pin(Self::handle_request(
             request, 
             self.shared.clone(),
-            self.cancellation_token.clone()
+            self.cancellation_token.clone(),
+            self.conn_info,
+            served
         ))
     }
 }
 
 impl Scope {
-    pub(crate) fn new(shared: Weak<AppInstance>) -> Self {
+    pub(crate) fn new(shared: Weak<AppInstance>, conn_info: ConnInfo, cancellation_token: CancellationToken) -> Self {
         Self {
-            cancellation_token: CancellationToken::new(),
-            shared
+            cancellation_token,
+            requests: Arc::new(AtomicUsize::new(0)),
+            shared,
+            conn_info
         }
     }
     
+    #[cfg_attr(feature = "http2", allow(unused_variables))]
     pub(super) async fn handle_request(
         request: Request<Incoming>, 
         shared: Weak<AppInstance>,
-        cancellation_token: CancellationToken
+        cancellation_token: CancellationToken,
+        conn_info: ConnInfo,
+        served: usize
     ) -> HttpResult {
         let shared = match shared.upgrade() {
             Some(shared) => shared,
@@ -66,54 +86,257 @@ impl Scope {
             }
         };
         
+        let received_at = ReceivedAt::now();
+        let guard = shared.stats.track();
+        
+        let uri = request.uri().clone();
+        let response = Self::dispatch(request, &shared, cancellation_token, conn_info, received_at).await;
+        let response = shared.pipeline.map_response(response, &uri).await;
+        
+        // The request stays in flight until its response body is sent
+        let response = response.map(|response| response.map(|body| GuardedBody::wrap(body, guard)));
+        
+        #[cfg(all(feature = "http1", not(feature = "http2")))]
+        if shared.http_config.is_connection_exhausted(served) {
+            return response.map(|mut response| {
+                response.headers_mut().insert(hyper::header::CONNECTION, HeaderValue::from_static("close"));
+                response
+            });
+        }
+        response
+    }
+    
+    #[inline]
+    async fn dispatch(
+        mut request: Request<Incoming>, 
+        shared: &AppInstance,
+        cancellation_token: CancellationToken,
+        conn_info: ConnInfo,
+        received_at: ReceivedAt
+    ) -> HttpResult {
+        // The original path is kept to bind catch-all parameters with its slashes
+        let mut unmerged_path = None;
+        if shared.merge_slashes {
+            if shared.preserve_catch_all_slashes && request.uri().path().contains("//") {
+                unmerged_path = Some(request.uri().path().to_owned());
+            }
+            merge_slashes(request.uri_mut());
+        }
+        
+        if let Some(canonical_host) = &shared.canonical_host {
+            if let Some(response) = Self::redirect_to_canonical_host(canonical_host, &request, shared) {
+                return response;
+            }
+        }
+        
         let pipeline = &shared.pipeline;
-        match pipeline.endpoints().get_endpoint(request.method(), request.uri()) {
-            RouteOption::RouteNotFound => status!(404),
-            RouteOption::MethodNotFound(allowed) => status!(405, [
-                (ALLOW, allowed)
-            ]),
-            RouteOption::Ok(endpoint_context) => {
-                let (handler, params) = endpoint_context.into_parts();
-                
-                #[cfg(feature = "di")]
-                let mut request = HttpRequest::new(request, shared.container.create_scope())
-                    .into_limited(shared.body_limit);
-                
-                #[cfg(not(feature = "di"))]
-                let mut request = HttpRequest::new(request).into_limited(shared.body_limit);
-                
-                let extensions = request.extensions_mut();
-                extensions.insert(cancellation_token);
-                extensions.insert(params);
-                extensions.insert(shared.body_limit);
-                
-                let request_method = request.method().clone();
-                let uri = request.uri().clone();
-                let error_handler = pipeline.error_handler();
-                
-                #[cfg(feature = "middleware")]
-                let response = if pipeline.has_middleware_pipeline() {
-                    let ctx = HttpContext::new(request, handler, error_handler.clone());
-                    pipeline.execute(ctx).await
-                } else {
-                    handler.call(request).await
-                };
-                #[cfg(not(feature = "middleware"))]
-                let response = handler.call(request).await;
-                
-                match response {
-                    Err(err) => call_weak_err_handler(error_handler, &uri, err).await,
-                    Ok(response) if request_method != Method::HEAD => Ok(response),
-                    Ok(mut response) => {
-                        Self::keep_content_length(response.size_hint(), response.headers_mut());
-                        *response.body_mut() = HttpBody::empty();
-                        Ok(response)
+        let endpoints = pipeline.endpoints(request.uri(), request.headers());
+        let route_option = match &unmerged_path {
+            Some(path) => endpoints.get_endpoint_with_slashes(request.method(), path),
+            None => endpoints.get_endpoint(request.method(), request.uri())
+        };
+        let (handler, metadata, params) = match route_option {
+            RouteOption::RouteNotFound => match endpoints.get_fallback(request.uri()) {
+                Some(handler) => (handler, RouteMetadata::default(), PathArguments::new()),
+                None => return shared.error_format.status(StatusCode::NOT_FOUND, request.uri())
+            },
+            RouteOption::MethodNotFound(allowed) => return shared.error_format
+                .status(StatusCode::METHOD_NOT_ALLOWED, request.uri())
+                .map(|mut response| {
+                    if let Ok(allowed) = HeaderValue::from_str(&allowed) {
+                        response.headers_mut().insert(ALLOW, allowed);
                     }
+                    response
+                }),
+            RouteOption::Ok(endpoint_context) => endpoint_context.into_parts()
+        };
+        let route = metadata.pattern().cloned();
+        
+        if let Some(accepted) = metadata.get::<AcceptedContentTypes>() {
+            if !accepted.is_accepted(request.headers()) {
+                Self::log_rejection("unsupported_media_type", StatusCode::UNSUPPORTED_MEDIA_TYPE, request.method(), request.uri(), route.as_deref());
+                return shared.error_format.status(StatusCode::UNSUPPORTED_MEDIA_TYPE, request.uri());
+            }
+        }
+        
+        let required_headers = shared.required_headers
+            .check(request.headers())
+            .and_then(|_| metadata
+                .get::<RequiredHeaders>()
+                .map_or(Ok(()), |required| required.check(request.headers())));
+        if let Err(err) = required_headers {
+            Self::log_rejection("missing_required_header", err.status, request.method(), request.uri(), route.as_deref());
+            return call_weak_err_handler(pipeline.error_handler(), request.uri(), err).await;
+        }
+        
+        let body_limit = metadata
+            .get::<RequestBodyLimit>()
+            .copied()
+            .unwrap_or(shared.body_limit);
+        if body_limit.is_exceeded_by(request.headers()) {
+            Self::log_rejection("payload_too_large", StatusCode::PAYLOAD_TOO_LARGE, request.method(), request.uri(), route.as_deref());
+            return shared.error_format.status(StatusCode::PAYLOAD_TOO_LARGE, request.uri());
+        }
+        
+        let _permit = match metadata.get::<ConcurrencyLimiter>() {
+            None => None,
+            Some(limiter) => match limiter.acquire().await {
+                Some(permit) => Some(permit),
+                None => {
+                    Self::log_rejection("concurrency_limited", limiter.rejection_status(), request.method(), request.uri(), route.as_deref());
+                    return shared.error_format.status(limiter.rejection_status(), request.uri());
                 }
             }
+        };
+        
+        let metadata_timeout = metadata.get::<RequestTimeout>().copied();
+        
+        #[cfg(feature = "di")]
+        let mut request = HttpRequest::new(request, shared.container.create_scope())
+            .into_drained(shared.body_drain_limit)
+            .into_limited(body_limit);
+        
+        #[cfg(not(feature = "di"))]
+        let mut request = HttpRequest::new(request)
+            .into_drained(shared.body_drain_limit)
+            .into_limited(body_limit);
+        
+        // A request-scoped token, so a timed-out request is cancelled 
+        // without affecting the other requests of the same connection
+        let cancellation_token = cancellation_token.child_token();
+        
+        let extensions = request.extensions_mut();
+        extensions.insert(cancellation_token.clone());
+        extensions.insert(conn_info);
+        extensions.insert(received_at);
+        extensions.insert(params);
+        extensions.insert(metadata);
+        extensions.insert(body_limit);
+        extensions.insert(shared.unprocessable_status);
+        extensions.insert(shared.uri_settings);
+        #[cfg(feature = "multipart")]
+        extensions.insert(shared.multipart_limits);
+        #[cfg(any(
+            feature = "compression-brotli",
+            feature = "compression-gzip",
+            feature = "compression-zstd",
+            feature = "compression-full"
+        ))]
+        extensions.insert(shared.compression_config);
+        
+        if let Some(base_domain) = &shared.base_domain {
+            let subdomain = base_domain.subdomain(request.uri(), request.headers());
+            request.extensions_mut().insert(subdomain);
+        }
+        
+        let request_method = request.method().clone();
+        let uri = request.uri().clone();
+        let error_handler = pipeline.error_handler();
+        
+        let is_route_timeout = metadata_timeout.is_some();
+        let timeout = metadata_timeout.unwrap_or(shared.request_timeout);
+        let correlation_id = shared.correlation_id_header
+            .as_ref()
+            .and_then(|name| CorrelationId::from_headers(name, request.headers()));
+        
+        #[cfg(feature = "middleware")]
+        let response = async {
+            if pipeline.has_middleware_pipeline() {
+                let ctx = HttpContext::new(request, handler, error_handler.clone());
+                pipeline.execute(ctx).await
+            } else {
+                handler.call(request).await
+            }
+        };
+        #[cfg(not(feature = "middleware"))]
+        let response = handler.call(request);
+        
+        let response = CorrelationId::scope(correlation_id, response);
+        
+        let response = match timeout {
+            RequestTimeout::Disabled => response.await,
+            RequestTimeout::FirstByte(timeout) => match time::timeout(timeout, response).await {
+                Ok(response) => response,
+                Err(_) => {
+                    cancellation_token.cancel();
+                    Self::log_rejection("timeout", StatusCode::GATEWAY_TIMEOUT, &request_method, &uri, route.as_deref());
+                    return shared.error_format.status(StatusCode::GATEWAY_TIMEOUT, &uri);
+                }
+            },
+            RequestTimeout::Total(timeout) => {
+                let deadline = Instant::now() + timeout;
+                match time::timeout_at(deadline, response).await {
+                    Ok(response) => response.map(|response| {
+                        if !is_route_timeout && Self::is_event_stream(response.headers()) {
+                            response
+                        } else {
+                            response.map(|body| TimeoutBody::wrap(body, deadline, cancellation_token.clone()))
+                        }
+                    }),
+                    Err(_) => {
+                        cancellation_token.cancel();
+                        Self::log_rejection("timeout", StatusCode::GATEWAY_TIMEOUT, &request_method, &uri, route.as_deref());
+                        return shared.error_format.status(StatusCode::GATEWAY_TIMEOUT, &uri);
+                    }
+                }
+            }
+        };
+        
+        match response {
+            Err(err) => {
+                // The body limit is also enforced while streaming a request without `Content-Length`
+                if err.status == StatusCode::PAYLOAD_TOO_LARGE {
+                    Self::log_rejection("payload_too_large", err.status, &request_method, &uri, route.as_deref());
+                }
+                call_weak_err_handler(error_handler, &uri, err).await
+            },
+            Ok(response) if request_method != Method::HEAD => Ok(response),
+            Ok(mut response) => {
+                Self::keep_content_length(response.size_hint(), response.headers_mut());
+                *response.body_mut() = HttpBody::empty();
+                Ok(response)
+            }
         }
     }
     
+    #[inline]
+    fn redirect_to_canonical_host(
+        canonical_host: &CanonicalHost,
+        request: &Request<Incoming>,
+        shared: &AppInstance
+    ) -> Option<HttpResult> {
+        let uri = UriParts::with_settings(request.uri(), request.headers(), shared.uri_settings);
+        canonical_host
+            .redirect_location(&uri)
+            .map(|location| status!(301, [(LOCATION, location)]))
+    }
+    
+    /// Emits a single structured event for a request that is rejected by the framework, 
+    /// e.g. because of a missing required header or an exceeded concurrency limit
+    /// 
+    /// The `route` is the pattern of the matched route, e.g. `/users/{id}`, if any
+    #[inline]
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn log_rejection(reason: &'static str, status: StatusCode, method: &Method, uri: &Uri, route: Option<&str>) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            target: "volga::rejection",
+            reason,
+            status = status.as_u16(),
+            %method,
+            path = uri.path(),
+            route = route.unwrap_or_default(),
+            "request rejected");
+    }
+    
+    #[inline]
+    fn is_event_stream(headers: &HeaderMap) -> bool {
+        headers
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with(SSE_CONTENT_TYPE))
+    }
+    
     fn keep_content_length(size_hint: SizeHint, headers: &mut HeaderMap) {
         if headers.contains_key(CONTENT_LENGTH) { 
             return;
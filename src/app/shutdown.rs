@@ -0,0 +1,97 @@
+﻿//! Graceful shutdown settings
+
+use futures_util::future::BoxFuture;
+use std::{future::Future, time::Duration};
+
+/// Default time that connections are given to finish their requests on shutdown
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A callback that runs once the server has stopped
+type ShutdownHook = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+/// Describes how the server shuts down
+pub(crate) struct ShutdownConfig {
+    /// Time that connections are given to finish their requests
+    pub(crate) timeout: Duration,
+    
+    /// Custom shutdown trigger that is awaited along with `Ctrl+C`
+    signal: Option<BoxFuture<'static, ()>>,
+    
+    /// Callbacks that run once all connections are closed or the `timeout` has elapsed
+    hooks: Vec<ShutdownHook>
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+            signal: None,
+            hooks: Vec::new()
+        }
+    }
+}
+
+impl ShutdownConfig {
+    /// Sets a custom shutdown trigger
+    pub(crate) fn set_signal<F>(&mut self, signal: F)
+    where
+        F: Future<Output = ()> + Send + 'static
+    {
+        self.signal = Some(Box::pin(signal));
+    }
+    
+    /// Registers a callback that runs on shutdown
+    pub(crate) fn add_hook<F, Fut>(&mut self, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static
+    {
+        self.hooks.push(Box::new(move || Box::pin(hook())));
+    }
+    
+    /// Takes the custom shutdown trigger, if there is no one, the returned future never completes
+    pub(crate) fn take_signal(&mut self) -> impl Future<Output = ()> + Send + 'static {
+        let signal = self.signal.take();
+        async move {
+            match signal {
+                Some(signal) => signal.await,
+                None => std::future::pending().await
+            }
+        }
+    }
+    
+    /// Runs the registered callbacks one by one in the order of registration
+    pub(crate) async fn run_hooks(self) {
+        for hook in self.hooks {
+            hook().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use super::ShutdownConfig;
+
+    #[tokio::test]
+    async fn it_runs_hooks_in_registration_order() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut config = ShutdownConfig::default();
+        for i in 0..3 {
+            let calls = calls.clone();
+            config.add_hook(move || async move { calls.lock().unwrap().push(i); });
+        }
+        
+        config.run_hooks().await;
+        
+        assert_eq!(*calls.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn it_completes_signal_once_triggered() {
+        let mut config = ShutdownConfig::default();
+        config.set_signal(async {});
+        
+        config.take_signal().await;
+    }
+}
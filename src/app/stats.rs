@@ -0,0 +1,83 @@
+﻿//! Lightweight request statistics
+
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc
+};
+
+/// A cloneable handle to the request counters of an [`App`](crate::App)
+/// 
+/// # Example
+/// ```no_run
+/// use volga::App;
+///
+/// let app = App::new();
+/// let stats = app.stats_handle();
+/// 
+/// println!("served: {}, in-flight: {}", stats.total(), stats.in_flight());
+/// ```
+#[derive(Clone, Default)]
+pub struct StatsHandle {
+    inner: Arc<Stats>
+}
+
+#[derive(Default)]
+struct Stats {
+    total: AtomicU64,
+    in_flight: AtomicUsize
+}
+
+/// Tracks a single request while it's being processed
+pub(crate) struct RequestGuard {
+    stats: StatsHandle
+}
+
+impl StatsHandle {
+    /// Returns the total number of requests that have been processed
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.inner.total.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of requests that are currently being processed
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        self.inner.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Marks a request as in-flight until the returned guard is dropped
+    #[inline]
+    pub(crate) fn track(&self) -> RequestGuard {
+        self.inner.in_flight.fetch_add(1, Ordering::Relaxed);
+        RequestGuard { stats: self.clone() }
+    }
+}
+
+impl Drop for RequestGuard {
+    #[inline]
+    fn drop(&mut self) {
+        let stats = &self.stats.inner;
+        stats.in_flight.fetch_sub(1, Ordering::Relaxed);
+        stats.total.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatsHandle;
+
+    #[test]
+    fn it_tracks_in_flight_and_total_requests() {
+        let stats = StatsHandle::default();
+        
+        let guard = stats.track();
+        
+        assert_eq!(stats.in_flight(), 1);
+        assert_eq!(stats.total(), 0);
+        
+        drop(guard);
+
+        assert_eq!(stats.in_flight(), 0);
+        assert_eq!(stats.total(), 1);
+    }
+}
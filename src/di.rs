@@ -1,9 +1,10 @@
 ﻿//! Tools for Dependency Injection
 
 use super::{App, error::Error};
+use std::{fmt, future::Future};
 pub use self::{
     container::{Container, ContainerBuilder},
-    dc::Dc,
+    dc::{Dc, OptionalDc},
     inject::Inject,
 };
 
@@ -11,24 +12,65 @@ pub mod dc;
 pub mod inject;
 pub mod container;
 
+/// Describes a failure to resolve a service from the DI container
+/// 
+/// It's carried by the [`Error`] that is returned by [`Container::resolve`] 
+/// and the [`Dc<T>`] extractor, so the unresolved type can be inspected in an error handler.
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{App, di::ResolveError, status};
+///
+/// let mut app = App::new();
+/// 
+/// app.map_err(|err| async move {
+///     match err.into_inner().downcast::<ResolveError>() {
+///         Ok(err) => status!(500, "missing service: {}", err.type_name()),
+///         Err(_) => status!(500)
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolveError {
+    type_name: &'static str,
+    reason: &'static str
+}
+
+impl ResolveError {
+    /// Returns the name of the type that could not be resolved
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Services Error: {}: {}", self.reason, self.type_name)
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
 struct DiError;
 
 impl DiError {
     #[inline]
-    fn service_not_registered(type_name: &str) -> Error {
-        Error::server_error(format!("Services Error: service not registered: {}", type_name))
+    fn service_not_registered(type_name: &'static str) -> Error {
+        Error::server_error(ResolveError { type_name, reason: "service not registered" })
     }
 
     #[inline]
-    fn resolve_error(type_name: &str) -> Error {
-        Error::server_error(format!("Services Error: unable to resolve the service: {}", type_name))
+    fn resolve_error(type_name: &'static str) -> Error {
+        Error::server_error(ResolveError { type_name, reason: "unable to resolve the service" })
     }
 
     #[inline]
-    fn resolve_transient_error() -> Error {
-        Error::server_error(
-            "Services Error: cannot resolve a `Transient` service as ref, use `resolve::<T>()` or `Dc<T>` instead",
-        )
+    fn resolve_transient_error(type_name: &'static str) -> Error {
+        Error::server_error(ResolveError {
+            type_name,
+            reason: "cannot resolve a `Transient` service as ref, use `resolve::<T>()` or `Dc<T>` instead"
+        })
     }
 }
 
@@ -52,6 +94,38 @@ impl App {
         self
     }
 
+    /// Registers singleton service that is initialized asynchronously by the `factory` 
+    /// before the server starts, e.g. a connection pool
+    /// 
+    /// The `factory` receives the container with the services that are registered so far.
+    /// If it fails, the [`App::run`] returns the error.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    /// 
+    /// #[derive(Default, Clone)]
+    /// struct Pool;
+    /// 
+    /// impl Pool {
+    ///     async fn connect() -> std::io::Result<Self> {
+    ///         Ok(Pool)
+    ///     }
+    /// }
+    /// 
+    /// let mut app = App::new();
+    /// app.add_singleton_async(|_| async { Ok(Pool::connect().await?) });
+    /// ```
+    pub fn add_singleton_async<T, F, Fut>(&mut self, factory: F) -> &mut Self
+    where
+        T: Inject + 'static,
+        F: FnOnce(Container) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, Error>> + Send + 'static
+    {
+        self.container.register_singleton_async(factory);
+        self
+    }
+
     /// Registers scoped service
     ///
     /// # Example
@@ -69,6 +143,29 @@ impl App {
         self
     }
 
+    /// Registers scoped service that is created by the `factory` once per request
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    ///
+    /// #[derive(Default, Clone)]
+    /// struct ScopedService {
+    ///     id: u64
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_scoped_factory(|| ScopedService { id: 42 });
+    /// ```
+    pub fn add_scoped_factory<T, F>(&mut self, factory: F) -> &mut Self
+    where
+        T: Inject + 'static,
+        F: Fn() -> T + Send + Sync + 'static
+    {
+        self.container.register_scoped_factory(factory);
+        self
+    }
+
     /// Registers transient service
     ///
     /// # Example
@@ -85,4 +182,27 @@ impl App {
         self.container.register_transient::<T>();
         self
     }
+
+    /// Registers transient service that is created by the `factory` on every resolve
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    ///
+    /// #[derive(Default, Clone)]
+    /// struct TransientService {
+    ///     id: u64
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_transient_factory(|| TransientService { id: 42 });
+    /// ```
+    pub fn add_transient_factory<T, F>(&mut self, factory: F) -> &mut Self
+    where
+        T: Inject + 'static,
+        F: Fn() -> T + Send + Sync + 'static
+    {
+        self.container.register_transient_factory(factory);
+        self
+    }
 }
@@ -1,10 +1,11 @@
 ﻿use super::{Inject, DiError};
 use crate::error::Error;
-use futures_util::TryFutureExt;
+use futures_util::{future::BoxFuture, TryFutureExt};
 use tokio::sync::OnceCell;
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
+    future::Future,
     hash::{BuildHasherDefault, Hasher},
     sync::Arc
 };
@@ -19,7 +20,9 @@ type ArcService = Arc<
 pub(crate) enum ServiceEntry {
     Singleton(ArcService),
     Scoped(OnceCell<ArcService>),
+    ScopedFactory(ArcService, OnceCell<ArcService>),
     Transient,
+    TransientFactory(ArcService),
 }
 
 impl ServiceEntry {
@@ -28,11 +31,24 @@ impl ServiceEntry {
         match self {
             ServiceEntry::Singleton(service) => ServiceEntry::Singleton(service.clone()),
             ServiceEntry::Scoped(_) => ServiceEntry::Scoped(OnceCell::new()),
+            ServiceEntry::ScopedFactory(factory, _) => ServiceEntry::ScopedFactory(factory.clone(), OnceCell::new()),
             ServiceEntry::Transient => ServiceEntry::Transient,
+            ServiceEntry::TransientFactory(factory) => ServiceEntry::TransientFactory(factory.clone()),
         }
     }
 }
 
+type Factory<T> = Box<
+    dyn Fn() -> T
+    + Send
+    + Sync
+>;
+
+type Initializer = Box<
+    dyn FnOnce(Container) -> BoxFuture<'static, Result<ArcService, Error>>
+    + Send
+>;
+
 type ServiceMap = HashMap<TypeId, ServiceEntry, BuildHasherDefault<TypeIdHasher>>;
 
 #[derive(Default)]
@@ -56,7 +72,8 @@ impl Hasher for TypeIdHasher {
 
 /// Represents a DI container builder
 pub struct ContainerBuilder {
-    services: ServiceMap
+    services: ServiceMap,
+    initializers: Vec<(TypeId, Initializer)>
 }
 
 impl Default for ContainerBuilder {
@@ -68,33 +85,116 @@ impl Default for ContainerBuilder {
 impl ContainerBuilder {
     /// Create a new DI container builder
     pub fn new() -> Self {
-        Self { services: ServiceMap::default() }
+        Self { 
+            services: ServiceMap::default(),
+            initializers: Vec::new()
+        }
     }
 
     /// Build a DI container
+    /// 
+    /// # Panics
+    /// If there are singletons registered with [`register_singleton_async`](Self::register_singleton_async), 
+    /// use [`build_async`](Self::build_async) instead.
     pub fn build(self) -> Container {
+        assert!(
+            self.initializers.is_empty(),
+            "the container has async singletons, use `build_async()` to build it");
         Container {
             services: self.services
         }
     }
 
+    /// Initializes async singletons and builds a DI container
+    /// 
+    /// Returns the first initialization error if any.
+    pub async fn build_async(mut self) -> Result<Container, Error> {
+        self.init_async().await?;
+        Ok(self.build())
+    }
+
+    /// Runs the initializers of async singletons in the registration order 
+    /// and registers the initialized instances
+    pub(crate) async fn init_async(&mut self) -> Result<(), Error> {
+        for (type_id, init) in std::mem::take(&mut self.initializers) {
+            let container = Container { services: self.services.clone() };
+            let instance = init(container).await?;
+            self.services.insert(type_id, ServiceEntry::Singleton(instance));
+        }
+        Ok(())
+    }
+
     /// Register a singleton service
     pub fn register_singleton<T: Inject + 'static>(&mut self, instance: T) {
         let entry = ServiceEntry::Singleton(Arc::new(instance));
         self.services.insert(TypeId::of::<T>(), entry);
     }
 
+    /// Register a singleton service that is initialized asynchronously by the `factory` 
+    /// when the container is built with [`build_async`](Self::build_async)
+    /// 
+    /// The `factory` receives the container with the services that are registered so far, 
+    /// including the async singletons that are registered earlier, so it can resolve them.
+    pub fn register_singleton_async<T, F, Fut>(&mut self, factory: F)
+    where
+        T: Inject + 'static,
+        F: FnOnce(Container) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, Error>> + Send + 'static
+    {
+        let init: Initializer = Box::new(|container| Box::pin(factory(container)
+            .map_ok(|instance| Arc::new(instance) as ArcService)));
+        self.services.remove(&TypeId::of::<T>());
+        self.initializers.push((TypeId::of::<T>(), init));
+    }
+
     /// Register a scoped service
+    /// 
+    /// An instance is created with [`Inject::inject`] once per scope, the server creates 
+    /// a new scope for each request, so all `Dc<T>` within one request share the same instance, 
+    /// while different requests get different ones. The instance is dropped along with the scope, 
+    /// unless it's still held, e.g. by a streaming response body or a spawned task.
     pub fn register_scoped<T: Inject + 'static>(&mut self) {
         let entry = ServiceEntry::Scoped(OnceCell::new());
         self.services.insert(TypeId::of::<T>(), entry);
     }
 
+    /// Register a scoped service that is created by the `factory` once per scope
+    /// 
+    /// The [`Inject`] implementation of `T` is not used, so dependencies that the `factory` needs, 
+    /// e.g. singletons, have to be captured by it.
+    pub fn register_scoped_factory<T, F>(&mut self, factory: F)
+    where
+        T: Inject + 'static,
+        F: Fn() -> T + Send + Sync + 'static
+    {
+        let factory: Factory<T> = Box::new(factory);
+        let entry = ServiceEntry::ScopedFactory(Arc::new(factory), OnceCell::new());
+        self.services.insert(TypeId::of::<T>(), entry);
+    }
+
     /// Register a transient service
+    /// 
+    /// A new instance is created with [`Inject::inject`] on every resolve, 
+    /// so each `Dc<T>` gets its own instance, while the dependencies it resolves
+    /// follow their own lifetimes, e.g. singletons are still shared.
     pub fn register_transient<T: Inject + 'static>(&mut self) {
         let entry = ServiceEntry::Transient;
         self.services.insert(TypeId::of::<T>(), entry);
     }
+
+    /// Register a transient service that is created by the `factory` on every resolve
+    /// 
+    /// The [`Inject`] implementation of `T` is not used, so dependencies that the `factory` needs, 
+    /// e.g. singletons, have to be captured by it.
+    pub fn register_transient_factory<T, F>(&mut self, factory: F)
+    where
+        T: Inject + 'static,
+        F: Fn() -> T + Send + Sync + 'static
+    {
+        let factory: Factory<T> = Box::new(factory);
+        let entry = ServiceEntry::TransientFactory(Arc::new(factory));
+        self.services.insert(TypeId::of::<T>(), entry);
+    }
 }
 
 /// Represents a DI container
@@ -126,6 +226,8 @@ impl Container {
     pub async fn resolve<T: Inject + 'static>(&mut self) -> Result<T, Error> {
         match self.get_service_entry::<T>()? {
             ServiceEntry::Transient => T::inject(self).await,
+            ServiceEntry::TransientFactory(factory) => Self::resolve_internal::<Factory<T>>(factory)
+                .map(|factory| factory()),
             ServiceEntry::Singleton(instance) => Self::resolve_internal(instance).cloned(),
             ServiceEntry::Scoped(cell) => {
                 let instance = cell
@@ -136,14 +238,27 @@ impl Container {
                     })
                     .await?;
                 Self::resolve_internal(instance).cloned()
+            },
+            ServiceEntry::ScopedFactory(factory, cell) => {
+                let instance = cell.get_or_try_init(|| Self::create_scoped::<T>(factory)).await?;
+                Self::resolve_internal(instance).cloned()
             }
         }
     }
 
+    /// Resolve a service if it is registered, otherwise returns `None`
+    pub async fn try_resolve<T: Inject + 'static>(&mut self) -> Result<Option<T>, Error> {
+        if !self.services.contains_key(&TypeId::of::<T>()) {
+            return Ok(None);
+        }
+        self.resolve::<T>().await.map(Some)
+    }
+
     /// Resolve a service as ref
     pub async fn resolve_ref<T: Inject + 'static>(&mut self) -> Result<&T, Error> {
         match self.get_service_entry::<T>()? {
-            ServiceEntry::Transient => Err(DiError::resolve_transient_error()),
+            ServiceEntry::Transient | 
+            ServiceEntry::TransientFactory(_) => Err(DiError::resolve_transient_error(std::any::type_name::<T>())),
             ServiceEntry::Singleton(instance) => Self::resolve_internal(instance),
             ServiceEntry::Scoped(cell) => {
                 let instance = cell
@@ -154,10 +269,20 @@ impl Container {
                     })
                     .await?;
                 Self::resolve_internal(instance)
+            },
+            ServiceEntry::ScopedFactory(factory, cell) => {
+                let instance = cell.get_or_try_init(|| Self::create_scoped::<T>(factory)).await?;
+                Self::resolve_internal(instance)
             }
         }
     }
 
+    #[inline]
+    async fn create_scoped<T: Inject + 'static>(factory: &ArcService) -> Result<ArcService, Error> {
+        Self::resolve_internal::<Factory<T>>(factory)
+            .map(|factory| Arc::new(factory()) as ArcService)
+    }
+
     /// Fetch the service entry or return an error if not registered.
     #[inline]
     fn get_service_entry<T: Inject + 'static>(&self) -> Result<&ServiceEntry, Error> {
@@ -168,7 +293,7 @@ impl Container {
     }
 
     #[inline]
-    fn resolve_internal<T: Inject + 'static>(instance: &ArcService) -> Result<&T, Error> {
+    fn resolve_internal<T: Send + Sync + 'static>(instance: &ArcService) -> Result<&T, Error> {
         (**instance)
             .downcast_ref::<T>()
             .ok_or(DiError::resolve_error(std::any::type_name::<T>()))
@@ -180,6 +305,7 @@ mod tests {
     use std::collections::HashMap;
     use std::sync::{Arc, Mutex};
     use super::{Error, Container, ContainerBuilder, Inject};
+    use crate::di::ResolveError;
 
     trait Cache: Send + Sync {
         fn get(&self, key: &str) -> Option<String>;
@@ -219,6 +345,76 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn it_returns_resolve_error_with_type_name() {
+        let mut container = ContainerBuilder::new().build();
+
+        let err = container.resolve::<InMemoryCache>().await.err().unwrap();
+
+        assert_eq!(err.status, 500);
+
+        let err = err.into_inner().downcast::<ResolveError>().unwrap();
+
+        assert!(err.type_name().ends_with("InMemoryCache"));
+    }
+
+    #[tokio::test]
+    async fn it_returns_resolve_error_for_inner_dependency() {
+        let mut container = ContainerBuilder::new();
+        container.register_transient::<CacheWrapper>();
+
+        let mut container = container.build();
+
+        let err = container.resolve::<CacheWrapper>().await.err().unwrap();
+        let err = err.into_inner().downcast::<ResolveError>().unwrap();
+
+        assert!(err.type_name().ends_with("InMemoryCache"));
+    }
+
+    #[tokio::test]
+    async fn it_registers_async_singleton() {
+        let mut container = ContainerBuilder::new();
+        container.register_singleton_async(|_| async {
+            tokio::task::yield_now().await;
+            let cache = InMemoryCache::default();
+            cache.set("key", "value");
+            Ok(cache)
+        });
+        container.register_singleton_async(|mut container| async move {
+            Ok(CacheWrapper { inner: container.resolve().await? })
+        });
+
+        let mut container = container.build_async().await.unwrap();
+
+        let cache = container.resolve::<CacheWrapper>().await.unwrap();
+        cache.inner.set("key", "value 2");
+
+        let cache = container.resolve::<InMemoryCache>().await.unwrap();
+
+        assert_eq!(cache.get("key").unwrap(), "value 2");
+    }
+
+    #[tokio::test]
+    async fn it_returns_async_singleton_initialization_error() {
+        let mut container = ContainerBuilder::new();
+        container.register_singleton_async(|_| async {
+            Err::<InMemoryCache, _>(Error::server_error("connection refused"))
+        });
+
+        let container = container.build_async().await;
+
+        assert!(container.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_async_singleton_is_built_synchronously() {
+        let mut container = ContainerBuilder::new();
+        container.register_singleton_async(|_| async { Ok(InMemoryCache::default()) });
+
+        _ = container.build();
+    }
+
     #[tokio::test]
     async fn it_registers_singleton() {
         let mut container = ContainerBuilder::new();
@@ -267,6 +463,51 @@ mod tests {
         assert!(key.is_none());
     }
 
+    #[tokio::test]
+    async fn it_registers_transient_factory() {
+        let mut container = ContainerBuilder::new();
+        container.register_transient_factory(|| CacheWrapper { inner: InMemoryCache::default() });
+
+        let mut container = container.build();
+
+        let cache = container.resolve::<CacheWrapper>().await.unwrap();
+        cache.inner.set("key", "value");
+
+        let cache = container.resolve::<CacheWrapper>().await.unwrap();
+        let key = cache.inner.get("key");
+
+        assert!(key.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_does_not_resolve_transient_factory_as_ref() {
+        let mut container = ContainerBuilder::new();
+        container.register_transient_factory(InMemoryCache::default);
+
+        let mut container = container.build();
+
+        let cache = container.resolve_ref::<InMemoryCache>().await;
+
+        assert!(cache.is_err());
+    }
+
+    #[tokio::test]
+    async fn it_shares_singleton_dependencies_of_transient() {
+        let mut container = ContainerBuilder::new();
+        container.register_singleton(InMemoryCache::default());
+        container.register_transient::<CacheWrapper>();
+
+        let mut container = container.build();
+
+        let first = container.resolve::<CacheWrapper>().await.unwrap();
+        let second = container.resolve::<CacheWrapper>().await.unwrap();
+
+        first.inner.set("key", "value");
+
+        assert!(Arc::ptr_eq(&first.inner.inner, &second.inner.inner));
+        assert_eq!(second.inner.get("key").unwrap(), "value");
+    }
+
     #[tokio::test]
     async fn it_registers_scoped() {
         let mut container = ContainerBuilder::new();
@@ -304,6 +545,48 @@ mod tests {
         assert_eq!(key, "value 1");
     }
 
+    #[tokio::test]
+    async fn it_registers_scoped_factory() {
+        let mut container = ContainerBuilder::new();
+        container.register_scoped_factory(InMemoryCache::default);
+
+        let container = container.build();
+
+        {
+            let mut scope = container.create_scope();
+            let cache = scope.resolve::<InMemoryCache>().await.unwrap();
+            cache.set("key", "value 1");
+
+            let cache = scope.resolve_ref::<InMemoryCache>().await.unwrap();
+
+            assert_eq!(cache.get("key").unwrap(), "value 1");
+        }
+
+        {
+            let mut scope = container.create_scope();
+            let cache = scope.resolve::<InMemoryCache>().await.unwrap();
+
+            assert!(cache.get("key").is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn it_drops_scoped_instance_with_scope() {
+        let mut container = ContainerBuilder::new();
+        container.register_scoped::<InMemoryCache>();
+
+        let container = container.build();
+
+        let mut scope = container.create_scope();
+        let cache = scope.resolve::<InMemoryCache>().await.unwrap();
+
+        assert_eq!(Arc::strong_count(&cache.inner), 2);
+
+        drop(scope);
+
+        assert_eq!(Arc::strong_count(&cache.inner), 1);
+    }
+
     #[tokio::test]
     async fn it_registers_scoped_and_resolves_as_ref() {
         let mut container = ContainerBuilder::new();
@@ -105,10 +105,103 @@ impl<T: Inject + 'static> FromPayload for Dc<T> {
     }
 }
 
+/// Wraps the injectable type of `T` if it is registered in Dependency Injection Container,
+/// otherwise holds `None`
+/// 
+/// Useful for optional dependencies, e.g. plugins that may not be registered.
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{App, di::OptionalDc, ok};
+/// 
+/// #[derive(Clone, Default)]
+/// struct Greeter;
+/// 
+/// impl Greeter {
+///     fn greet(&self) -> &'static str {
+///         "Hello!"
+///     }
+/// }
+/// 
+///# #[tokio::main]
+///# async fn main() -> std::io::Result<()> {
+/// let mut app = App::new();
+/// 
+/// app.map_get("/greet", |greeter: OptionalDc<Greeter>| async move {
+///     match greeter.as_ref() {
+///         Some(greeter) => ok!(greeter.greet()),
+///         None => ok!("Hi!")
+///     }
+/// });
+///# app.run().await
+///# }
+/// ```
+#[derive(Debug, Default, Copy, Clone)]
+pub struct OptionalDc<T: Inject>(pub Option<T>);
+
+impl<T: Inject> OptionalDc<T> {
+    /// Unwraps the inner `Option<T>`
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T: Inject> Deref for OptionalDc<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Option<T> {
+        &self.0
+    }
+}
+
+impl<T: Inject> DerefMut for OptionalDc<T> {
+    fn deref_mut(&mut self) -> &mut Option<T> {
+        &mut self.0
+    }
+}
+
+pin_project! {
+    /// A future that resolves an optional dependency from DI container.
+    pub struct ExtractOptionalDependencyFut<T> {
+        #[pin]
+        container: Container,
+        _marker: PhantomData<T>
+    }
+}
+
+impl<T: Inject + 'static> Future for ExtractOptionalDependencyFut<T> {
+    type Output = Result<OptionalDc<T>, Error>;
+    
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let fut = this.container.try_resolve::<T>();
+        pin_mut!(fut);
+        let result = ready!(fut.poll(cx));
+        Poll::Ready(result.map(OptionalDc))
+    }
+}
+
+impl<T: Inject + 'static> FromPayload for OptionalDc<T> {
+    type Future = ExtractOptionalDependencyFut<T>;
+
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Dc(container) = payload {
+            ExtractOptionalDependencyFut { container: container.clone(), _marker: PhantomData }
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn source() -> Source {
+        Source::Dc
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
-    use super::Dc;
+    use super::{Dc, OptionalDc};
     use crate::di::ContainerBuilder;
     use crate::http::endpoints::args::{FromPayload, Payload};
     
@@ -139,4 +232,25 @@ mod tests {
         assert_eq!(dc[1], 2);
         assert_eq!(dc[2], 3);
     }
+    
+    #[tokio::test]
+    async fn it_reads_registered_optional_dependency() {
+        let mut container = ContainerBuilder::new();
+        container.register_singleton::<Cache>(Arc::new(Mutex::new(vec![1])));
+        
+        let mut scope = container.build().create_scope();
+        
+        let dc = OptionalDc::<Cache>::from_payload(Payload::Dc(&mut scope)).await.unwrap();
+        
+        assert_eq!(dc.into_inner().unwrap().lock().unwrap()[0], 1);
+    }
+    
+    #[tokio::test]
+    async fn it_reads_none_for_unregistered_optional_dependency() {
+        let mut scope = ContainerBuilder::new().build().create_scope();
+        
+        let dc = OptionalDc::<Cache>::from_payload(Payload::Dc(&mut scope)).await.unwrap();
+        
+        assert!(dc.is_none());
+    }
 }
\ No newline at end of file
@@ -10,16 +10,21 @@ use std::{
     error::Error as StdError
 };
 use std::io::ErrorKind;
-pub use self::handler::{ErrorHandler, ErrorFunc};
+pub use self::{
+    format::ErrorFormat,
+    handler::{ErrorHandler, ErrorFunc}
+};
+pub use crate::http::endpoints::args::BodyAlreadyConsumed;
 
 #[cfg(feature = "problem-details")]
 pub use self::problem::Problem;
 
+pub mod format;
 pub mod handler;
 #[cfg(feature = "problem-details")]
 pub mod problem;
 
-type BoxError = Box<
+pub(crate) type BoxError = Box<
     dyn StdError 
     + Send 
     + Sync
@@ -183,4 +188,19 @@ impl App {
             .set_error_handler(ErrorFunc(handler).into());
         self
     }
+    
+    /// Sets a format of the error responses generated by the framework
+    /// 
+    /// Default: [`ErrorFormat::Plain`]
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::{App, error::ErrorFormat};
+    /// 
+    /// let app = App::new().with_error_format(ErrorFormat::Json);
+    /// ```
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
 }
\ No newline at end of file
@@ -0,0 +1,184 @@
+﻿//! Format of framework-generated error responses
+
+use crate::{HttpResult, status};
+use super::{
+    Error,
+    handler::{PipelineErrorHandler, default_error_handler},
+    ErrorFunc
+};
+
+use hyper::{StatusCode, Uri};
+
+#[cfg(feature = "problem-details")]
+use crate::{response, headers::CONTENT_TYPE, HttpBody};
+#[cfg(feature = "problem-details")]
+use serde_json::json;
+#[cfg(feature = "problem-details")]
+use super::Problem;
+
+/// Content type of Problem Details responses
+#[cfg(feature = "problem-details")]
+const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Describes a format of error responses that are generated by the framework 
+/// (e.g. 404, 405 or 400 responses produced by extractors)
+/// 
+/// > NOTE: It doesn't affect a custom error handler registered with [`App::map_err`](crate::App::map_err)
+/// 
+/// The set of formats depends on the enabled features, so it is marked as `#[non_exhaustive]`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorFormat {
+    /// Plain error responses
+    /// 
+    /// Default
+    #[default]
+    Plain,
+    
+    /// JSON error responses in the following format: `{ "error": "...", "status": 404 }`
+    Json,
+    
+    /// [Problem Details](https://www.rfc-editor.org/rfc/rfc9457) error responses 
+    /// with `application/problem+json` content type in the following format:
+    /// `{ "type": "...", "title": "Not Found", "status": 404, "instance": "/users/1" }`
+    #[cfg(feature = "problem-details")]
+    ProblemDetails
+}
+
+impl ErrorFormat {
+    /// Creates a framework-generated error response with specified `status` 
+    /// for the request to the `instance` URI
+    #[inline]
+    #[cfg_attr(not(feature = "problem-details"), allow(unused_variables))]
+    pub(crate) fn status(self, status: StatusCode, instance: &Uri) -> HttpResult {
+        match self {
+            ErrorFormat::Plain => status!(status.as_u16()),
+            ErrorFormat::Json => status!(status.as_u16(), {
+                "error": status.canonical_reason().unwrap_or("unknown status code"),
+                "status": status.as_u16()
+            }),
+            #[cfg(feature = "problem-details")]
+            ErrorFormat::ProblemDetails => problem_response(status, None, instance.path())
+        }
+    }
+
+    /// Returns a default error handler for this format
+    #[inline]
+    pub(crate) fn error_handler(self) -> PipelineErrorHandler {
+        match self {
+            ErrorFormat::Plain => ErrorFunc(default_error_handler).into(),
+            ErrorFormat::Json => ErrorFunc(json_error_handler).into(),
+            #[cfg(feature = "problem-details")]
+            ErrorFormat::ProblemDetails => ErrorFunc(problem_error_handler).into()
+        }
+    }
+}
+
+/// Error handler that creates a JSON [`HttpResult`] from error
+#[inline]
+async fn json_error_handler(err: Error) -> HttpResult {
+    let status = err.status.as_u16();
+    status!(status, {
+        "error": err.to_string(),
+        "status": status
+    })
+}
+
+/// Error handler that creates a Problem Details [`HttpResult`] from error
+#[inline]
+#[cfg(feature = "problem-details")]
+async fn problem_error_handler(err: Error) -> HttpResult {
+    let detail = err.to_string();
+    problem_response(err.status, Some(&detail), err.instance.as_deref().unwrap_or_default())
+}
+
+/// Creates a Problem Details response, the `instance` member is omitted if it's empty
+#[inline]
+#[cfg(feature = "problem-details")]
+fn problem_response(status: StatusCode, detail: Option<&str>, instance: &str) -> HttpResult {
+    let mut body = json!({
+        "type": Problem::get_problem_type_url(status.as_u16()),
+        "title": status.canonical_reason().unwrap_or("unknown status code"),
+        "status": status.as_u16()
+    });
+    if let Some(detail) = detail {
+        body["detail"] = detail.into();
+    }
+    if !instance.is_empty() {
+        body["instance"] = instance.into();
+    }
+    response!(
+        status,
+        HttpBody::json(body),
+        [
+            (CONTENT_TYPE, PROBLEM_CONTENT_TYPE)
+        ]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+    use hyper::{StatusCode, Uri};
+    use crate::error::Error;
+    use super::{ErrorFormat, json_error_handler};
+
+    #[tokio::test]
+    async fn it_creates_plain_error_response() {
+        let mut response = ErrorFormat::Plain.status(StatusCode::NOT_FOUND, &Uri::from_static("/")).unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(body.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_creates_json_error_response() {
+        let mut response = ErrorFormat::Json.status(StatusCode::METHOD_NOT_ALLOWED, &Uri::from_static("/")).unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(response.status(), 405);
+        assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+        assert_eq!(String::from_utf8_lossy(body), "{\"error\":\"Method Not Allowed\",\"status\":405}");
+    }
+
+    #[tokio::test]
+    async fn it_handles_error_as_json() {
+        let error = Error::client_error("Query parsing error: missing field `id`");
+        
+        let mut response = json_error_handler(error).await.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(response.status(), 400);
+        assert_eq!(String::from_utf8_lossy(body), "{\"error\":\"Query parsing error: missing field `id`\",\"status\":400}");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "problem-details")]
+    async fn it_creates_problem_details_error_response() {
+        let mut response = ErrorFormat::ProblemDetails.status(StatusCode::NOT_FOUND, &Uri::from_static("/users/1?full=true")).unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(response.status(), 404);
+        assert_eq!(response.headers().get("content-type").unwrap(), "application/problem+json");
+        assert_eq!(
+            String::from_utf8_lossy(body),
+            "{\"instance\":\"/users/1\",\"status\":404,\"title\":\"Not Found\",\"type\":\"https://tools.ietf.org/html/rfc9110#section-15.5.5\"}");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "problem-details")]
+    async fn it_handles_error_as_problem_details() {
+        let mut error = Error::client_error("Query parsing error: missing field `id`");
+        error.instance = Some("/users".into());
+
+        let mut response = super::problem_error_handler(error).await.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+
+        assert_eq!(response.status(), 400);
+        assert_eq!(body["detail"], "Query parsing error: missing field `id`");
+        assert_eq!(body["instance"], "/users");
+        assert_eq!(body["status"], 400);
+    }
+}
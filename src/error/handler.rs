@@ -12,7 +12,7 @@ use std::{
 
 /// Trait for types that represents an error handler
 pub trait ErrorHandler {
-    fn call(&self, err: Error) -> BoxFuture<HttpResult>;
+    fn call(&self, err: Error) -> BoxFuture<'_, HttpResult>;
 }
 
 /// Owns a closure that handles an error
@@ -25,7 +25,7 @@ where
     Fut: Future<Output = R> + Send,
 {
     #[inline]
-    fn call(&self, err: Error) -> BoxFuture<HttpResult> {
+    fn call(&self, err: Error) -> BoxFuture<'_, HttpResult> {
         Box::pin(async move {
             match self.0(err).await.into_response() {
                 Ok(resp) => Ok(resp),
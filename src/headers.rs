@@ -8,6 +8,7 @@ pub use hyper::{
         STRICT_TRANSPORT_SECURITY,
         ACCEPT_ENCODING,
         ACCEPT_RANGES,
+        CACHE_CONTROL,
         CONTENT_DISPOSITION,
         CONTENT_ENCODING,
         CONTENT_LENGTH,
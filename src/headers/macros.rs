@@ -37,7 +37,7 @@ pub use custom_headers;
 mod test {
     use hyper::header::HeaderValue;
     use hyper::HeaderMap;
-    use crate::headers::{Header, custom_headers};
+    use crate::headers::Header;
 
     custom_headers! {
         (ApiKey, "x-api-key")
@@ -43,9 +43,10 @@ where
         
         if let Some((item, q_val)) = parts {
             let item = T::from_str(item)?;
-            let q = &q_val[0..2];
-            if Self::PREFIX.contains(&q) {
-                let q_val = &q_val[2..];
+            let q_val = Self::PREFIX
+                .iter()
+                .find_map(|prefix| q_val.strip_prefix(prefix));
+            if let Some(q_val) = q_val {
                 let value = q_val.parse::<f32>()
                     .map_err(QualityError::parsing_error)?;
                 Ok(Quality::new(item, value))
@@ -120,4 +121,12 @@ mod tests {
 
         assert!(quality.is_err());
     }
+
+    #[test]
+    #[cfg(any(feature = "compression-gzip", feature = "decompression-gzip"))]
+    fn it_falls_back_to_rank_value_for_short_param() {
+        let quality = Quality::<Encoding>::from_str("gzip;q").unwrap();
+
+        assert_eq!(quality.value, Encoding::Gzip.rank() as f32);
+    }
 }
\ No newline at end of file
@@ -1,10 +1,11 @@
 ﻿//! Base HTTP tools
 
 // Re-exporting HTTP status codes, Response and some headers from hyper/http
-pub use hyper::{Response, StatusCode};
+pub use hyper::{Response, StatusCode, ext::ReasonPhrase};
 
 pub use body::{BoxBody, UnsyncBoxBody, HttpBody};
 pub use request::HttpRequest;
+pub use correlation_id::CorrelationId;
 pub use response::{
     into_response::IntoResponse,
     HttpHeaders,
@@ -17,4 +18,6 @@ pub use response::{
 pub mod body;
 pub mod request;
 pub mod response;
-pub mod endpoints;
\ No newline at end of file
+pub mod endpoints;
+pub mod sse;
+pub mod correlation_id;
\ No newline at end of file
@@ -1,12 +1,13 @@
 ﻿use bytes::{Bytes};
 use futures_util::TryStreamExt;
 use hyper::body::{Body, Frame, Incoming, SizeHint};
-use http_body_util::{BodyExt, Empty, Full, StreamBody, Limited};
+use http_body_util::{BodyExt, Empty, Full, StreamBody, Limited, LengthLimitError};
 use pin_project_lite::pin_project;
 use serde::Serialize;
 use tokio_util::io::ReaderStream;
-use tokio::fs::File;
-use crate::error::Error;
+use tokio::{fs::File, io::AsyncRead};
+use hyper::StatusCode;
+use crate::error::{Error, BoxError};
 
 use std::{
     borrow::Cow,
@@ -55,8 +56,8 @@ impl Body for HttpBody {
     fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
         match self.project().inner.project() {
             InnerBodyProj::Incoming { inner } => inner.poll_frame(cx).map_err(Error::client_error),
-            InnerBodyProj::Limited { inner } => inner.poll_frame(cx).map_err(Error::client_error),
-            InnerBodyProj::BoxedLimited  { inner } => inner.poll_frame(cx).map_err(Error::client_error),
+            InnerBodyProj::Limited { inner } => inner.poll_frame(cx).map_err(limited_error),
+            InnerBodyProj::BoxedLimited  { inner } => inner.poll_frame(cx).map_err(limited_error),
             InnerBodyProj::Boxed  { inner } => inner.poll_frame(cx),
         }
     }
@@ -130,10 +131,10 @@ impl HttpBody {
         match self.inner {
             InnerBody::Boxed { inner } => inner,
             InnerBody::BoxedLimited { inner } => inner
-                .map_err(Error::client_error)
+                .map_err(limited_error)
                 .boxed(),
             InnerBody::Limited { inner } => inner
-                .map_err(Error::client_error)
+                .map_err(limited_error)
                 .boxed(),
             InnerBody::Incoming { inner } => inner
                 .map_err(Error::client_error)
@@ -202,7 +203,19 @@ impl HttpBody {
     /// Creates a new [`HttpBody`] from [`File`] stream
     #[inline]
     pub fn wrap_stream(content: File) -> HttpBody {
-        let reader_stream = ReaderStream::new(content);
+        Self::wrap_reader(content)
+    }
+
+    /// Creates a new [`HttpBody`] from any [`AsyncRead`] source
+    /// 
+    /// The reader is polled only when the connection is ready to send more data, 
+    /// so a slow client naturally applies backpressure to the source.
+    #[inline]
+    pub fn wrap_reader<R>(reader: R) -> HttpBody
+    where
+        R: AsyncRead + Send + Sync + 'static
+    {
+        let reader_stream = ReaderStream::new(reader);
         let stream_body = StreamBody::new(reader_stream
             .map_err(Error::client_error)
             .map_ok(Frame::data));
@@ -210,6 +223,16 @@ impl HttpBody {
     }
 }
 
+/// Maps an error of the limited body, so exceeding the limit results in `413 Payload Too Large`
+#[inline]
+fn limited_error(err: BoxError) -> Error {
+    if err.is::<LengthLimitError>() {
+        Error::from_parts(StatusCode::PAYLOAD_TOO_LARGE, None, err)
+    } else {
+        Error::client_error(err)
+    }
+}
+
 impl From<Cow<'static, str>> for HttpBody {
     #[inline]
     fn from(value: Cow<'static, str>) -> Self {
@@ -220,10 +243,62 @@ impl From<Cow<'static, str>> for HttpBody {
     }
 }
 
+pin_project! {
+    /// A body that holds the `guard` until it's fully sent or dropped, 
+    /// e.g. to keep a request counted while its response is being streamed
+    pub(crate) struct GuardedBody<G> {
+        #[pin]
+        inner: HttpBody,
+        guard: Option<G>
+    }
+}
+
+impl<G: Send + Sync + 'static> GuardedBody<G> {
+    /// Wraps the `inner` body into [`HttpBody`] that releases the `guard` once it's done
+    pub(crate) fn wrap(inner: HttpBody, guard: G) -> HttpBody {
+        if inner.is_end_stream() {
+            return inner;
+        }
+        HttpBody::boxed(Self { inner, guard: Some(guard) })
+    }
+}
+
+impl<G> Body for GuardedBody<G> {
+    type Data = Bytes;
+    type Error = Error;
+
+    #[inline]
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+        let frame = this.inner.as_mut().poll_frame(cx);
+        let is_done = match &frame {
+            Poll::Ready(None) | Poll::Ready(Some(Err(_))) => true,
+            Poll::Ready(Some(Ok(_))) => this.inner.is_end_stream(),
+            Poll::Pending => false
+        };
+        if is_done {
+            this.guard.take();
+        }
+        frame
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
     use http_body_util::BodyExt;
     use crate::HttpBody;
+    use super::GuardedBody;
 
     #[tokio::test]
     async fn it_returns_err_if_body_limit_exceeded() {
@@ -232,7 +307,7 @@ mod tests {
         
         let collected = body.collect().await;
         
-        assert!(collected.is_err());
+        assert_eq!(collected.err().unwrap().status, 413);
     }
 
     #[tokio::test]
@@ -244,6 +319,17 @@ mod tests {
 
         assert!(collected.is_ok());
     }
-}
-
 
+    #[tokio::test]
+    async fn it_holds_guard_until_body_is_sent() {
+        let guard = Arc::new(());
+        let body = GuardedBody::wrap(HttpBody::full("Hello, World!"), guard.clone());
+        
+        assert_eq!(Arc::strong_count(&guard), 2);
+        
+        let mut body = body;
+        let _ = body.frame().await;
+        
+        assert_eq!(Arc::strong_count(&guard), 1);
+    }
+}
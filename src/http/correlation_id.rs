@@ -0,0 +1,122 @@
+﻿//! Correlation ID propagation to outbound calls
+
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use std::future::Future;
+
+tokio::task_local! {
+    static CURRENT: Option<CorrelationId>;
+}
+
+/// Represents the correlation ID of the request that is currently being handled
+/// 
+/// If enabled with [`App::with_correlation_id`](crate::App::with_correlation_id), 
+/// the ID is read from the configured request header and is available 
+/// within the request handler and middlewares via [`CorrelationId::current`], 
+/// so it can be attached to the calls to downstream services.
+/// 
+/// > **Note:** the ID is stored in a task-local, tasks that are spawned by the handler 
+/// > don't inherit it and have to receive it explicitly.
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{App, ok, http::CorrelationId};
+///
+///# #[tokio::main]
+///# async fn main() -> std::io::Result<()> {
+/// let mut app = App::new().with_correlation_id("x-correlation-id");
+/// 
+/// app.map_get("/orders", || async {
+///     if let Some(id) = CorrelationId::current() {
+///         // e.g. client.get(url).header(id.name(), id.value())
+///     }
+///     ok!()
+/// });
+///# app.run().await
+///# }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId {
+    name: HeaderName,
+    value: HeaderValue
+}
+
+impl CorrelationId {
+    /// Returns the correlation ID of the current request 
+    /// or `None` if the request has no correlation ID or it's called outside the request scope
+    #[inline]
+    pub fn current() -> Option<Self> {
+        CURRENT
+            .try_with(Clone::clone)
+            .ok()
+            .flatten()
+    }
+    
+    /// Returns the header name that the correlation ID is propagated with
+    #[inline]
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+    
+    /// Returns the correlation ID as a header value
+    #[inline]
+    pub fn value(&self) -> &HeaderValue {
+        &self.value
+    }
+    
+    /// Returns the correlation ID as a string slice if it consists of visible ASCII characters only
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        self.value.to_str().ok()
+    }
+    
+    /// Reads the correlation ID from the `name` header
+    #[inline]
+    pub(crate) fn from_headers(name: &HeaderName, headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(name)
+            .map(|value| Self { name: name.clone(), value: value.clone() })
+    }
+    
+    /// Runs the `future` with the correlation ID set as the current one
+    #[inline]
+    pub(crate) fn scope<F: Future>(id: Option<Self>, future: F) -> impl Future<Output = F::Output> {
+        CURRENT.scope(id, future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::header::{HeaderMap, HeaderName};
+    use super::CorrelationId;
+    
+    fn correlation_id(value: &'static str) -> Option<CorrelationId> {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-correlation-id", value.parse().unwrap());
+        CorrelationId::from_headers(&HeaderName::from_static("x-correlation-id"), &headers)
+    }
+
+    #[tokio::test]
+    async fn it_carries_id_within_scope() {
+        let id = correlation_id("123-321-456");
+        
+        let current = CorrelationId::scope(id, async {
+            tokio::task::yield_now().await;
+            CorrelationId::current()
+        }).await.unwrap();
+
+        assert_eq!(current.name(), "x-correlation-id");
+        assert_eq!(current.as_str(), Some("123-321-456"));
+    }
+
+    #[test]
+    fn it_returns_none_outside_scope() {
+        assert!(CorrelationId::current().is_none());
+    }
+
+    #[test]
+    fn it_returns_none_if_header_is_missing() {
+        let id = CorrelationId::from_headers(&HeaderName::from_static("x-correlation-id"), &HeaderMap::new());
+
+        assert!(id.is_none());
+    }
+}
@@ -1,14 +1,15 @@
-﻿use std::collections::HashMap;
-use hyper::{Method, Uri};
+﻿use hyper::{Method, Uri};
 
+use crate::app::router::RouteInfo;
 use super::endpoints::{
-    route::Route,
+    route::{Route, RouteMap, RouteMetadata, RouteParams},
     handlers::RouteHandler,
     route::PathArguments
 };
 
 pub(crate) mod handlers;
-pub(super) mod route;
+pub(crate) mod route;
+pub(crate) mod constraints;
 pub mod args;
 
 const ALLOW_METHOD_SEPARATOR : &str = ",";
@@ -16,7 +17,8 @@ const PATH_SEPARATOR : char = '/';
 
 /// Describes a mapping between HTTP Verbs, routes and request handlers
 pub(crate) struct Endpoints {
-    routes: Route
+    routes: Route,
+    fallbacks: Vec<(Vec<String>, RouteHandler)>
 }
 
 /// Specifies statuses that could be returned after route matching
@@ -29,29 +31,46 @@ pub(crate) enum RouteOption {
 /// Describes a context of the executing route
 pub(crate) struct EndpointContext {
     pub(crate) handler: RouteHandler,
+    pub(crate) metadata: RouteMetadata,
     pub(crate) params: Vec<(String, String)>
 }
 
 impl EndpointContext {
-    pub(crate) fn into_parts(self) -> (RouteHandler, Vec<(String, String)>) {
-        (self.handler, self.params)
+    pub(crate) fn into_parts(self) -> (RouteHandler, RouteMetadata, Vec<(String, String)>) {
+        (self.handler, self.metadata, self.params)
     }
     
-    fn new(handler: RouteHandler, params: PathArguments) -> Self {
-        Self { handler, params }
+    fn new(handler: RouteHandler, metadata: RouteMetadata, params: PathArguments) -> Self {
+        Self { handler, metadata, params }
     }
 }
 
 impl Endpoints {
     pub(crate) fn new() -> Self {
-        Self { routes: Route::Static(HashMap::new()) }
+        Self { 
+            routes: Route::Static(RouteMap::default()),
+            fallbacks: Vec::new()
+        }
     }
 
     /// Gets a context of the executing route by its `HttpRequest`
     #[inline]
     pub(crate) fn get_endpoint(&self, method: &Method, uri: &Uri) -> RouteOption {
         let path_segments = Self::split_path(uri.path());
-        let route_params = match self.routes.find(&path_segments) {
+        Self::resolve(method, self.routes.find(&path_segments))
+    }
+
+    /// Gets a context of the executing route by the request `path` that has consecutive slashes,
+    /// routing it as if they were merged, while catch-all parameters keep them
+    #[inline]
+    pub(crate) fn get_endpoint_with_slashes(&self, method: &Method, path: &str) -> RouteOption {
+        let path_segments = Self::split_path(path);
+        Self::resolve(method, self.routes.find_skipping_empty(&path_segments))
+    }
+
+    #[inline]
+    fn resolve(method: &Method, route_params: Option<RouteParams<'_>>) -> RouteOption {
+        let route_params = match route_params {
             Some(params) => params,
             None => return RouteOption::RouteNotFound,
         };
@@ -66,8 +85,12 @@ impl Endpoints {
                         .join(ALLOW_METHOD_SEPARATOR);
                     RouteOption::MethodNotFound(allowed_methods)
                 },
-                |handler| RouteOption::Ok(
-                    EndpointContext::new(handler.clone(), route_params.params)
+                |endpoint| RouteOption::Ok(
+                    EndpointContext::new(
+                        endpoint.handler.clone(), 
+                        endpoint.metadata.clone(), 
+                        route_params.params
+                    )
                 ),
             );
         }
@@ -82,13 +105,69 @@ impl Endpoints {
         self.routes.insert(&path_segments, method, handler);
     }
     
+    /// Maps the fallback request handler to the route prefix
+    #[inline]
+    pub(crate) fn map_fallback(&mut self, prefix: &str, handler: RouteHandler) {
+        let path_segments = Self::split_path(prefix);
+        self.fallbacks.retain(|(segments, _)| *segments != path_segments);
+        self.fallbacks.push((path_segments, handler));
+    }
+    
+    /// Gets the fallback request handler with the longest route prefix matching the `uri`
+    #[inline]
+    pub(crate) fn get_fallback(&self, uri: &Uri) -> Option<RouteHandler> {
+        if self.fallbacks.is_empty() {
+            return None;
+        }
+        
+        let path_segments = Self::split_path(uri.path());
+        self.fallbacks
+            .iter()
+            .filter(|(prefix, _)| Self::is_root(prefix) || path_segments.starts_with(prefix))
+            .max_by_key(|(prefix, _)| if Self::is_root(prefix) { 0 } else { prefix.len() })
+            .map(|(_, handler)| handler.clone())
+    }
+    
+    /// Gets a mutable reference to the metadata of the route mapped to the HTTP Verb and route pattern
+    #[inline]
+    pub(crate) fn metadata_mut(&mut self, method: &Method, pattern: &str) -> Option<&mut RouteMetadata> {
+        let path_segments = Self::split_path(pattern);
+        self.routes
+            .get_mut(&path_segments, method)
+            .map(|endpoint| &mut endpoint.metadata)
+    }
+    
+    /// Returns a list of all mapped routes, tagged with the `host` they are mapped for, 
+    /// sorted by route pattern and HTTP Verb
+    pub(crate) fn routes(&self, host: Option<&str>) -> Vec<RouteInfo> {
+        let mut routes = Vec::new();
+        self.routes.collect(&mut Vec::new(), &mut routes);
+        
+        let mut routes = routes
+            .into_iter()
+            .map(|(method, path_segments)| RouteInfo { 
+                pattern: format!("{PATH_SEPARATOR}{}", path_segments.join("/")),
+                host: host.map(String::from),
+                method
+            })
+            .collect::<Vec<_>>();
+        routes.sort_by(|a, b| a.pattern
+            .cmp(&b.pattern)
+            .then_with(|| a.method.as_str().cmp(b.method.as_str())));
+        routes
+    }
+    
     #[inline]
     pub(crate) fn contains(&mut self, method: &Method, pattern: &str) -> bool {
         let path_segments = Self::split_path(pattern);
-        self.routes.find(&path_segments).and_then(|params| match &params.route {
-            Route::Handler(handlers) => Some(handlers.contains_key(method)),
-            _ => None,
-        }).unwrap_or(false)
+        self.routes
+            .get_mut(&path_segments, method)
+            .is_some()
+    }
+
+    #[inline]
+    fn is_root(path_segments: &[String]) -> bool {
+        matches!(path_segments, [segment] if segment.is_empty())
     }
 
     #[inline]
@@ -123,6 +202,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_gets_fallback_with_longest_prefix() {
+        let mut endpoints = Endpoints::new();
+
+        let api = Func::new(|| async { Results::text("api") });
+        let v2 = Func::new(|| async { Results::text("v2") });
+
+        endpoints.map_fallback("/api/v2", v2.clone());
+        endpoints.map_fallback("/api", api.clone());
+
+        let uri = "https://example.com/api/v2/users".parse().unwrap();
+        let fallback = endpoints.get_fallback(&uri).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&fallback, &(v2 as super::RouteHandler)));
+
+        let uri = "https://example.com/api/users".parse().unwrap();
+        let fallback = endpoints.get_fallback(&uri).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&fallback, &(api as super::RouteHandler)));
+
+        let uri = "https://example.com/apis".parse().unwrap();
+        assert!(endpoints.get_fallback(&uri).is_none());
+    }
+
     #[test]
     fn it_returns_route_not_found() {
         let mut endpoints = Endpoints::new();
@@ -182,4 +283,16 @@ mod tests {
 
         assert!(!has_route);
     }
+
+    #[test]
+    fn is_has_constrained_route_after_map() {
+        let mut endpoints = Endpoints::new();
+
+        let handler = Func::new(|| async { Results::ok() });
+
+        endpoints.map_route(Method::GET, "users/{id:integer}", handler);
+
+        assert!(endpoints.contains(&Method::GET, "users/{id:integer}"));
+        assert!(!endpoints.contains(&Method::GET, "users/{id}"));
+    }
 }
\ No newline at end of file
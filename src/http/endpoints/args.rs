@@ -1,6 +1,6 @@
 ﻿//! Extractors for HTTP request parts and body
 
-use std::future::Future;
+use std::{fmt, future::Future};
 use hyper::{
     http::{request::Parts, Extensions},
     HeaderMap,
@@ -22,18 +22,33 @@ pub mod query;
 pub mod json;
 pub mod file;
 pub mod cancellation_token;
+pub mod conn_info;
 pub mod request;
 pub mod form;
+pub mod http_version;
+pub mod if_range;
+pub mod lines;
+pub mod merge_patch;
+pub mod json_patch;
+pub mod pagination;
+pub mod received_at;
+pub mod subdomain;
+pub mod uri_parts;
 
 #[cfg(feature = "multipart")]
 pub mod multipart;
 
+#[cfg(feature = "prost")]
+pub mod protobuf;
+
 /// Holds the payload for extractors
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum Payload<'a> {
     None,
     Full(HttpRequest),
     Body(HttpBody),
     Parts(&'a Parts, HttpBody),
+    Head(&'a Parts),
     Query(&'a Uri),
     Headers(&'a HeaderMap),
     Path(&'a (String, String)),
@@ -47,6 +62,7 @@ pub(crate) enum Source {
     None,
     Full,
     Parts,
+    Head,
     Path,
     Query,
     Headers,
@@ -80,6 +96,46 @@ pub(crate) trait FromPayload: Send + Sized {
     }
 }
 
+/// Describes a failure to extract the request body that has already been consumed by another extractor
+/// 
+/// The request body can be read only once, so only one body extractor is allowed per handler. 
+/// It's carried by the [`Error`] returned for such a handler, so it can be handled in an error handler.
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{App, error::BodyAlreadyConsumed, status};
+///
+/// let mut app = App::new();
+/// 
+/// app.map_err(|err| async move {
+///     if err.into_inner().is::<BodyAlreadyConsumed>() {
+///         status!(500, "a handler reads the request body twice")
+///     } else {
+///         status!(500)
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyAlreadyConsumed;
+
+impl fmt::Display for BodyAlreadyConsumed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Payload extraction error: request body has already been consumed by another extractor")
+    }
+}
+
+impl std::error::Error for BodyAlreadyConsumed {}
+
+/// Describes errors of the payload extraction
+struct PayloadError;
+
+impl PayloadError {
+    #[inline]
+    fn body_already_consumed() -> Error {
+        Error::server_error(BodyAlreadyConsumed)
+    }
+}
+
 impl FromRequest for () {
     #[inline]
     async fn from_request(_req: HttpRequest) -> Result<Self, Error> {
@@ -110,6 +166,7 @@ macro_rules! define_generic_from_request {
                         Source::Query => Payload::Query(&parts.uri),
                         Source::Headers => Payload::Headers(&parts.headers),
                         Source::Ext => Payload::Ext(&parts.extensions),
+                        Source::Head => Payload::Head(&parts),
                         #[cfg(feature = "di")]
                         Source::Dc => Payload::Dc(&mut container),
                         Source::Path => match iter.next() {
@@ -118,11 +175,11 @@ macro_rules! define_generic_from_request {
                         },
                         Source::Body => match body.take() {
                             Some(body) => Payload::Body(body),
-                            None => Payload::None
+                            None => return Err(PayloadError::body_already_consumed())
                         },
                         Source::Parts => match body.take() {
                             Some(body) => Payload::Parts(&parts, body),
-                            None => Payload::None
+                            None => return Err(PayloadError::body_already_consumed())
                         },
                         Source::Full => match body.take() {
                             Some(body) => {
@@ -132,7 +189,7 @@ macro_rules! define_generic_from_request {
                                 let req = Payload::Full(HttpRequest::from_parts(parts.clone(), body));
                                 req
                             },
-                            None => Payload::None
+                            None => return Err(PayloadError::body_already_consumed())
                         },
                     }).await?,
                     )*    
@@ -0,0 +1,121 @@
+﻿//! Extractors for connection information
+
+use futures_util::future::{ready, Ready};
+use hyper::http::Extensions;
+use std::net::SocketAddr;
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Holds the addresses of the connection the request was received on
+/// 
+/// If the PROXY protocol is enabled with [`App::with_proxy_protocol`](crate::App::with_proxy_protocol), 
+/// the addresses are taken from the PROXY header, so they describe the original client connection 
+/// rather than the one from the load balancer.
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, ConnInfo, ok};
+///
+/// async fn handle(conn: ConnInfo) -> HttpResult {
+///     ok!("Client address: {}", conn.remote_addr())
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConnInfo {
+    remote_addr: SocketAddr,
+    local_addr: SocketAddr
+}
+
+impl ConnInfo {
+    /// Creates a new connection information
+    #[inline]
+    pub(crate) fn new(remote_addr: SocketAddr, local_addr: SocketAddr) -> Self {
+        Self { remote_addr, local_addr }
+    }
+
+    /// Returns the address of the client
+    #[inline]
+    pub fn remote_addr(&self) -> SocketAddr {
+        self.remote_addr
+    }
+
+    /// Returns the address the client connected to
+    #[inline]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    #[inline]
+    pub(crate) fn from_extensions(extensions: &Extensions) -> Result<Self, Error> {
+        extensions
+            .get::<ConnInfo>()
+            .copied()
+            .ok_or_else(ConnInfoError::not_available)
+    }
+}
+
+/// Extracts `ConnInfo` from request
+impl FromRequestRef for ConnInfo {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Self::from_extensions(req.extensions())
+    }
+}
+
+/// Extracts `ConnInfo` from request parts
+impl FromPayload for ConnInfo {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Ext(extensions) = payload {
+            ready(Self::from_extensions(extensions))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Ext
+    }
+}
+
+/// Describes errors of connection info extractor
+struct ConnInfoError;
+
+impl ConnInfoError {
+    #[inline]
+    fn not_available() -> Error {
+        Error::server_error("Connection info error: connection info is not available")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::http::Extensions;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::ConnInfo;
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let conn = ConnInfo::new(([10, 0, 0, 1], 56324).into(), ([10, 0, 0, 2], 443).into());
+        let mut extensions = Extensions::new();
+        extensions.insert(conn);
+
+        let extracted = ConnInfo::from_payload(Payload::Ext(&extensions)).await.unwrap();
+
+        assert_eq!(extracted.remote_addr(), conn.remote_addr());
+        assert_eq!(extracted.local_addr(), conn.local_addr());
+    }
+
+    #[tokio::test]
+    async fn it_returns_error_if_not_available() {
+        let extensions = Extensions::new();
+
+        let err = ConnInfo::from_payload(Payload::Ext(&extensions)).await.unwrap_err();
+
+        assert!(err.is_server_error());
+    }
+}
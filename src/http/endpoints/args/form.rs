@@ -125,7 +125,7 @@ impl FormError {
 
     #[inline]
     fn collect_error(err: Error) -> Error {
-        Error::client_error(format!("Form Data parsing error: {}", err))
+        Error::from_parts(err.status, None, format!("Form Data parsing error: {}", err))
     }
 }
 
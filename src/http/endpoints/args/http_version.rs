@@ -0,0 +1,120 @@
+﻿//! Extractors for the HTTP protocol version
+
+use futures_util::future::{ready, Ready};
+use hyper::{http::request::Parts, Version};
+use std::fmt::{Display, Formatter};
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Holds the HTTP protocol version the request was served over, 
+/// which for TLS connections is the one negotiated via ALPN
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, HttpVersion, ok};
+///
+/// async fn handle(version: HttpVersion) -> HttpResult {
+///     if version.is_http2() {
+///         ok!("served over HTTP/2")
+///     } else {
+///         ok!("served over {version}")
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HttpVersion(Version);
+
+impl HttpVersion {
+    /// Returns the protocol version
+    #[inline]
+    pub fn version(&self) -> Version {
+        self.0
+    }
+
+    /// Checks whether the request was served over HTTP/1.x
+    #[inline]
+    pub fn is_http1(&self) -> bool {
+        matches!(self.0, Version::HTTP_09 | Version::HTTP_10 | Version::HTTP_11)
+    }
+
+    /// Checks whether the request was served over HTTP/2
+    #[inline]
+    pub fn is_http2(&self) -> bool {
+        self.0 == Version::HTTP_2
+    }
+}
+
+impl Display for HttpVersion {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl From<Version> for HttpVersion {
+    #[inline]
+    fn from(version: Version) -> Self {
+        Self(version)
+    }
+}
+
+/// Extracts `HttpVersion` from request
+impl FromRequestRef for HttpVersion {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Ok(Self(req.version()))
+    }
+}
+
+/// Extracts `HttpVersion` from request parts
+impl FromPayload for HttpVersion {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Head(Parts { version, .. }) = payload {
+            ready(Ok(Self(*version)))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{Request, Version};
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::HttpVersion;
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        for version in [Version::HTTP_11, Version::HTTP_2] {
+            let (parts, _) = Request::get("/")
+                .version(version)
+                .body(())
+                .unwrap()
+                .into_parts();
+
+            let extracted = HttpVersion::from_payload(Payload::Head(&parts)).await.unwrap();
+
+            assert_eq!(extracted.version(), version);
+        }
+    }
+
+    #[test]
+    fn it_tells_protocol_family() {
+        let http1 = HttpVersion::from(Version::HTTP_11);
+        let http2 = HttpVersion::from(Version::HTTP_2);
+
+        assert!(http1.is_http1() && !http1.is_http2());
+        assert!(http2.is_http2() && !http2.is_http1());
+        assert_eq!(http1.to_string(), "HTTP/1.1");
+        assert_eq!(http2.to_string(), "HTTP/2.0");
+    }
+}
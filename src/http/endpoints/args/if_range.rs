@@ -0,0 +1,186 @@
+﻿//! Extractors for conditional range requests
+
+use futures_util::future::{ready, Ready};
+use hyper::{header::{HeaderName, IF_RANGE, RANGE}, HeaderMap};
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Holds the `Range` request header along with its `If-Range` precondition
+///
+/// The range should be served (`206 Partial Content`) only if the `If-Range` validator
+/// still matches the current representation, otherwise the full representation (`200 OK`)
+/// should be sent.
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, IfRangeCondition, ok, status};
+///
+/// async fn handle(if_range: IfRangeCondition) -> HttpResult {
+///     let etag = "\"v1\"";
+///     match if_range.range(Some(etag), None) {
+///         Some(range) => status!(206, "partial content for {range}"),
+///         None => ok!("full content")
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct IfRangeCondition {
+    range: Option<String>,
+    validator: Option<String>
+}
+
+impl IfRangeCondition {
+    /// Returns the raw `Range` header value if any
+    #[inline]
+    pub fn range_header(&self) -> Option<&str> {
+        self.range.as_deref()
+    }
+
+    /// Returns the raw `If-Range` header value if any
+    #[inline]
+    pub fn validator(&self) -> Option<&str> {
+        self.validator.as_deref()
+    }
+
+    /// Checks whether the `If-Range` precondition holds for the current representation
+    /// described by its `etag` and `last_modified` date.
+    ///
+    /// Returns `true` if there is no `If-Range` header. An entity tag is compared using
+    /// the strong comparison, so a weak `etag` never matches, and a date must exactly match
+    /// the `last_modified` one.
+    pub fn is_satisfied(&self, etag: Option<&str>, last_modified: Option<&str>) -> bool {
+        let Some(validator) = self.validator.as_deref() else {
+            return true;
+        };
+
+        if validator.starts_with('"') {
+            etag.is_some_and(|etag| !etag.starts_with("W/") && etag == validator)
+        } else if validator.starts_with("W/") {
+            false
+        } else {
+            last_modified.is_some_and(|last_modified| last_modified == validator)
+        }
+    }
+
+    /// Returns the `Range` header value if the range should be served
+    /// for the current representation described by its `etag` and `last_modified` date,
+    /// otherwise `None` which means that the full representation should be sent.
+    #[inline]
+    pub fn range(&self, etag: Option<&str>, last_modified: Option<&str>) -> Option<&str> {
+        self.range
+            .as_deref()
+            .filter(|_| self.is_satisfied(etag, last_modified))
+    }
+
+    #[inline]
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let read = |name: HeaderName| headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_owned());
+        Self {
+            range: read(RANGE),
+            validator: read(IF_RANGE)
+        }
+    }
+}
+
+impl FromRequestRef for IfRangeCondition {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Ok(Self::from_headers(req.headers()))
+    }
+}
+
+/// Extracts the `Range` and `If-Range` headers from request parts
+impl FromPayload for IfRangeCondition {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Headers(headers) = payload {
+            ready(Ok(Self::from_headers(headers)))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::{IF_RANGE, RANGE}, HeaderMap};
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::IfRangeCondition;
+
+    const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+    fn headers(range: Option<&'static str>, if_range: Option<&'static str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(range) = range {
+            headers.insert(RANGE, range.parse().unwrap());
+        }
+        if let Some(if_range) = if_range {
+            headers.insert(IF_RANGE, if_range.parse().unwrap());
+        }
+        headers
+    }
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let headers = headers(Some("bytes=0-99"), Some("\"v1\""));
+
+        let if_range = IfRangeCondition::from_payload(Payload::Headers(&headers)).await.unwrap();
+
+        assert_eq!(if_range.range_header(), Some("bytes=0-99"));
+        assert_eq!(if_range.validator(), Some("\"v1\""));
+    }
+
+    #[test]
+    fn it_returns_range_without_precondition() {
+        let if_range = IfRangeCondition::from_headers(&headers(Some("bytes=0-99"), None));
+
+        assert_eq!(if_range.range(None, None), Some("bytes=0-99"));
+    }
+
+    #[test]
+    fn it_returns_range_for_matching_etag() {
+        let if_range = IfRangeCondition::from_headers(&headers(Some("bytes=0-99"), Some("\"v1\"")));
+
+        assert_eq!(if_range.range(Some("\"v1\""), None), Some("bytes=0-99"));
+    }
+
+    #[test]
+    fn it_returns_none_for_stale_etag() {
+        let if_range = IfRangeCondition::from_headers(&headers(Some("bytes=0-99"), Some("\"v1\"")));
+
+        assert_eq!(if_range.range(Some("\"v2\""), None), None);
+    }
+
+    #[test]
+    fn it_returns_none_for_weak_etag() {
+        let if_range = IfRangeCondition::from_headers(&headers(Some("bytes=0-99"), Some("W/\"v1\"")));
+
+        assert_eq!(if_range.range(Some("W/\"v1\""), None), None);
+    }
+
+    #[test]
+    fn it_compares_last_modified_date() {
+        let if_range = IfRangeCondition::from_headers(&headers(Some("bytes=0-99"), Some(LAST_MODIFIED)));
+
+        assert_eq!(if_range.range(None, Some(LAST_MODIFIED)), Some("bytes=0-99"));
+        assert_eq!(if_range.range(None, Some("Thu, 22 Oct 2015 07:28:00 GMT")), None);
+    }
+
+    #[test]
+    fn it_returns_none_without_range() {
+        let if_range = IfRangeCondition::from_headers(&headers(None, Some("\"v1\"")));
+
+        assert_eq!(if_range.range(Some("\"v1\""), None), None);
+    }
+}
@@ -5,7 +5,9 @@ use pin_project_lite::pin_project;
 use serde::de::DeserializeOwned;
 
 use http_body_util::{combinators::Collect, BodyExt};
+use hyper::{http::request::Parts, StatusCode};
 use serde::Serialize;
+use serde_json::error::Category;
 use crate::{error::Error, HttpBody};
 
 use std::{
@@ -25,8 +27,24 @@ use crate::http::{
     }
 };
 
+/// Status code of responses to syntactically valid JSON payloads 
+/// that can't be deserialized into the expected type
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct UnprocessableStatus(pub(crate) StatusCode);
+
+impl Default for UnprocessableStatus {
+    #[inline]
+    fn default() -> Self {
+        Self(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+}
+
 /// Wraps typed JSON data
 ///
+/// Malformed JSON is rejected with `400 Bad Request`, while well-formed JSON
+/// that doesn't match the type `T` is rejected with `422 Unprocessable Entity`, 
+/// which can be changed with [`App::with_unprocessable_status`](crate::App::with_unprocessable_status).
+///
 /// # Example
 /// ```no_run
 /// use volga::{HttpResult, Json, ok};
@@ -83,6 +101,7 @@ pin_project! {
     pub struct ExtractJsonPayloadFut<T> {
         #[pin]
         fut: Collect<HttpBody>,
+        unprocessable_status: UnprocessableStatus,
         _marker: PhantomData<T>
     }
 }
@@ -98,7 +117,7 @@ impl<T: DeserializeOwned + Send> Future for ExtractJsonPayloadFut<T> {
         let body = result.to_bytes();
         let json = serde_json::from_slice(&body)
             .map(Json::<T>)
-            .map_err(JsonError::from_serde_error);
+            .map_err(|err| JsonError::from_serde_error(err, *this.unprocessable_status));
         Poll::Ready(json)
     }
 }
@@ -109,55 +128,111 @@ impl<T: DeserializeOwned + Send> FromPayload for Json<T> {
     type Future = ExtractJsonPayloadFut<T>;
 
     fn from_payload(payload: Payload) -> Self::Future {
-        if let Payload::Body(body) = payload {
-            ExtractJsonPayloadFut { fut: body.collect(), _marker: PhantomData }
+        if let Payload::Parts(parts, body) = payload {
+            ExtractJsonPayloadFut {
+                fut: body.collect(),
+                unprocessable_status: unprocessable_status(parts),
+                _marker: PhantomData
+            }
         } else {
             unreachable!()
         }
     }
 
     fn source() -> Source {
-        Source::Body
+        Source::Parts
     }
 }
 
+#[inline]
+fn unprocessable_status(parts: &Parts) -> UnprocessableStatus {
+    parts.extensions
+        .get::<UnprocessableStatus>()
+        .copied()
+        .unwrap_or_default()
+}
+
 struct JsonError;
 
 impl JsonError {
     #[inline]
-    fn from_serde_error(err: serde_json::Error) -> Error {
-        Error::client_error(format!("JSON parsing error: {}", err))
+    fn from_serde_error(err: serde_json::Error, unprocessable_status: UnprocessableStatus) -> Error {
+        match err.classify() {
+            Category::Data => Error::from_parts(
+                unprocessable_status.0,
+                None,
+                format!("JSON parsing error: {}", err)),
+            _ => Error::client_error(format!("JSON parsing error: {}", err))
+        }
     }
 
     #[inline]
     fn collect_error(err: Error) -> Error {
-        Error::client_error(format!("JSON parsing error: {}", err))
+        Error::from_parts(err.status, None, format!("JSON parsing error: {}", err))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use hyper::{Request, StatusCode};
     use serde::{Deserialize, Serialize};
     use crate::HttpBody;
     use crate::http::endpoints::args::{FromPayload, Payload};
-    use super::Json;
+    use super::{Json, UnprocessableStatus};
     
-    #[derive(Serialize, Deserialize)]
+    #[derive(Debug, Serialize, Deserialize)]
     struct User {
         age: i32,
         name: String,
     }
     
+    fn request(body: &'static str) -> Request<HttpBody> {
+        Request::post("/")
+            .body(HttpBody::boxed(HttpBody::full(body)))
+            .unwrap()
+    }
+    
     #[tokio::test]
     async fn it_reads_from_payload() {
         let user = User { age: 33, name: "John".into() };
-        let body = HttpBody::boxed(HttpBody::json(user));
+        let req = Request::post("/")
+            .body(HttpBody::boxed(HttpBody::json(user)))
+            .unwrap();
         
-        let user = Json::<User>::from_payload(Payload::Body(body)).await.unwrap();
+        let (parts, body) = req.into_parts();
+        let user = Json::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap();
         
         assert_eq!(user.age, 33);
         assert_eq!(user.name, "John");
     }
+
+    #[tokio::test]
+    async fn it_returns_bad_request_for_malformed_json() {
+        let (parts, body) = request("{\"age\": 33, \"name\": ").into_parts();
+        
+        let err = Json::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+        
+        assert_eq!(err.into_parts().0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn it_returns_unprocessable_entity_for_missing_field() {
+        let (parts, body) = request("{\"age\": 33}").into_parts();
+
+        let err = Json::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+
+        assert_eq!(err.into_parts().0, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn it_returns_configured_status_for_wrong_type() {
+        let (mut parts, body) = request("{\"age\": \"old\", \"name\": \"John\"}").into_parts();
+        parts.extensions.insert(UnprocessableStatus(StatusCode::BAD_REQUEST));
+
+        let err = Json::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+
+        assert_eq!(err.into_parts().0, StatusCode::BAD_REQUEST);
+    }
     
     #[test]
     fn it_converts_to_json() {
@@ -0,0 +1,481 @@
+﻿//! Extractors for JSON Patch documents (RFC 6902)
+
+use futures_util::ready;
+use pin_project_lite::pin_project;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use http_body_util::{combinators::Collect, BodyExt};
+use hyper::{http::request::Parts, StatusCode};
+use crate::{error::Error, headers::CONTENT_TYPE, HttpBody};
+
+use std::{
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+use crate::http::{
+    endpoints::args::{
+        FromPayload,
+        Payload,
+        Source
+    }
+};
+
+/// Content type of JSON Patch documents
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// Represents a single JSON Patch operation
+///
+/// Paths are JSON Pointers ([RFC 6901](https://www.rfc-editor.org/rfc/rfc6901))
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOperation {
+    /// Adds the `value` at the `path`
+    Add { path: String, value: Value },
+    /// Removes the value at the `path`
+    Remove { path: String },
+    /// Replaces the value at the `path` with the `value`
+    Replace { path: String, value: Value },
+    /// Moves the value at the `from` location to the `path`
+    Move { from: String, path: String },
+    /// Copies the value at the `from` location to the `path`
+    Copy { from: String, path: String },
+    /// Tests that the value at the `path` is equal to the `value`
+    Test { path: String, value: Value }
+}
+
+/// Wraps a JSON Patch document ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902))
+///
+/// The request must have the `application/json-patch+json` content type,
+/// otherwise `415 Unsupported Media Type` is returned.
+///
+/// Same as [`MergePatch`](crate::MergePatch), the patch is applied to a JSON value in place 
+/// with [`JsonPatch::apply`] or to a copy of a typed value with [`JsonPatch::apply_typed`].
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, JsonPatch, ok};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct User {
+///     name: String,
+///     email: Option<String>
+/// }
+///
+/// async fn handle(patch: JsonPatch) -> HttpResult {
+///     let user = User { name: "John".into(), email: None };
+///     let user = patch.apply_typed(&user)?;
+///     ok!(user)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPatch(pub Vec<PatchOperation>);
+
+impl JsonPatch {
+    /// Unwraps the inner list of operations
+    pub fn into_inner(self) -> Vec<PatchOperation> {
+        self.0
+    }
+
+    /// Applies the patch to the `target` JSON value in place
+    ///
+    /// Operations are applied in order and the patch is atomic, so if any of them fails
+    /// the `target` is left unchanged. A failed `test` operation or a missing target location
+    /// results in `409 Conflict`.
+    pub fn apply(&self, target: &mut Value) -> Result<(), Error> {
+        let mut patched = target.clone();
+        for operation in &self.0 {
+            apply_operation(&mut patched, operation)?;
+        }
+        *target = patched;
+        Ok(())
+    }
+
+    /// Applies the patch to a copy of the `base` value and returns the patched one
+    pub fn apply_typed<T: Serialize + DeserializeOwned>(&self, base: &T) -> Result<T, Error> {
+        let mut target = serde_json::to_value(base)
+            .map_err(JsonPatchError::from_serde_error)?;
+        self.apply(&mut target)?;
+        serde_json::from_value(target)
+            .map_err(JsonPatchError::from_serde_error)
+    }
+
+    /// Parses a JSON Patch document from the JSON array of operations
+    fn from_value(value: Value) -> Result<Self, Error> {
+        let Value::Array(operations) = value else {
+            return Err(JsonPatchError::invalid_document("expected an array of operations"));
+        };
+        operations
+            .into_iter()
+            .map(PatchOperation::from_value)
+            .collect::<Result<_, _>>()
+            .map(JsonPatch)
+    }
+}
+
+impl Deref for JsonPatch {
+    type Target = [PatchOperation];
+
+    fn deref(&self) -> &[PatchOperation] {
+        &self.0
+    }
+}
+
+impl PatchOperation {
+    fn from_value(value: Value) -> Result<Self, Error> {
+        let Value::Object(mut operation) = value else {
+            return Err(JsonPatchError::invalid_document("expected an operation object"));
+        };
+        let mut string = |name: &str| match operation.remove(name) {
+            Some(Value::String(value)) => Ok(value),
+            _ => Err(JsonPatchError::invalid_document(format!("missing or invalid `{name}` member")))
+        };
+        let op = string("op")?;
+        let path = string("path")?;
+        let from = |mut operation: Map<String, Value>| match operation.remove("from") {
+            Some(Value::String(from)) => Ok(from),
+            _ => Err(JsonPatchError::invalid_document("missing or invalid `from` member"))
+        };
+        let value = |mut operation: Map<String, Value>| operation
+            .remove("value")
+            .ok_or_else(|| JsonPatchError::invalid_document("missing `value` member"));
+        match op.as_str() {
+            "add" => Ok(Self::Add { path, value: value(operation)? }),
+            "remove" => Ok(Self::Remove { path }),
+            "replace" => Ok(Self::Replace { path, value: value(operation)? }),
+            "move" => Ok(Self::Move { from: from(operation)?, path }),
+            "copy" => Ok(Self::Copy { from: from(operation)?, path }),
+            "test" => Ok(Self::Test { path, value: value(operation)? }),
+            op => Err(JsonPatchError::invalid_document(format!("unknown operation `{op}`")))
+        }
+    }
+}
+
+fn apply_operation(target: &mut Value, operation: &PatchOperation) -> Result<(), Error> {
+    match operation {
+        PatchOperation::Add { path, value } => add(target, path, value.clone()),
+        PatchOperation::Remove { path } => remove(target, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let current = target
+                .pointer_mut(path)
+                .ok_or_else(|| JsonPatchError::path_not_found(path))?;
+            *current = value.clone();
+            Ok(())
+        },
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                return Err(JsonPatchError::conflict(format!("cannot move `{from}` into its child `{path}`")));
+            }
+            let value = remove(target, from)?;
+            add(target, path, value)
+        },
+        PatchOperation::Copy { from, path } => {
+            let value = target
+                .pointer(from)
+                .cloned()
+                .ok_or_else(|| JsonPatchError::path_not_found(from))?;
+            add(target, path, value)
+        },
+        PatchOperation::Test { path, value } => match target.pointer(path) {
+            Some(current) if current == value => Ok(()),
+            _ => Err(JsonPatchError::conflict(format!("test failed at `{path}`")))
+        }
+    }
+}
+
+fn add(target: &mut Value, path: &str, value: Value) -> Result<(), Error> {
+    let Some((parent, token)) = split_pointer(path)? else {
+        *target = value;
+        return Ok(());
+    };
+    match target.pointer_mut(parent) {
+        Some(Value::Object(object)) => {
+            object.insert(token, value);
+            Ok(())
+        },
+        Some(Value::Array(array)) => {
+            let index = if token == "-" { array.len() } else { parse_index(&token, path)? };
+            if index > array.len() {
+                return Err(JsonPatchError::path_not_found(path));
+            }
+            array.insert(index, value);
+            Ok(())
+        },
+        _ => Err(JsonPatchError::path_not_found(path))
+    }
+}
+
+fn remove(target: &mut Value, path: &str) -> Result<Value, Error> {
+    let Some((parent, token)) = split_pointer(path)? else {
+        return Err(JsonPatchError::conflict("cannot remove the whole document"));
+    };
+    match target.pointer_mut(parent) {
+        Some(Value::Object(object)) => object
+            .remove(&token)
+            .ok_or_else(|| JsonPatchError::path_not_found(path)),
+        Some(Value::Array(array)) => {
+            let index = parse_index(&token, path)?;
+            if index >= array.len() {
+                return Err(JsonPatchError::path_not_found(path));
+            }
+            Ok(array.remove(index))
+        },
+        _ => Err(JsonPatchError::path_not_found(path))
+    }
+}
+
+/// Splits a JSON Pointer into the parent pointer and the unescaped last reference token,
+/// returns `None` for the whole document pointer
+fn split_pointer(path: &str) -> Result<Option<(&str, String)>, Error> {
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let index = path
+        .rfind('/')
+        .filter(|_| path.starts_with('/'))
+        .ok_or_else(|| JsonPatchError::invalid_document(format!("invalid JSON pointer `{path}`")))?;
+    let token = path[index + 1..]
+        .replace("~1", "/")
+        .replace("~0", "~");
+    Ok(Some((&path[..index], token)))
+}
+
+fn parse_index(token: &str, path: &str) -> Result<usize, Error> {
+    if token.len() > 1 && token.starts_with('0') {
+        return Err(JsonPatchError::path_not_found(path));
+    }
+    token
+        .parse()
+        .map_err(|_| JsonPatchError::path_not_found(path))
+}
+
+pin_project! {
+    /// A future that collects an incoming body stream into bytes and deserializes it into a JSON Patch document.
+    pub struct ExtractJsonPatchPayloadFut {
+        #[pin]
+        fut: Collect<HttpBody>,
+        is_supported: bool
+    }
+}
+
+impl Future for ExtractJsonPatchPayloadFut {
+    type Output = Result<JsonPatch, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if !*this.is_supported {
+            return Poll::Ready(Err(JsonPatchError::unsupported_content_type()));
+        }
+        let result = ready!(this.fut.poll(cx))
+            .map_err(JsonPatchError::collect_error)?;
+        let body = result.to_bytes();
+        let patch = serde_json::from_slice(&body)
+            .map_err(JsonPatchError::from_serde_error)
+            .and_then(JsonPatch::from_value);
+        Poll::Ready(patch)
+    }
+}
+
+/// Extracts JSON Patch document from request body into `JsonPatch`
+impl FromPayload for JsonPatch {
+    type Future = ExtractJsonPatchPayloadFut;
+
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Parts(parts, body) = payload {
+            ExtractJsonPatchPayloadFut {
+                fut: body.collect(),
+                is_supported: is_json_patch(parts)
+            }
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn source() -> Source {
+        Source::Parts
+    }
+}
+
+#[inline]
+fn is_json_patch(parts: &Parts) -> bool {
+    parts.headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim)
+        .is_some_and(|essence| essence.eq_ignore_ascii_case(JSON_PATCH_CONTENT_TYPE))
+}
+
+struct JsonPatchError;
+
+impl JsonPatchError {
+    #[inline]
+    fn from_serde_error(err: serde_json::Error) -> Error {
+        Error::client_error(format!("JSON patch error: {}", err))
+    }
+
+    #[inline]
+    fn collect_error(err: Error) -> Error {
+        Error::from_parts(err.status, None, format!("JSON patch error: {}", err))
+    }
+
+    #[inline]
+    fn invalid_document(reason: impl Into<String>) -> Error {
+        Error::client_error(format!("JSON patch error: {}", reason.into()))
+    }
+
+    #[inline]
+    fn path_not_found(path: &str) -> Error {
+        Self::conflict(format!("path `{path}` does not exist"))
+    }
+
+    #[inline]
+    fn conflict(reason: impl Into<String>) -> Error {
+        Error::from_parts(
+            StatusCode::CONFLICT,
+            None,
+            format!("JSON patch error: {}", reason.into()))
+    }
+
+    #[inline]
+    fn unsupported_content_type() -> Error {
+        Error::from_parts(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            None,
+            "JSON patch error: unsupported content type")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Request;
+    use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
+    use crate::HttpBody;
+    use crate::headers::CONTENT_TYPE;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::{JsonPatch, PatchOperation};
+
+    #[derive(Serialize, Deserialize)]
+    struct User {
+        name: String,
+        age: i32,
+    }
+
+    fn patch(operations: Value) -> JsonPatch {
+        JsonPatch::from_value(operations).unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let req = Request::patch("/")
+            .header(CONTENT_TYPE, "application/json-patch+json")
+            .body(HttpBody::boxed(HttpBody::json(json!([
+                { "op": "replace", "path": "/name", "value": "Jack" }
+            ]))))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let patch = JsonPatch::from_payload(Payload::Parts(&parts, body)).await.unwrap();
+
+        assert_eq!(patch.0, vec![PatchOperation::Replace { path: "/name".into(), value: json!("Jack") }]);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unsupported_content_type() {
+        let req = Request::patch("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body(HttpBody::boxed(HttpBody::json(json!([]))))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let err = JsonPatch::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+
+        assert_eq!(err.into_parts().0, 415);
+    }
+
+    #[test]
+    fn it_rejects_unknown_operation() {
+        let err = JsonPatch::from_value(json!([{ "op": "merge", "path": "/a" }])).unwrap_err();
+
+        assert!(err.is_client_error());
+    }
+
+    #[test]
+    fn it_replaces_value() {
+        let user = User { name: "John".into(), age: 33 };
+
+        let user = patch(json!([
+            { "op": "test", "path": "/name", "value": "John" },
+            { "op": "replace", "path": "/name", "value": "Jack" }
+        ])).apply_typed(&user).unwrap();
+
+        assert_eq!(user.name, "Jack");
+        assert_eq!(user.age, 33);
+    }
+
+    #[test]
+    fn it_returns_conflict_if_test_fails() {
+        let mut target = json!({ "name": "John" });
+
+        let err = patch(json!([
+            { "op": "replace", "path": "/name", "value": "Jack" },
+            { "op": "test", "path": "/name", "value": "John" }
+        ])).apply(&mut target).unwrap_err();
+
+        assert_eq!(err.status, 409);
+        assert_eq!(target, json!({ "name": "John" }));
+    }
+
+    #[test]
+    fn it_adds_and_removes_values() {
+        let mut target = json!({ "tags": ["a", "c"], "old": true });
+
+        patch(json!([
+            { "op": "add", "path": "/tags/1", "value": "b" },
+            { "op": "add", "path": "/tags/-", "value": "d" },
+            { "op": "add", "path": "/a~1b", "value": 1 },
+            { "op": "remove", "path": "/old" }
+        ])).apply(&mut target).unwrap();
+
+        assert_eq!(target, json!({ "tags": ["a", "b", "c", "d"], "a/b": 1 }));
+    }
+
+    #[test]
+    fn it_moves_and_copies_values() {
+        let mut target = json!({ "a": { "b": 1 }, "c": [] });
+
+        patch(json!([
+            { "op": "copy", "from": "/a/b", "path": "/c/0" },
+            { "op": "move", "from": "/a/b", "path": "/d" }
+        ])).apply(&mut target).unwrap();
+
+        assert_eq!(target, json!({ "a": {}, "c": [1], "d": 1 }));
+    }
+
+    #[test]
+    fn it_returns_conflict_for_missing_path() {
+        let mut target = json!({ "a": 1 });
+
+        let err = patch(json!([{ "op": "remove", "path": "/b" }]))
+            .apply(&mut target)
+            .unwrap_err();
+
+        assert_eq!(err.status, 409);
+    }
+
+    #[test]
+    fn it_does_not_move_value_into_its_child() {
+        let mut target = json!({ "a": { "b": {} } });
+
+        let err = patch(json!([{ "op": "move", "from": "/a", "path": "/a/b/c" }]))
+            .apply(&mut target)
+            .unwrap_err();
+
+        assert_eq!(err.status, 409);
+    }
+}
@@ -0,0 +1,232 @@
+﻿//! Extractors for line-by-line request body streaming
+
+use bytes::{Buf, BytesMut};
+use futures_util::{future::{ready, Ready}, ready, Stream};
+use http_body_util::{BodyDataStream, BodyExt};
+use pin_project_lite::pin_project;
+use crate::{error::Error, HttpBody};
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+use crate::http::endpoints::args::{
+    FromPayload,
+    Payload,
+    Source
+};
+
+/// Default maximum length of a single line in bytes
+/// 
+/// Default: 64 KB
+const DEFAULT_MAX_LINE_LENGTH: usize = 64 * 1024;
+
+pin_project! {
+    /// A stream of lines read from the request body
+    /// 
+    /// The body is read chunk by chunk and split on `\n` (a trailing `\r` is trimmed), 
+    /// so the whole body is never buffered in memory. 
+    /// If a line exceeds the maximum length, the stream yields an error and ends.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::{HttpResult, Lines, ok};
+    /// use futures_util::TryStreamExt;
+    ///
+    /// async fn handle(lines: Lines) -> HttpResult {
+    ///     let mut lines = lines.with_max_length(1024);
+    ///     let mut count = 0;
+    ///     while let Some(line) = lines.try_next().await? {
+    ///         println!("{line}");
+    ///         count += 1;
+    ///     }
+    ///     ok!("Received {count} lines")
+    /// }
+    /// ```
+    pub struct Lines {
+        #[pin]
+        body: BodyDataStream<HttpBody>,
+        buffer: BytesMut,
+        scanned: usize,
+        max_length: usize,
+        eof: bool,
+        done: bool
+    }
+}
+
+impl Lines {
+    /// Creates a new stream of lines from the [`HttpBody`]
+    #[inline]
+    fn new(body: HttpBody) -> Self {
+        Self {
+            body: body.into_data_stream(),
+            buffer: BytesMut::new(),
+            scanned: 0,
+            max_length: DEFAULT_MAX_LINE_LENGTH,
+            eof: false,
+            done: false
+        }
+    }
+    
+    /// Sets the maximum length of a single line in bytes
+    /// 
+    /// Default: 64 KB
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    #[inline]
+    fn decode(mut line: BytesMut) -> Result<String, Error> {
+        if line.last() == Some(&b'\r') {
+            line.truncate(line.len() - 1);
+        }
+        String::from_utf8(line.to_vec())
+            .map_err(LinesError::from_utf8_error)
+    }
+}
+
+impl Stream for Lines {
+    type Item = Result<String, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        loop {
+            if *this.done {
+                return Poll::Ready(None);
+            }
+            
+            if let Some(offset) = this.buffer[*this.scanned..].iter().position(|b| *b == b'\n') {
+                let line_length = *this.scanned + offset;
+                *this.scanned = 0;
+                if line_length > *this.max_length {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(LinesError::line_too_long(*this.max_length))));
+                }
+                
+                let line = this.buffer.split_to(line_length);
+                this.buffer.advance(1);
+                return Poll::Ready(Some(Self::decode(line)));
+            }
+            
+            *this.scanned = this.buffer.len();
+            if *this.scanned > *this.max_length {
+                *this.done = true;
+                return Poll::Ready(Some(Err(LinesError::line_too_long(*this.max_length))));
+            }
+            
+            if *this.eof {
+                *this.done = true;
+                if this.buffer.is_empty() {
+                    return Poll::Ready(None);
+                }
+                let line = this.buffer.split();
+                return Poll::Ready(Some(Self::decode(line)));
+            }
+            
+            match ready!(this.body.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => this.buffer.extend_from_slice(&chunk),
+                Some(Err(err)) => {
+                    *this.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                },
+                None => *this.eof = true
+            }
+        }
+    }
+}
+
+/// Extracts request body as a stream of lines
+impl FromPayload for Lines {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Body(body) = payload {
+            ready(Ok(Lines::new(body)))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Body
+    }
+}
+
+/// Describes errors of lines extractor
+struct LinesError;
+
+impl LinesError {
+    #[inline]
+    fn from_utf8_error(err: std::string::FromUtf8Error) -> Error {
+        Error::client_error(format!("Line reading error: {}", err))
+    }
+
+    #[inline]
+    fn line_too_long(max_length: usize) -> Error {
+        Error::client_error(format!("Line reading error: line exceeds the maximum length of {max_length} bytes"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use futures_util::{stream, TryStreamExt};
+    use http_body_util::StreamBody;
+    use hyper::body::Frame;
+    use super::Lines;
+    use crate::error::Error;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use crate::HttpBody;
+    
+    fn chunked_body(chunks: &[&'static str]) -> HttpBody {
+        let chunks = chunks
+            .iter()
+            .map(|chunk| Ok::<_, Error>(Frame::data(Bytes::from_static(chunk.as_bytes()))))
+            .collect::<Vec<_>>();
+        HttpBody::boxed(StreamBody::new(stream::iter(chunks)))
+    }
+
+    #[tokio::test]
+    async fn it_reads_lines_from_payload() {
+        let body = HttpBody::full("first\nsecond\r\nthird");
+        
+        let lines = Lines::from_payload(Payload::Body(body)).await.unwrap();
+        let lines: Vec<String> = lines.try_collect().await.unwrap();
+        
+        assert_eq!(lines, ["first", "second", "third"]);
+    }
+
+    #[tokio::test]
+    async fn it_reads_lines_split_across_chunks() {
+        let body = chunked_body(&["fir", "st\nsec", "ond\n", "\nthi", "rd\n"]);
+
+        let lines = Lines::from_payload(Payload::Body(body)).await.unwrap();
+        let lines: Vec<String> = lines.try_collect().await.unwrap();
+
+        assert_eq!(lines, ["first", "second", "", "third"]);
+    }
+
+    #[tokio::test]
+    async fn it_returns_error_for_too_long_line() {
+        let body = chunked_body(&["short\n", "very long", " line\n"]);
+
+        let mut lines = Lines::from_payload(Payload::Body(body)).await.unwrap()
+            .with_max_length(8);
+
+        assert_eq!(lines.try_next().await.unwrap().unwrap(), "short");
+        assert!(lines.try_next().await.is_err());
+        assert!(lines.try_next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn it_reads_empty_body_as_no_lines() {
+        let lines = Lines::from_payload(Payload::Body(HttpBody::empty())).await.unwrap();
+        let lines: Vec<String> = lines.try_collect().await.unwrap();
+
+        assert!(lines.is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+﻿//! Extractors for JSON Merge Patch documents (RFC 7386)
+
+use futures_util::ready;
+use pin_project_lite::pin_project;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use http_body_util::{combinators::Collect, BodyExt};
+use crate::{error::Error, HttpBody};
+
+use std::{
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+use crate::http::{
+    endpoints::args::{
+        FromPayload,
+        Payload,
+        Source
+    }
+};
+
+/// Wraps a JSON Merge Patch document ([RFC 7386](https://www.rfc-editor.org/rfc/rfc7386))
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, MergePatch, ok};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct User {
+///     name: String,
+///     email: Option<String>
+/// }
+///
+/// async fn handle(patch: MergePatch) -> HttpResult {
+///     let user = User { name: "John".into(), email: None };
+///     let user = patch.apply_typed(&user)?;
+///     ok!(user)
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MergePatch(pub Value);
+
+impl MergePatch {
+    /// Unwraps the inner patch document
+    pub fn into_inner(self) -> Value {
+        self.0
+    }
+
+    /// Applies the patch to the `target` JSON value in place
+    ///
+    /// Object members are merged recursively, `null` members remove the corresponding keys
+    /// and any other value replaces the target entirely.
+    pub fn apply(&self, target: &mut Value) {
+        merge(target, &self.0);
+    }
+
+    /// Applies the patch to a copy of the `base` value and returns the patched one
+    pub fn apply_typed<T: Serialize + DeserializeOwned>(&self, base: &T) -> Result<T, Error> {
+        let mut target = serde_json::to_value(base)
+            .map_err(MergePatchError::from_serde_error)?;
+        self.apply(&mut target);
+        serde_json::from_value(target)
+            .map_err(MergePatchError::from_serde_error)
+    }
+}
+
+impl Deref for MergePatch {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.0
+    }
+}
+
+/// Implements the `MergePatch` algorithm from RFC 7386, section 2
+fn merge(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge(target.entry(key.as_str()).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// A future that collects an incoming body stream into bytes and deserializes it into a merge patch document.
+    pub struct ExtractMergePatchPayloadFut {
+        #[pin]
+        fut: Collect<HttpBody>
+    }
+}
+
+impl Future for ExtractMergePatchPayloadFut {
+    type Output = Result<MergePatch, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = ready!(this.fut.poll(cx))
+            .map_err(MergePatchError::collect_error)?;
+        let body = result.to_bytes();
+        let patch = serde_json::from_slice(&body)
+            .map(MergePatch)
+            .map_err(MergePatchError::from_serde_error);
+        Poll::Ready(patch)
+    }
+}
+
+/// Extracts JSON Merge Patch document from request body into `MergePatch`
+impl FromPayload for MergePatch {
+    type Future = ExtractMergePatchPayloadFut;
+
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Body(body) = payload {
+            ExtractMergePatchPayloadFut { fut: body.collect() }
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn source() -> Source {
+        Source::Body
+    }
+}
+
+struct MergePatchError;
+
+impl MergePatchError {
+    #[inline]
+    fn from_serde_error(err: serde_json::Error) -> Error {
+        Error::client_error(format!("Merge patch error: {}", err))
+    }
+
+    #[inline]
+    fn collect_error(err: Error) -> Error {
+        Error::from_parts(err.status, None, format!("Merge patch error: {}", err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use crate::HttpBody;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::MergePatch;
+
+    #[derive(Serialize, Deserialize)]
+    struct User {
+        name: String,
+        email: Option<String>,
+        age: i32,
+    }
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let body = HttpBody::boxed(HttpBody::json(json!({ "name": "John" })));
+
+        let patch = MergePatch::from_payload(Payload::Body(body)).await.unwrap();
+
+        assert_eq!(patch.0, json!({ "name": "John" }));
+    }
+
+    #[test]
+    fn it_removes_keys_with_null() {
+        let mut target = json!({ "a": "b", "c": "d" });
+
+        MergePatch(json!({ "a": null })).apply(&mut target);
+
+        assert_eq!(target, json!({ "c": "d" }));
+    }
+
+    #[test]
+    fn it_merges_nested_objects() {
+        let mut target = json!({
+            "title": "Goodbye!",
+            "author": { "givenName": "John", "familyName": "Doe" },
+            "tags": ["example", "sample"]
+        });
+
+        MergePatch(json!({
+            "title": "Hello!",
+            "author": { "familyName": null, "phone": "+01-123-456-7890" },
+            "tags": ["example"]
+        })).apply(&mut target);
+
+        assert_eq!(target, json!({
+            "title": "Hello!",
+            "author": { "givenName": "John", "phone": "+01-123-456-7890" },
+            "tags": ["example"]
+        }));
+    }
+
+    #[test]
+    fn it_replaces_non_object_target() {
+        let mut target = json!(["a", "b"]);
+
+        MergePatch(json!({ "a": { "b": null } })).apply(&mut target);
+
+        assert_eq!(target, json!({ "a": {} }));
+    }
+
+    #[test]
+    fn it_applies_to_struct() {
+        let user = User { name: "John".into(), email: Some("john@example.com".into()), age: 33 };
+
+        let user = MergePatch(json!({ "email": null, "age": 34 })).apply_typed(&user).unwrap();
+
+        assert_eq!(user.name, "John");
+        assert_eq!(user.email, None);
+        assert_eq!(user.age, 34);
+    }
+
+    #[test]
+    fn it_returns_error_if_patched_value_is_invalid() {
+        let user = User { name: "John".into(), email: None, age: 33 };
+
+        let user = MergePatch(json!({ "age": "old" })).apply_typed(&user);
+
+        assert!(user.is_err());
+    }
+}
@@ -1,16 +1,20 @@
 ﻿//! Extractors for multipart/form data
 
 use bytes::Bytes;
-use futures_util::future::{ready, Ready};
+use futures_util::{future::{ready, Ready}, Stream};
 use http_body_util::BodyExt;
+use pin_project_lite::pin_project;
 use tokio::io::{AsyncWriteExt, BufWriter};
 
 use std::{
     ops::{Deref, DerefMut},
-    path::Path
+    path::Path,
+    pin::Pin,
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    task::{Context, Poll}
 };
 
-use crate::error::Error;
+use crate::{App, error::Error};
 use crate::headers::{HeaderMap, CONTENT_TYPE};
 use crate::http::{
     endpoints::args::{
@@ -20,8 +24,39 @@ use crate::http::{
     }
 };
 
+/// Default maximum length of a field name (in bytes)
+pub const DEFAULT_FIELD_NAME_LIMIT: usize = 256;
+
+/// Default maximum size of headers of a single part (in bytes)
+pub const DEFAULT_PART_HEADERS_LIMIT: usize = 8 * 1024;
+
+const CRLF_CRLF: &[u8] = b"\r\n\r\n";
+
+/// App-wide limits of multipart field names and part headers
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct MultipartLimits {
+    pub(crate) field_name: usize,
+    pub(crate) part_headers: usize
+}
+
+impl Default for MultipartLimits {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            field_name: DEFAULT_FIELD_NAME_LIMIT,
+            part_headers: DEFAULT_PART_HEADERS_LIMIT
+        }
+    }
+}
+
 /// Describes a multipart file/form data
 ///
+/// Parts whose field name or headers exceed the configured limits are rejected with `400 Bad Request`.
+/// The part headers limit is enforced while the request body is parsed, so oversized headers are never buffered.
+/// The limits can be configured app-wide with [`App::with_multipart_field_name_limit`](crate::App::with_multipart_field_name_limit) 
+/// and [`App::with_multipart_part_headers_limit`](crate::App::with_multipart_part_headers_limit), 
+/// or for a single extractor with [`Multipart::with_field_name_limit`] and [`Multipart::with_part_headers_limit`].
+///
 /// # Example
 /// ```no_run
 /// use volga::{HttpResult, Multipart, ok};
@@ -31,7 +66,39 @@ use crate::http::{
 ///     ok!("Files saved!")
 /// }
 /// ```
-pub struct Multipart(multer::Multipart<'static>);
+pub struct Multipart {
+    inner: multer::Multipart<'static>,
+    field_name_limit: usize,
+    part_headers_limit: Arc<AtomicUsize>
+}
+
+pin_project! {
+    /// Wraps the request body stream and fails it once headers of a part exceed the limit,
+    /// since `multer` buffers part headers until their end regardless of their size
+    struct PartHeadersGuard<S> {
+        #[pin]
+        inner: S,
+        scanner: PartHeadersScanner,
+        limit: Arc<AtomicUsize>
+    }
+}
+
+/// Tracks the multipart framing of the body to count the size of part headers
+struct PartHeadersScanner {
+    delimiter: Vec<u8>,
+    state: ScanState
+}
+
+enum ScanState {
+    /// Looking for the `\r\n--boundary` delimiter, holds the number of matched bytes
+    Body(usize),
+    /// Skipping the rest of the delimiter line, the closing delimiter ends the scan
+    Delimiter,
+    /// Reading part headers, holds their size so far and the number of matched `\r\n\r\n` bytes
+    Headers(usize, usize),
+    /// The closing delimiter has been read
+    Epilogue
+}
 
 /// Represents a single field in a multipart stream
 /// 
@@ -151,12 +218,41 @@ impl Multipart {
         Ok(())
     }
     
+    /// Sets the maximum length of a field name (in bytes)
+    ///
+    /// Default: 256 bytes
+    pub fn with_field_name_limit(mut self, limit: usize) -> Self {
+        self.field_name_limit = limit;
+        self
+    }
+
+    /// Sets the maximum size of headers of a single part (in bytes)
+    ///
+    /// Default: 8 KB
+    pub fn with_part_headers_limit(self, limit: usize) -> Self {
+        self.part_headers_limit.store(limit, Ordering::Relaxed);
+        self
+    }
+    
     /// Yields the next [`Field`] if available
     #[inline]
     pub async fn next_field(&mut self) -> Result<Option<Field>, Error> {
-        self.0.next_field().await
-            .map_err(MultipartError::read_error)
-            .map(|field| field.map(Field))
+        let field = self.inner.next_field().await
+            .map_err(MultipartError::read_error)?;
+        field
+            .map(|field| self.validate(field))
+            .transpose()
+    }
+
+    /// Checks the field name length, it can't exceed the part headers limit 
+    /// that is enforced while parsing, so the name is never buffered beyond it
+    #[inline]
+    fn validate(&self, field: multer::Field<'static>) -> Result<Field, Error> {
+        let name_len = field.name().map_or(0, str::len);
+        if name_len > self.field_name_limit {
+            return Err(MultipartError::field_name_too_long(self.field_name_limit));
+        }
+        Ok(Field(field))
     }
     
     #[inline]
@@ -170,13 +266,13 @@ impl Deref for Multipart {
     type Target = multer::Multipart<'static>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.inner
     }
 }
 
 impl DerefMut for Multipart {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.inner
     }
 }
 
@@ -189,16 +285,144 @@ impl<'a> TryFrom<Payload<'a>> for Multipart {
             let boundary = Self::parse_boundary(&parts.headers)
                 .ok_or(MultipartError::invalid_boundary())?;
 
-            let stream = body.into_data_stream();
+            let limits = parts.extensions
+                .get::<MultipartLimits>()
+                .copied()
+                .unwrap_or_default();
+            let part_headers_limit = Arc::new(AtomicUsize::new(limits.part_headers));
+            let stream = PartHeadersGuard {
+                inner: body.into_data_stream(),
+                scanner: PartHeadersScanner::new(&boundary),
+                limit: part_headers_limit.clone()
+            };
             let multipart = multer::Multipart::new(stream, boundary);
 
-            Ok(Multipart(multipart))
+            Ok(Multipart {
+                inner: multipart,
+                field_name_limit: limits.field_name,
+                part_headers_limit
+            })
         } else {
             unreachable!()
         }
     }
 }
 
+/// Multipart specific impl for [`App`]
+impl App {
+    /// Sets the app-wide maximum length of a multipart field name (in bytes)
+    /// 
+    /// It can be overridden for a single extractor with [`Multipart::with_field_name_limit`].
+    ///
+    /// Default: 256 bytes
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_multipart_field_name_limit(64);
+    /// ```
+    pub fn with_multipart_field_name_limit(mut self, limit: usize) -> Self {
+        self.multipart_limits.field_name = limit;
+        self
+    }
+
+    /// Sets the app-wide maximum size of headers of a single multipart part (in bytes)
+    /// 
+    /// It can be overridden for a single extractor with [`Multipart::with_part_headers_limit`].
+    ///
+    /// Default: 8 KB
+    /// 
+    ///# Examples
+    /// ```no_run
+    ///use volga::App;
+    ///
+    ///let app = App::new().with_multipart_part_headers_limit(1024);
+    /// ```
+    pub fn with_multipart_part_headers_limit(mut self, limit: usize) -> Self {
+        self.multipart_limits.part_headers = limit;
+        self
+    }
+}
+
+impl<S, E> Stream for PartHeadersGuard<S>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: Into<Error>
+{
+    type Item = Result<Bytes, Error>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        match this.inner.poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                let limit = this.limit.load(Ordering::Relaxed);
+                if this.scanner.scan(&chunk, limit) {
+                    Poll::Ready(Some(Ok(chunk)))
+                } else {
+                    Poll::Ready(Some(Err(MultipartError::part_headers_too_large(limit))))
+                }
+            },
+            Poll::Ready(Some(Err(err))) => Poll::Ready(Some(Err(err.into()))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+impl PartHeadersScanner {
+    #[inline]
+    fn new(boundary: &str) -> Self {
+        let delimiter = [b"\r\n--", boundary.as_bytes()].concat();
+        // The first delimiter may be at the very beginning of the body, without the leading CRLF
+        Self { delimiter, state: ScanState::Body(2) }
+    }
+
+    /// Scans the next `chunk` of the body, returns `false` if part headers exceed the `limit`
+    fn scan(&mut self, chunk: &[u8], limit: usize) -> bool {
+        for &byte in chunk {
+            self.state = match self.state {
+                ScanState::Body(matched) => match Self::advance(&self.delimiter, matched, byte) {
+                    matched if matched == self.delimiter.len() => ScanState::Delimiter,
+                    matched => ScanState::Body(matched)
+                },
+                ScanState::Delimiter => match byte {
+                    b'-' => ScanState::Epilogue,
+                    b'\n' => ScanState::Headers(0, 0),
+                    _ => ScanState::Delimiter
+                },
+                ScanState::Headers(size, matched) => {
+                    if size >= limit {
+                        return false;
+                    }
+                    match Self::advance(CRLF_CRLF, matched, byte) {
+                        4 => ScanState::Body(0),
+                        matched => ScanState::Headers(size + 1, matched)
+                    }
+                },
+                ScanState::Epilogue => return true
+            };
+        }
+        true
+    }
+
+    /// Returns the number of matched bytes of the `pattern` after the next `byte`
+    /// 
+    /// Both the delimiter and `\r\n\r\n` start with `\r` that doesn't occur in the rest of them 
+    /// except for the `\r\n` prefix, so a mismatch only needs to check whether the `byte` starts a new match.
+    #[inline]
+    fn advance(pattern: &[u8], matched: usize, byte: u8) -> usize {
+        if pattern[matched] == byte {
+            matched + 1
+        } else if pattern[0] == byte {
+            1
+        } else {
+            0
+        }
+    }
+}
+
 /// Extracts a file stream from request body
 impl FromPayload for Multipart {
     type Future = Ready<Result<Self, Error>>;
@@ -228,14 +452,33 @@ impl MultipartError {
 
     #[inline]
     fn read_error(error: multer::Error) -> Error {
-        Error::client_error(format!("Multipart error: {error}"))
+        match error {
+            multer::Error::StreamReadFailed(err) => match err.downcast::<Error>() {
+                Ok(err) => *err,
+                Err(err) => Error::client_error(format!("Multipart error: failed to read stream: {err}"))
+            },
+            error => Error::client_error(format!("Multipart error: {error}"))
+        }
+    }
+
+    #[inline]
+    fn field_name_too_long(limit: usize) -> Error {
+        Error::client_error(format!("Multipart error: field name exceeds the limit of {limit} bytes"))
+    }
+
+    #[inline]
+    fn part_headers_too_large(limit: usize) -> Error {
+        Error::client_error(format!("Multipart error: part headers exceed the limit of {limit} bytes"))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Multipart;
-    use hyper::Request;
+    use super::{Multipart, MultipartLimits, DEFAULT_FIELD_NAME_LIMIT};
+    use bytes::Bytes;
+    use futures_util::{stream, StreamExt};
+    use http_body_util::StreamBody;
+    use hyper::{body::Frame, Request};
     use crate::headers::CONTENT_TYPE;
     use crate::http::body::HttpBody;
     use crate::http::endpoints::args::{FromPayload, Payload};
@@ -258,4 +501,100 @@ mod tests {
         }
     }
 
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn it_rejects_overlong_field_name() {
+        let name = "a".repeat(DEFAULT_FIELD_NAME_LIMIT + 1);
+        let data = format!("--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\nabcd\r\n--X-BOUNDARY--\r\n");
+
+        let req = Request::get("/")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X-BOUNDARY")
+            .body(HttpBody::boxed(HttpBody::full(data)))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let mut multipart = Multipart::from_payload(Payload::Parts(&parts, body)).await.unwrap();
+        let err = multipart.next_field().await.err().unwrap();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_oversize_part_headers() {
+        let data = "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"my_text_field\"\r\nContent-Type: text/plain\r\n\r\nabcd\r\n--X-BOUNDARY--\r\n";
+
+        let req = Request::get("/")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X-BOUNDARY")
+            .body(HttpBody::boxed(HttpBody::full(data)))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let mut multipart = Multipart::from_payload(Payload::Parts(&parts, body)).await.unwrap()
+            .with_part_headers_limit(64);
+        let err = multipart.next_field().await.err().unwrap();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_oversize_part_headers_before_they_end() {
+        let data = format!("--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"{}\"", "a".repeat(1024));
+        // The stream never ends, so the part headers can't be read to the end
+        let stream = stream::once(async move { Ok(Frame::data(Bytes::from(data))) })
+            .chain(stream::pending());
+
+        let req = Request::get("/")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X-BOUNDARY")
+            .body(HttpBody::boxed(StreamBody::new(stream)))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let mut multipart = Multipart::from_payload(Payload::Parts(&parts, body)).await.unwrap()
+            .with_part_headers_limit(512);
+        let err = multipart.next_field().await.err().unwrap();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[tokio::test]
+    async fn it_uses_app_wide_limits() {
+        let data = "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"my_text_field\"\r\n\r\nabcd\r\n--X-BOUNDARY--\r\n";
+
+        let mut req = Request::get("/")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X-BOUNDARY")
+            .body(HttpBody::boxed(HttpBody::full(data)))
+            .unwrap();
+        req.extensions_mut().insert(MultipartLimits { field_name: 4, ..Default::default() });
+
+        let (parts, body) = req.into_parts();
+        let mut multipart = Multipart::from_payload(Payload::Parts(&parts, body)).await.unwrap();
+        let err = multipart.next_field().await.err().unwrap();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[tokio::test]
+    async fn it_reads_fields_split_across_chunks() {
+        let data = "--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nabcd\r\n--X-BOUNDARY\r\nContent-Disposition: form-data; name=\"b\"\r\n\r\nefgh\r\n--X-BOUNDARY--\r\n";
+        let chunks = data
+            .as_bytes()
+            .chunks(3)
+            .map(|chunk| Ok(Frame::data(Bytes::copy_from_slice(chunk))))
+            .collect::<Vec<_>>();
+
+        let req = Request::get("/")
+            .header(CONTENT_TYPE, "multipart/form-data; boundary=X-BOUNDARY")
+            .body(HttpBody::boxed(StreamBody::new(stream::iter(chunks))))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let mut multipart = Multipart::from_payload(Payload::Parts(&parts, body)).await.unwrap()
+            .with_part_headers_limit(64);
+
+        let mut values = Vec::new();
+        while let Some(field) = multipart.next_field().await.unwrap() {
+            values.push(field.text().await.unwrap());
+        }
+
+        assert_eq!(values, ["abcd", "efgh"]);
+    }
+}
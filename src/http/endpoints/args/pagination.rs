@@ -0,0 +1,288 @@
+﻿//! Extractors for pagination parameters
+
+use futures_util::future::{ready, Ready};
+use hyper::Uri;
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Default number of items per page
+pub const DEFAULT_PER_PAGE: usize = 20;
+
+/// Default maximum number of items per page
+pub const MAX_PER_PAGE: usize = 100;
+
+/// Holds validated pagination parameters of the request query
+///
+/// Parameters could be specified either as `?page=&per_page=` or as `?limit=&offset=`.
+/// If not specified, the first page of `DEFAULT` items is used, and the number of items per page
+/// is clamped to `MAX`. Invalid, negative or zero values are rejected with `400 Bad Request`.
+///
+/// Both `DEFAULT` and `MAX` must be greater than zero, otherwise the extractor fails to compile.
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, Pagination, ok};
+///
+/// async fn handle(pagination: Pagination<10, 50>) -> HttpResult {
+///     ok!("offset: {}, limit: {}", pagination.offset(), pagination.limit())
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Pagination<const DEFAULT: usize = DEFAULT_PER_PAGE, const MAX: usize = MAX_PER_PAGE> {
+    offset: usize,
+    limit: usize
+}
+
+#[derive(Default)]
+struct PaginationQuery {
+    page: Option<String>,
+    per_page: Option<String>,
+    limit: Option<String>,
+    offset: Option<String>
+}
+
+impl PaginationQuery {
+    #[inline]
+    fn from_query_str(query_str: &str) -> Result<Self, Error> {
+        let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(query_str)
+            .map_err(PaginationError::from_query_error)?;
+        let mut query = Self::default();
+        for (key, value) in pairs {
+            match key.as_str() {
+                "page" => query.page = Some(value),
+                "per_page" => query.per_page = Some(value),
+                "limit" => query.limit = Some(value),
+                "offset" => query.offset = Some(value),
+                _ => {}
+            }
+        }
+        Ok(query)
+    }
+}
+
+impl<const DEFAULT: usize, const MAX: usize> Default for Pagination<DEFAULT, MAX> {
+    #[inline]
+    fn default() -> Self {
+        let () = Self::ASSERT_LIMITS;
+        Self { offset: 0, limit: DEFAULT.min(MAX) }
+    }
+}
+
+impl<const DEFAULT: usize, const MAX: usize> Pagination<DEFAULT, MAX> {
+    /// Rejects zero limits at compile time, so the `limit` is never zero
+    const ASSERT_LIMITS: () = assert!(DEFAULT > 0 && MAX > 0, "pagination limits must be greater than zero");
+
+    /// Returns the number of items to skip
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the maximum number of items to return
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the 1-based page number
+    #[inline]
+    pub fn page(&self) -> usize {
+        self.offset / self.limit + 1
+    }
+
+    /// Returns the number of items per page, the same as [`Pagination::limit`]
+    #[inline]
+    pub fn per_page(&self) -> usize {
+        self.limit
+    }
+
+    /// Parses the query string slice into [`Pagination`]
+    pub(crate) fn from_query_str(query_str: &str) -> Result<Self, Error> {
+        let () = Self::ASSERT_LIMITS;
+        let query = PaginationQuery::from_query_str(query_str)?;
+
+        if query.page.is_some() && query.offset.is_some() {
+            return Err(PaginationError::ambiguous("page", "offset"));
+        }
+        if query.per_page.is_some() && query.limit.is_some() {
+            return Err(PaginationError::ambiguous("per_page", "limit"));
+        }
+
+        let limit = match (query.per_page, query.limit) {
+            (Some(per_page), _) => parse_positive("per_page", &per_page)?,
+            (_, Some(limit)) => parse_positive("limit", &limit)?,
+            _ => DEFAULT
+        };
+        let limit = limit.min(MAX);
+
+        let offset = match (query.page, query.offset) {
+            (Some(page), _) => (parse_positive("page", &page)? - 1)
+                .checked_mul(limit)
+                .ok_or_else(|| PaginationError::out_of_range("page"))?,
+            (_, Some(offset)) => parse("offset", &offset)?,
+            _ => 0
+        };
+
+        Ok(Self { offset, limit })
+    }
+
+    /// Parses the request [`Uri`] into [`Pagination`]
+    #[inline]
+    pub(crate) fn from_uri(uri: &Uri) -> Result<Self, Error> {
+        Self::from_query_str(uri.query().unwrap_or(""))
+    }
+}
+
+#[inline]
+fn parse(name: &str, value: &str) -> Result<usize, Error> {
+    match value.trim().parse::<i64>() {
+        Ok(value) if value < 0 => Err(PaginationError::negative(name)),
+        Ok(value) => usize::try_from(value).map_err(|_| PaginationError::out_of_range(name)),
+        Err(_) => Err(PaginationError::not_a_number(name))
+    }
+}
+
+#[inline]
+fn parse_positive(name: &str, value: &str) -> Result<usize, Error> {
+    match parse(name, value)? {
+        0 => Err(PaginationError::zero(name)),
+        value => Ok(value)
+    }
+}
+
+/// Extracts pagination parameters from request `Uri` query
+impl<const DEFAULT: usize, const MAX: usize> FromRequestRef for Pagination<DEFAULT, MAX> {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Self::from_uri(req.uri())
+    }
+}
+
+/// Extracts pagination parameters from request `Uri` query
+impl<const DEFAULT: usize, const MAX: usize> FromPayload for Pagination<DEFAULT, MAX> {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Query(uri) = payload {
+            ready(Self::from_uri(uri))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Query
+    }
+}
+
+/// Describes errors of pagination extractor
+struct PaginationError;
+
+impl PaginationError {
+    #[inline]
+    fn from_query_error(err: serde::de::value::Error) -> Error {
+        Error::client_error(format!("Pagination error: {}", err))
+    }
+
+    #[inline]
+    fn not_a_number(name: &str) -> Error {
+        Error::client_error(format!("Pagination error: `{}` must be an integer", name))
+    }
+
+    #[inline]
+    fn negative(name: &str) -> Error {
+        Error::client_error(format!("Pagination error: `{}` must not be negative", name))
+    }
+
+    #[inline]
+    fn zero(name: &str) -> Error {
+        Error::client_error(format!("Pagination error: `{}` must be greater than zero", name))
+    }
+
+    #[inline]
+    fn out_of_range(name: &str) -> Error {
+        Error::client_error(format!("Pagination error: `{}` is out of range", name))
+    }
+
+    #[inline]
+    fn ambiguous(first: &str, second: &str) -> Error {
+        Error::client_error(format!("Pagination error: `{}` and `{}` cannot be used together", first, second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Uri;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::Pagination;
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let uri = "https://www.example.com/api/users?page=3&per_page=10".parse::<Uri>().unwrap();
+
+        let pagination = Pagination::<20, 100>::from_payload(Payload::Query(&uri)).await.unwrap();
+
+        assert_eq!(pagination.page(), 3);
+        assert_eq!(pagination.per_page(), 10);
+        assert_eq!(pagination.offset(), 20);
+        assert_eq!(pagination.limit(), 10);
+    }
+
+    #[test]
+    fn it_applies_defaults() {
+        let pagination = Pagination::<25, 100>::from_query_str("").unwrap();
+
+        assert_eq!(pagination.page(), 1);
+        assert_eq!(pagination.offset(), 0);
+        assert_eq!(pagination.limit(), 25);
+        assert_eq!(pagination, Pagination::default());
+    }
+
+    #[test]
+    fn it_clamps_per_page_to_max() {
+        let pagination = Pagination::<20, 50>::from_query_str("page=2&per_page=500").unwrap();
+
+        assert_eq!(pagination.per_page(), 50);
+        assert_eq!(pagination.offset(), 50);
+    }
+
+    #[test]
+    fn it_reads_limit_and_offset() {
+        let pagination = Pagination::<20, 100>::from_query_str("limit=15&offset=45").unwrap();
+
+        assert_eq!(pagination.offset(), 45);
+        assert_eq!(pagination.limit(), 15);
+        assert_eq!(pagination.page(), 4);
+    }
+
+    #[test]
+    fn it_rejects_negative_page() {
+        let err = Pagination::<20, 100>::from_query_str("page=-1").unwrap_err();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[test]
+    fn it_rejects_zero_page() {
+        let err = Pagination::<20, 100>::from_query_str("page=0").unwrap_err();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[test]
+    fn it_rejects_non_numeric_per_page() {
+        let err = Pagination::<20, 100>::from_query_str("per_page=ten").unwrap_err();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+
+    #[test]
+    fn it_rejects_page_with_offset() {
+        let err = Pagination::<20, 100>::from_query_str("page=2&offset=10").unwrap_err();
+
+        assert_eq!(err.into_parts().0, 400);
+    }
+}
@@ -1,6 +1,7 @@
 ﻿//! Extractors for route/path segments
 
 use futures_util::future::{ready, Ready};
+use percent_encoding::percent_decode_str;
 use hyper::http::Extensions;
 use serde::de::DeserializeOwned;
 
@@ -116,14 +117,18 @@ impl<T: DeserializeOwned + Send> FromRequestRef for Path<T> {
     }
 }
 
-/// Extracts path args directly into handler method args that implements `FromStr`
+/// Extracts percent-decoded path args directly into handler method args that implements `FromStr`
 impl<T: FromStr + Send> FromPayload for T {
     type Future = Ready<Result<Self, Error>>;
 
     #[inline]
     fn from_payload(payload: Payload) -> Self::Future {
         if let Payload::Path((arg, value)) = payload {
-            ready(value.parse::<T>().map_err(|_| PathError::type_mismatch(arg)))
+            let value = percent_decode_str(value)
+                .decode_utf8()
+                .map_err(|_| PathError::type_mismatch(arg))
+                .and_then(|value| value.parse::<T>().map_err(|_| PathError::type_mismatch(arg)));
+            ready(value)
         } else {
             unreachable!()
         }
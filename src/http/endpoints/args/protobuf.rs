@@ -0,0 +1,228 @@
+﻿//! Extractors for Protocol Buffers messages
+
+use futures_util::ready;
+use pin_project_lite::pin_project;
+use prost::Message;
+
+use http_body_util::{combinators::Collect, BodyExt};
+use hyper::{http::request::Parts, StatusCode};
+use crate::{error::Error, headers::CONTENT_TYPE, HttpBody};
+
+use std::{
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+use crate::http::{
+    endpoints::args::{
+        FromPayload,
+        Payload,
+        Source
+    }
+};
+
+/// Content type of Protocol Buffers messages
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Alternative content type of Protocol Buffers messages
+const PROTOBUF_ALT_CONTENT_TYPE: &str = "application/protobuf";
+
+/// Wraps a typed Protocol Buffers message
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, Protobuf, ok};
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct User {
+///     #[prost(string, tag = "1")]
+///     name: String,
+/// }
+///
+/// async fn handle(user: Protobuf<User>) -> HttpResult {
+///     ok!("Hello {}", user.name)
+/// }
+/// ```
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Protobuf<T>(pub T);
+
+impl<T> Protobuf<T> {
+    /// Unwraps the inner `T`
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Message> From<T> for Protobuf<T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Protobuf<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Protobuf<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+pin_project! {
+    /// A future that collects an incoming body stream into bytes and decodes it into a Protocol Buffers message.
+    pub struct ExtractProtobufPayloadFut<T> {
+        #[pin]
+        fut: Collect<HttpBody>,
+        is_supported: bool,
+        _marker: PhantomData<T>
+    }
+}
+
+impl<T: Message + Default> Future for ExtractProtobufPayloadFut<T> {
+    type Output = Result<Protobuf<T>, Error>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if !*this.is_supported {
+            return Poll::Ready(Err(ProtobufError::unsupported_content_type()));
+        }
+        let result = ready!(this.fut.poll(cx))
+            .map_err(ProtobufError::collect_error)?;
+        let message = T::decode(result.to_bytes())
+            .map(Protobuf::<T>)
+            .map_err(ProtobufError::from_decode_error);
+        Poll::Ready(message)
+    }
+}
+
+/// Extracts Protocol Buffers message from request body into `Protobuf<T>`
+/// where T is a [`prost::Message`]
+impl<T: Message + Default> FromPayload for Protobuf<T> {
+    type Future = ExtractProtobufPayloadFut<T>;
+
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Parts(parts, body) = payload {
+            ExtractProtobufPayloadFut {
+                fut: body.collect(),
+                is_supported: is_protobuf(parts),
+                _marker: PhantomData
+            }
+        } else {
+            unreachable!()
+        }
+    }
+
+    fn source() -> Source {
+        Source::Parts
+    }
+}
+
+#[inline]
+fn is_protobuf(parts: &Parts) -> bool {
+    parts.headers
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim)
+        .is_some_and(|essence|
+            essence.eq_ignore_ascii_case(PROTOBUF_CONTENT_TYPE) ||
+            essence.eq_ignore_ascii_case(PROTOBUF_ALT_CONTENT_TYPE))
+}
+
+struct ProtobufError;
+
+impl ProtobufError {
+    #[inline]
+    fn from_decode_error(err: prost::DecodeError) -> Error {
+        Error::client_error(format!("Protobuf parsing error: {}", err))
+    }
+
+    #[inline]
+    fn collect_error(err: Error) -> Error {
+        Error::from_parts(err.status, None, format!("Protobuf parsing error: {}", err))
+    }
+
+    #[inline]
+    fn unsupported_content_type() -> Error {
+        Error::from_parts(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            None,
+            "Protobuf parsing error: unsupported content type")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+    use hyper::Request;
+    use prost::Message;
+    use crate::HttpBody;
+    use crate::headers::CONTENT_TYPE;
+    use crate::http::IntoResponse;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::Protobuf;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct User {
+        #[prost(int32, tag = "1")]
+        age: i32,
+        #[prost(string, tag = "2")]
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn it_round_trips_message() {
+        let user = User { age: 33, name: "John".into() };
+        let mut response = Protobuf(user.clone()).into_response().unwrap();
+
+        assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), "application/x-protobuf");
+
+        let body = response.body_mut().collect().await.unwrap().to_bytes();
+        let req = Request::post("/")
+            .header(CONTENT_TYPE, "application/x-protobuf")
+            .body(HttpBody::boxed(HttpBody::full(body)))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let decoded = Protobuf::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap();
+
+        assert_eq!(decoded.into_inner(), user);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unsupported_content_type() {
+        let user = User { age: 33, name: "John".into() };
+        let req = Request::post("/")
+            .header(CONTENT_TYPE, "application/json")
+            .body(HttpBody::boxed(HttpBody::full(user.encode_to_vec())))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let err = Protobuf::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+
+        assert_eq!(err.into_parts().0, 415);
+    }
+
+    #[tokio::test]
+    async fn it_returns_error_for_malformed_message() {
+        let req = Request::post("/")
+            .header(CONTENT_TYPE, "application/protobuf")
+            .body(HttpBody::boxed(HttpBody::full("\u{ff}not a message")))
+            .unwrap();
+
+        let (parts, body) = req.into_parts();
+        let err = Protobuf::<User>::from_payload(Payload::Parts(&parts, body)).await.unwrap_err();
+
+        assert!(err.is_client_error());
+    }
+}
@@ -104,6 +104,72 @@ impl<T: DeserializeOwned + Send> FromPayload for Query<T> {
     }
 }
 
+/// Holds the raw, undecoded query string of the request [`Uri`]
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, RawQuery, ok};
+///
+/// async fn handle(query: RawQuery) -> HttpResult {
+///     ok!("Raw query: {}", query.as_str().unwrap_or_default())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RawQuery(pub Option<String>);
+
+impl RawQuery {
+    /// Unwraps the inner query string, `None` if the request has no query
+    pub fn into_inner(self) -> Option<String> {
+        self.0
+    }
+    
+    /// Returns the query string as a string slice, `None` if the request has no query
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    /// Reads the raw query string from the request [`Uri`]
+    #[inline]
+    pub(crate) fn from_uri(uri: &Uri) -> Self {
+        Self(uri.query().map(String::from))
+    }
+}
+
+impl Deref for RawQuery {
+    type Target = Option<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Extracts the raw `Uri` query string from request
+impl FromRequestRef for RawQuery {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Ok(Self::from_uri(req.uri()))
+    }
+}
+
+/// Extracts the raw `Uri` query string from request parts
+impl FromPayload for RawQuery {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Query(uri) = payload {
+            ready(Ok(Self::from_uri(uri)))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Query
+    }
+}
+
 /// Describes errors of query extractor
 struct QueryError;
 impl QueryError {
@@ -117,7 +183,7 @@ mod tests {
     use std::collections::HashMap;
     use hyper::Uri;
     use serde::Deserialize;
-    use crate::Query;
+    use crate::{Query, RawQuery};
     use crate::http::endpoints::args::{FromPayload, Payload};
 
     #[derive(Deserialize)]
@@ -241,4 +307,31 @@ mod tests {
         assert_eq!(query.0.get("name").unwrap(), "John");
         assert_eq!(query.0.get("age").unwrap(), "33");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn it_reads_raw_query_from_payload() {
+        let uri = "https://www.example.com/api/get?a=1&b=2".parse::<Uri>().unwrap();
+
+        let query = RawQuery::from_payload(Payload::Query(&uri)).await.unwrap();
+
+        assert_eq!(query.as_str(), Some("a=1&b=2"));
+    }
+
+    #[test]
+    fn it_keeps_raw_query_undecoded() {
+        let uri = "https://www.example.com/api/get?name=John%20Doe&tag=a+b".parse::<Uri>().unwrap();
+
+        let query = RawQuery::from_uri(&uri);
+
+        assert_eq!(query.into_inner().unwrap(), "name=John%20Doe&tag=a+b");
+    }
+
+    #[test]
+    fn it_reads_none_raw_query_from_uri_without_query() {
+        let uri = "https://www.example.com/api/get".parse::<Uri>().unwrap();
+
+        let query = RawQuery::from_uri(&uri);
+
+        assert!(query.is_none());
+    }
+}
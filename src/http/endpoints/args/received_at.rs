@@ -0,0 +1,125 @@
+﻿//! Extractors for the request receipt timestamp
+
+use futures_util::future::{ready, Ready};
+use hyper::http::Extensions;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Holds the moment the request was received by the server, 
+/// which is recorded once the request head is parsed and before it's routed
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, ReceivedAt, ok};
+///
+/// async fn handle(received_at: ReceivedAt) -> HttpResult {
+///     ok!("Queued for: {:?}", received_at.elapsed())
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReceivedAt {
+    instant: Instant,
+    time: SystemTime
+}
+
+impl ReceivedAt {
+    /// Records the current moment
+    #[inline]
+    pub(crate) fn now() -> Self {
+        Self { instant: Instant::now(), time: SystemTime::now() }
+    }
+
+    /// Returns the monotonic moment the request was received, 
+    /// suitable for latency measurement
+    #[inline]
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Returns the wall-clock time the request was received, 
+    /// suitable for auditing
+    #[inline]
+    pub fn system_time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Returns the time elapsed since the request was received
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        self.instant.elapsed()
+    }
+
+    #[inline]
+    pub(crate) fn from_extensions(extensions: &Extensions) -> Result<Self, Error> {
+        extensions
+            .get::<ReceivedAt>()
+            .copied()
+            .ok_or_else(ReceivedAtError::not_available)
+    }
+}
+
+/// Extracts `ReceivedAt` from request
+impl FromRequestRef for ReceivedAt {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Self::from_extensions(req.extensions())
+    }
+}
+
+/// Extracts `ReceivedAt` from request parts
+impl FromPayload for ReceivedAt {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Ext(extensions) = payload {
+            ready(Self::from_extensions(extensions))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Ext
+    }
+}
+
+/// Describes errors of receipt timestamp extractor
+struct ReceivedAtError;
+
+impl ReceivedAtError {
+    #[inline]
+    fn not_available() -> Error {
+        Error::server_error("Received at error: request receipt timestamp is not available")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::http::Extensions;
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::ReceivedAt;
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let received_at = ReceivedAt::now();
+        let mut extensions = Extensions::new();
+        extensions.insert(received_at);
+
+        let extracted = ReceivedAt::from_payload(Payload::Ext(&extensions)).await.unwrap();
+
+        assert_eq!(extracted, received_at);
+    }
+
+    #[tokio::test]
+    async fn it_returns_error_if_not_available() {
+        let extensions = Extensions::new();
+
+        let err = ReceivedAt::from_payload(Payload::Ext(&extensions)).await.unwrap_err();
+
+        assert!(err.is_server_error());
+    }
+}
@@ -0,0 +1,193 @@
+﻿//! Extractors for the request subdomain
+
+use futures_util::future::{ready, Ready};
+use hyper::{header::HOST, http::{uri::Authority, Extensions}, HeaderMap, Uri};
+use std::{ops::Deref, sync::Arc};
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+/// Holds the base domain that subdomains are parsed against
+#[derive(Debug, Clone)]
+pub(crate) struct BaseDomain(Arc<str>);
+
+impl BaseDomain {
+    /// Creates a new base domain, a trailing dot and letter case are ignored
+    pub(crate) fn new(domain: &str) -> Self {
+        Self(normalize(domain).into())
+    }
+
+    /// Parses the subdomain of the request host described by its [`Uri`] and headers
+    #[inline]
+    pub(crate) fn subdomain(&self, uri: &Uri, headers: &HeaderMap) -> Subdomain {
+        let host = headers
+            .get(HOST)
+            .and_then(|host| host.to_str().ok())
+            .or_else(|| uri.authority().map(|authority| authority.as_str()));
+        Subdomain(host.and_then(|host| self.subdomain_of(host)))
+    }
+
+    #[inline]
+    fn subdomain_of(&self, host: &str) -> Option<String> {
+        let authority = host.parse::<Authority>().ok()?;
+        let host = normalize(authority.host());
+        let subdomain = host.strip_suffix(self.0.as_ref())?.strip_suffix('.')?;
+        (!subdomain.is_empty()).then(|| subdomain.to_owned())
+    }
+}
+
+#[inline]
+fn normalize(domain: &str) -> String {
+    domain.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Holds the subdomain of the request host relative to the base domain
+/// configured with [`App::with_base_domain`](crate::App::with_base_domain)
+///
+/// It's `None` if the request was sent to the base domain itself or to a host outside of it.
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, Subdomain, ok};
+///
+/// async fn handle(tenant: Subdomain) -> HttpResult {
+///     match tenant.as_str() {
+///         Some(tenant) => ok!("Hello, {tenant}!"),
+///         None => ok!("Hello from the base domain!")
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Subdomain(pub Option<String>);
+
+impl Subdomain {
+    /// Unwraps the inner subdomain, `None` if there is no subdomain
+    pub fn into_inner(self) -> Option<String> {
+        self.0
+    }
+
+    /// Returns the subdomain as a string slice, `None` if there is no subdomain
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn from_extensions(extensions: &Extensions) -> Result<Self, Error> {
+        extensions
+            .get::<Subdomain>()
+            .cloned()
+            .ok_or_else(SubdomainError::base_domain_not_configured)
+    }
+}
+
+impl Deref for Subdomain {
+    type Target = Option<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Extracts `Subdomain` from request
+impl FromRequestRef for Subdomain {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Self::from_extensions(req.extensions())
+    }
+}
+
+/// Extracts `Subdomain` from request parts
+impl FromPayload for Subdomain {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Ext(extensions) = payload {
+            ready(Self::from_extensions(extensions))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Ext
+    }
+}
+
+/// Describes errors of subdomain extractor
+struct SubdomainError;
+
+impl SubdomainError {
+    #[inline]
+    fn base_domain_not_configured() -> Error {
+        Error::server_error("Subdomain error: base domain is not configured, use App::with_base_domain()")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::HOST, http::Extensions, HeaderMap, Uri};
+    use crate::http::endpoints::args::{FromPayload, Payload};
+    use super::{BaseDomain, Subdomain};
+
+    fn subdomain(base: &str, host: &str) -> Subdomain {
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, host.parse().unwrap());
+        BaseDomain::new(base).subdomain(&Uri::from_static("/"), &headers)
+    }
+
+    #[test]
+    fn it_parses_subdomain() {
+        assert_eq!(subdomain("example.com", "t1.example.com").as_str(), Some("t1"));
+    }
+
+    #[test]
+    fn it_parses_base_domain_as_none() {
+        assert_eq!(subdomain("example.com", "example.com").as_str(), None);
+    }
+
+    #[test]
+    fn it_ignores_port_and_case() {
+        assert_eq!(subdomain("Example.com.", "T1.EXAMPLE.com:8080").as_str(), Some("t1"));
+    }
+
+    #[test]
+    fn it_parses_nested_subdomain() {
+        assert_eq!(subdomain("example.com", "eu.t1.example.com").as_str(), Some("eu.t1"));
+    }
+
+    #[test]
+    fn it_parses_foreign_host_as_none() {
+        assert_eq!(subdomain("example.com", "t1.badexample.com").as_str(), None);
+        assert_eq!(subdomain("example.com", "t1.example.org").as_str(), None);
+    }
+
+    #[test]
+    fn it_reads_authority_without_host_header() {
+        let uri = Uri::from_static("https://t2.example.com:443/api");
+
+        let subdomain = BaseDomain::new("example.com").subdomain(&uri, &HeaderMap::new());
+
+        assert_eq!(subdomain.as_str(), Some("t2"));
+    }
+
+    #[tokio::test]
+    async fn it_reads_from_payload() {
+        let mut extensions = Extensions::new();
+        extensions.insert(Subdomain(Some("t1".into())));
+
+        let subdomain = Subdomain::from_payload(Payload::Ext(&extensions)).await.unwrap();
+
+        assert_eq!(subdomain.into_inner(), Some("t1".into()));
+    }
+
+    #[tokio::test]
+    async fn it_returns_error_if_base_domain_is_not_configured() {
+        let extensions = Extensions::new();
+
+        let err = Subdomain::from_payload(Payload::Ext(&extensions)).await.unwrap_err();
+
+        assert!(err.is_server_error());
+    }
+}
@@ -0,0 +1,319 @@
+﻿//! Extractors for URI components
+
+use futures_util::future::{ready, Ready};
+use hyper::{
+    header::{HeaderName, FORWARDED, HOST},
+    http::{request::Parts, uri::{Authority, PathAndQuery, Scheme}, Extensions},
+    HeaderMap,
+    Uri
+};
+
+use crate::{error::Error, HttpRequest};
+use crate::http::endpoints::args::{FromPayload, FromRequestRef, Payload, Source};
+
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+
+/// Settings that are used to resolve the scheme and authority of a request
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct UriSettings {
+    /// Specifies whether the connection is secured with TLS
+    pub(crate) tls: bool,
+    /// Specifies whether the `Forwarded` and `X-Forwarded-*` headers are trusted
+    pub(crate) trusted_proxy: bool
+}
+
+/// Holds the components of the request URI
+///
+/// Unlike [`Uri`], the scheme and authority are always resolved, 
+/// from the connection and the `Host` header if they are not present in the request target.
+/// If the trusted proxy mode is enabled with [`App::with_trusted_proxy`](crate::App::with_trusted_proxy),
+/// the `Forwarded`, `X-Forwarded-Proto` and `X-Forwarded-Host` headers take precedence.
+///
+/// # Example
+/// ```no_run
+/// use volga::{HttpResult, UriParts, ok};
+///
+/// async fn handle(uri: UriParts) -> HttpResult {
+///     ok!("{}://{}{}", uri.scheme(), uri.host().unwrap_or("localhost"), uri.path())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriParts {
+    scheme: Scheme,
+    authority: Option<Authority>,
+    path_and_query: PathAndQuery
+}
+
+impl UriParts {
+    /// Returns the scheme, e.g. `https`
+    #[inline]
+    pub fn scheme(&self) -> &str {
+        self.scheme.as_str()
+    }
+
+    /// Returns the authority, e.g. `example.com:8080`
+    #[inline]
+    pub fn authority(&self) -> Option<&str> {
+        self.authority.as_ref().map(Authority::as_str)
+    }
+
+    /// Returns the host, e.g. `example.com`
+    #[inline]
+    pub fn host(&self) -> Option<&str> {
+        self.authority.as_ref().map(Authority::host)
+    }
+
+    /// Returns the port if it is specified explicitly, e.g. `8080`
+    #[inline]
+    pub fn port(&self) -> Option<u16> {
+        self.authority.as_ref().and_then(Authority::port_u16)
+    }
+
+    /// Returns the path, e.g. `/users/1`
+    #[inline]
+    pub fn path(&self) -> &str {
+        self.path_and_query.path()
+    }
+
+    /// Returns the query string without the leading `?` if any, e.g. `page=1&per_page=20`
+    #[inline]
+    pub fn query(&self) -> Option<&str> {
+        self.path_and_query.query()
+    }
+
+    /// Builds an absolute URL for the `path` using the scheme and authority of the request,
+    /// e.g. `https://example.com/users/1` for `/users/1`
+    ///
+    /// If the authority is unknown, the `path` is returned as a relative reference.
+    pub fn absolute_url(&self, path: &str) -> String {
+        let separator = if path.starts_with('/') { "" } else { "/" };
+        match self.authority() {
+            Some(authority) => format!("{}://{authority}{separator}{path}", self.scheme()),
+            None => format!("{separator}{path}")
+        }
+    }
+
+    pub(crate) fn from_parts(uri: &Uri, headers: &HeaderMap, extensions: &Extensions) -> Self {
+        let settings = extensions
+            .get::<UriSettings>()
+            .copied()
+            .unwrap_or_default();
+        Self::with_settings(uri, headers, settings)
+    }
+
+    pub(crate) fn with_settings(uri: &Uri, headers: &HeaderMap, settings: UriSettings) -> Self {
+        let (forwarded_proto, forwarded_host) = if settings.trusted_proxy {
+            forwarded(headers)
+        } else {
+            (None, None)
+        };
+
+        let scheme = forwarded_proto
+            .or_else(|| uri.scheme().cloned())
+            .unwrap_or(if settings.tls { Scheme::HTTPS } else { Scheme::HTTP });
+        let authority = forwarded_host
+            .or_else(|| uri.authority().cloned())
+            .or_else(|| header(headers, HOST).and_then(|host| host.parse().ok()));
+        let path_and_query = uri
+            .path_and_query()
+            .cloned()
+            .unwrap_or_else(|| PathAndQuery::from_static("/"));
+
+        Self { scheme, authority, path_and_query }
+    }
+}
+
+/// Reads the scheme and authority of the original request from the `Forwarded` header, 
+/// or from the `X-Forwarded-Proto` and `X-Forwarded-Host` ones if it is missing.
+/// 
+/// Only the last value is taken into account, since proxies append to these headers,
+/// so it is the one added by the trusted proxy, while the preceding ones could be sent by the client.
+fn forwarded(headers: &HeaderMap) -> (Option<Scheme>, Option<Authority>) {
+    let mut proto = None;
+    let mut host = None;
+    if let Some(last) = last(headers, FORWARDED) {
+        for pair in last.split(';') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            match key.trim() {
+                key if key.eq_ignore_ascii_case("proto") => proto = value.parse().ok(),
+                key if key.eq_ignore_ascii_case("host") => host = value.parse().ok(),
+                _ => ()
+            }
+        }
+    }
+
+    let proto = proto.or_else(|| last(headers, X_FORWARDED_PROTO).and_then(|value| value.parse().ok()));
+    let host = host.or_else(|| last(headers, X_FORWARDED_HOST).and_then(|value| value.parse().ok()));
+    (proto, host)
+}
+
+/// Returns the last comma-separated value of the header, 
+/// taking into account that it can be split into several header lines
+#[inline]
+fn last(headers: &HeaderMap, name: HeaderName) -> Option<&str> {
+    headers
+        .get_all(name)
+        .iter()
+        .next_back()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .map(str::trim)
+}
+
+#[inline]
+fn header(headers: &HeaderMap, name: HeaderName) -> Option<&str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+impl FromRequestRef for UriParts {
+    #[inline]
+    fn from_request(req: &HttpRequest) -> Result<Self, Error> {
+        Ok(Self::from_parts(req.uri(), req.headers(), req.extensions()))
+    }
+}
+
+/// Extracts `UriParts` from request parts
+impl FromPayload for UriParts {
+    type Future = Ready<Result<Self, Error>>;
+
+    #[inline]
+    fn from_payload(payload: Payload) -> Self::Future {
+        if let Payload::Head(Parts { uri, headers, extensions, .. }) = payload {
+            ready(Ok(Self::from_parts(uri, headers, extensions)))
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[inline]
+    fn source() -> Source {
+        Source::Head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{http::Extensions, HeaderMap, Uri};
+    use super::{UriParts, UriSettings};
+
+    fn extensions(tls: bool, trusted_proxy: bool) -> Extensions {
+        let mut extensions = Extensions::new();
+        extensions.insert(UriSettings { tls, trusted_proxy });
+        extensions
+    }
+
+    fn forwarded_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("host", "10.0.0.1:7878".parse().unwrap());
+        headers.insert("x-forwarded-proto", "https".parse().unwrap());
+        headers.insert("x-forwarded-host", "spoofed.example.com, example.com".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn it_reads_parts_from_host_header() {
+        let uri: Uri = "/users/1?page=2".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("host", "example.com:8080".parse().unwrap());
+
+        let parts = UriParts::from_parts(&uri, &headers, &extensions(false, false));
+
+        assert_eq!(parts.scheme(), "http");
+        assert_eq!(parts.authority(), Some("example.com:8080"));
+        assert_eq!(parts.host(), Some("example.com"));
+        assert_eq!(parts.port(), Some(8080));
+        assert_eq!(parts.path(), "/users/1");
+        assert_eq!(parts.query(), Some("page=2"));
+    }
+
+    #[test]
+    fn it_reads_parts_from_absolute_uri() {
+        let uri: Uri = "http://example.com/users".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &HeaderMap::new(), &extensions(true, false));
+
+        assert_eq!(parts.scheme(), "http");
+        assert_eq!(parts.host(), Some("example.com"));
+        assert_eq!(parts.port(), None);
+        assert_eq!(parts.query(), None);
+    }
+
+    #[test]
+    fn it_uses_https_for_tls_connection() {
+        let uri: Uri = "/".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &HeaderMap::new(), &extensions(true, false));
+
+        assert_eq!(parts.scheme(), "https");
+        assert_eq!(parts.host(), None);
+    }
+
+    #[test]
+    fn it_ignores_forwarded_headers_if_proxy_is_not_trusted() {
+        let uri: Uri = "/".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &forwarded_headers(), &extensions(false, false));
+
+        assert_eq!(parts.scheme(), "http");
+        assert_eq!(parts.authority(), Some("10.0.0.1:7878"));
+    }
+
+    #[test]
+    fn it_reads_x_forwarded_headers_if_proxy_is_trusted() {
+        let uri: Uri = "/".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &forwarded_headers(), &extensions(false, true));
+
+        assert_eq!(parts.scheme(), "https");
+        assert_eq!(parts.authority(), Some("example.com"));
+    }
+
+    #[test]
+    fn it_prefers_forwarded_header_if_proxy_is_trusted() {
+        let uri: Uri = "/".parse().unwrap();
+        let mut headers = forwarded_headers();
+        headers.insert("forwarded", "for=10.0.0.2;proto=https;host=spoofed.example.com, for=192.0.2.60;proto=http;host=\"api.example.com:8443\"".parse().unwrap());
+
+        let parts = UriParts::from_parts(&uri, &headers, &extensions(false, true));
+
+        assert_eq!(parts.scheme(), "http");
+        assert_eq!(parts.host(), Some("api.example.com"));
+        assert_eq!(parts.port(), Some(8443));
+    }
+
+    #[test]
+    fn it_reads_last_forwarded_header_line_if_proxy_is_trusted() {
+        let uri: Uri = "/".parse().unwrap();
+        let mut headers = forwarded_headers();
+        headers.append("x-forwarded-host", "api.example.com".parse().unwrap());
+
+        let parts = UriParts::from_parts(&uri, &headers, &extensions(false, true));
+
+        assert_eq!(parts.authority(), Some("api.example.com"));
+    }
+
+    #[test]
+    fn it_builds_absolute_url() {
+        let uri: Uri = "/".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &forwarded_headers(), &extensions(false, true));
+
+        assert_eq!(parts.absolute_url("/users/1"), "https://example.com/users/1");
+        assert_eq!(parts.absolute_url("users/1"), "https://example.com/users/1");
+    }
+
+    #[test]
+    fn it_builds_relative_url_without_authority() {
+        let uri: Uri = "/".parse().unwrap();
+
+        let parts = UriParts::from_parts(&uri, &HeaderMap::new(), &extensions(false, false));
+
+        assert_eq!(parts.absolute_url("users/1"), "/users/1");
+    }
+}
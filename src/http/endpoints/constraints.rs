@@ -0,0 +1,100 @@
+﻿//! Route parameter constraints, e.g. `/users/{id:integer}`
+
+const CONSTRAINT_SEPARATOR: char = ':';
+
+/// Describes a built-in constraint of a route parameter
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum RouteConstraint {
+    /// An optionally signed sequence of digits, e.g. `-42`
+    Integer,
+    /// Any finite floating-point number, e.g. `3.14`
+    Number,
+    /// A UUID, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`
+    Uuid,
+    /// ASCII letters only
+    Alpha,
+    /// ASCII letters and digits only
+    Alphanumeric
+}
+
+impl RouteConstraint {
+    /// Splits the parameter definition (without brackets) into its name and constraint
+    /// 
+    /// # Panics
+    /// If the constraint is unknown
+    pub(crate) fn parse(param: &str) -> (&str, Option<Self>) {
+        let Some((name, constraint)) = param.split_once(CONSTRAINT_SEPARATOR) else {
+            return (param, None);
+        };
+        let constraint = match constraint {
+            "integer" | "int" => Self::Integer,
+            "number" => Self::Number,
+            "uuid" => Self::Uuid,
+            "alpha" => Self::Alpha,
+            "alphanumeric" => Self::Alphanumeric,
+            _ => panic!("Unknown route constraint: {constraint} in {{{param}}}")
+        };
+        (name, Some(constraint))
+    }
+
+    /// Checks whether the path `segment` satisfies the constraint
+    pub(crate) fn is_match(&self, segment: &str) -> bool {
+        match self {
+            Self::Integer => {
+                let digits = segment.strip_prefix('-').unwrap_or(segment);
+                !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+            },
+            Self::Number => segment.parse::<f64>().is_ok_and(f64::is_finite),
+            Self::Uuid => is_uuid(segment),
+            Self::Alpha => !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_alphabetic()),
+            Self::Alphanumeric => !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_alphanumeric()),
+        }
+    }
+}
+
+/// Checks whether the `segment` is a hyphenated UUID, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`
+#[inline]
+fn is_uuid(segment: &str) -> bool {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+    let mut groups = segment.split('-');
+    GROUPS.iter().all(|&len| groups
+        .next()
+        .is_some_and(|group| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()))) &&
+    groups.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RouteConstraint;
+
+    #[test]
+    fn it_parses_constraints() {
+        assert_eq!(RouteConstraint::parse("id"), ("id", None));
+        assert_eq!(RouteConstraint::parse("id:integer"), ("id", Some(RouteConstraint::Integer)));
+        assert_eq!(RouteConstraint::parse("id:uuid"), ("id", Some(RouteConstraint::Uuid)));
+        assert_eq!(RouteConstraint::parse("slug:alpha"), ("slug", Some(RouteConstraint::Alpha)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_on_unknown_constraint() {
+        RouteConstraint::parse("id:guid");
+    }
+
+    #[test]
+    fn it_matches_segments() {
+        assert!(RouteConstraint::Integer.is_match("-42"));
+        assert!(!RouteConstraint::Integer.is_match("4.2"));
+        assert!(!RouteConstraint::Integer.is_match("-"));
+        assert!(RouteConstraint::Number.is_match("4.2"));
+        assert!(!RouteConstraint::Number.is_match("NaN"));
+        assert!(RouteConstraint::Uuid.is_match("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(!RouteConstraint::Uuid.is_match("67e55044"));
+        assert!(!RouteConstraint::Uuid.is_match("67e55044-10b1-426f-9247-bb680e5fe0c8-1"));
+        assert!(!RouteConstraint::Uuid.is_match("67e55044-10b1-426f-9247-bb680e5fe0cz"));
+        assert!(RouteConstraint::Alpha.is_match("abc"));
+        assert!(!RouteConstraint::Alpha.is_match("abc1"));
+        assert!(RouteConstraint::Alphanumeric.is_match("abc1"));
+        assert!(!RouteConstraint::Alphanumeric.is_match("abc-1"));
+    }
+}
@@ -1,17 +1,169 @@
-﻿use std::collections::HashMap;
-use hyper::Method;
-use crate::http::endpoints::handlers::RouteHandler;
+﻿use std::{collections::HashMap, sync::Arc};
+use hyper::{http::Extensions, Method};
+use crate::http::endpoints::{handlers::RouteHandler, constraints::RouteConstraint};
 
 const END_OF_ROUTE: &str = "";
 const OPEN_BRACKET: char = '{';
 const CLOSE_BRACKET: char = '}';
+const CATCH_ALL: char = '*';
+const PATH_SEPARATOR: &str = "/";
 
 pub(crate) type PathArguments = Vec<(String, String)>;
 
 pub(crate) enum Route {
-    Static(HashMap<String, Route>),
-    Dynamic(HashMap<String, Route>),
-    Handler(HashMap<Method, RouteHandler>)
+    Static(RouteMap),
+    Dynamic(RouteMap),
+    Handler(HashMap<Method, RouteEndpoint>)
+}
+
+/// Holds the child routes of a path segment
+#[derive(Default)]
+pub(crate) struct RouteMap {
+    /// Static child routes and the handler route, by their path segment
+    routes: HashMap<String, Route>,
+    /// Dynamic child routes in precedence order, 
+    /// so constrained parameters are tried before unconstrained ones
+    params: Vec<(RouteParam, Route)>,
+    /// Catch-all child routes
+    catch_alls: Vec<(RouteParam, Route)>
+}
+
+/// Describes a dynamic path segment that is parsed once the route is mapped
+struct RouteParam {
+    /// The segment as it's defined in the route pattern, e.g. `{id:integer}`
+    segment: String,
+    /// Name of the parameter, e.g. `id`
+    name: String,
+    /// Constraint of the parameter value if any
+    constraint: Option<RouteConstraint>
+}
+
+/// Describes a request handler mapped to a route along with the route's metadata
+pub(crate) struct RouteEndpoint {
+    pub(crate) handler: RouteHandler,
+    pub(crate) metadata: RouteMetadata
+}
+
+/// Holds the route pattern and arbitrary typed metadata attached to a route
+#[derive(Clone, Default)]
+pub(crate) struct RouteMetadata {
+    inner: Arc<Extensions>,
+    pattern: Option<Arc<str>>
+}
+
+impl RouteEndpoint {
+    #[inline]
+    fn new(handler: RouteHandler, pattern: Arc<str>) -> Self {
+        let metadata = RouteMetadata { pattern: Some(pattern), ..Default::default() };
+        Self { handler, metadata }
+    }
+}
+
+impl RouteMetadata {
+    /// Returns the pattern of the route, e.g. `/users/{id}`,
+    /// or `None` for fallback handlers that are not mapped to a route
+    #[inline]
+    pub(crate) fn pattern(&self) -> Option<&Arc<str>> {
+        self.pattern.as_ref()
+    }
+
+    /// Inserts a value of type `T` into the route metadata, replacing the existing one if any
+    #[inline]
+    pub(crate) fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) {
+        Arc::make_mut(&mut self.inner).insert(value);
+    }
+
+    /// Returns a reference to a value of type `T` if it has been attached to the route
+    #[inline]
+    pub(crate) fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.inner.get::<T>()
+    }
+}
+
+impl RouteParam {
+    /// Parses the dynamic `segment`
+    /// 
+    /// # Panics
+    /// If the constraint of the parameter is unknown
+    fn parse(segment: &str) -> Self {
+        let param = Route::param(segment);
+        let (name, constraint) = match param.strip_prefix(CATCH_ALL) {
+            Some(name) => (name, None),
+            None => RouteConstraint::parse(param)
+        };
+        Self { segment: segment.into(), name: name.into(), constraint }
+    }
+
+    /// Returns the sort key of the parameter, constrained parameters go first
+    #[inline]
+    fn precedence(&self) -> (bool, Option<RouteConstraint>, &str) {
+        (self.constraint.is_none(), self.constraint, &self.name)
+    }
+}
+
+impl RouteMap {
+    /// Returns the handler route of the path segment if any
+    #[inline]
+    fn handler(&self) -> Option<&Route> {
+        self.routes
+            .get(END_OF_ROUTE)
+            .filter(|route| matches!(route, Route::Handler(_)))
+    }
+
+    /// Returns the endpoints of the path segment, inserting an empty handler route if there is none
+    fn endpoints_mut(&mut self) -> &mut HashMap<Method, RouteEndpoint> {
+        match self.routes.entry(END_OF_ROUTE.into()).or_insert_with(|| Route::Handler(HashMap::new())) {
+            Route::Handler(methods) => methods,
+            _ => unreachable!()
+        }
+    }
+
+    /// Gets a mutable reference to the child route defined exactly by the `segment`
+    fn get_mut(&mut self, segment: &str) -> Option<&mut Route> {
+        if !Route::is_dynamic_segment(segment) {
+            return self.routes.get_mut(segment);
+        }
+        self.params
+            .iter_mut()
+            .chain(self.catch_alls.iter_mut())
+            .find(|(param, _)| param.segment == segment)
+            .map(|(_, route)| route)
+    }
+
+    /// Gets a mutable reference to the child route defined by the `segment`, inserting it if there is none
+    fn get_or_insert(&mut self, segment: &str) -> &mut Route {
+        if !Route::is_dynamic_segment(segment) {
+            return self.routes
+                .entry(segment.into())
+                .or_insert_with(|| Route::Static(RouteMap::default()));
+        }
+        let params = if Route::is_catch_all_segment(segment) {
+            &mut self.catch_alls
+        } else {
+            &mut self.params
+        };
+        let index = match params.iter().position(|(param, _)| param.segment == segment) {
+            Some(index) => index,
+            None => {
+                let param = RouteParam::parse(segment);
+                let index = params.partition_point(|(other, _)| other.precedence() <= param.precedence());
+                params.insert(index, (param, Route::Dynamic(RouteMap::default())));
+                index
+            }
+        };
+        &mut params[index].1
+    }
+
+    /// Iterates over all child routes along with their path segments
+    fn iter(&self) -> impl Iterator<Item = (&str, &Route)> {
+        self.routes
+            .iter()
+            .map(|(segment, route)| (segment.as_str(), route))
+            .chain(self.params
+                .iter()
+                .chain(self.catch_alls.iter())
+                .map(|(param, route)| (param.segment.as_str(), route)))
+    }
 }
 
 pub(crate) struct RouteParams<'route> {
@@ -26,99 +178,141 @@ impl Route {
         method: Method, 
         handler: RouteHandler
     ) {
+        let pattern: Arc<str> = format!("{PATH_SEPARATOR}{}", path_segments.join(PATH_SEPARATOR)).into();
         let mut current = self;
         for (index, segment) in path_segments.iter().enumerate() {
-            let is_last = index == path_segments.len() - 1;
-            let is_dynamic = Self::is_dynamic_segment(segment);
-
+            if Self::is_catch_all_segment(segment) {
+                assert!(index == path_segments.len() - 1, "Catch-all parameter must be the last segment of the route: {segment}");
+            }
             current = match current {
-                Route::Static(map) | Route::Dynamic(map) => {
-                    let entry = map.entry(segment.clone()).or_insert_with(|| {
-                        if is_dynamic {
-                            Route::Dynamic(HashMap::new())
-                        } else {
-                            Route::Static(HashMap::new())
-                        }
-                    });
-
-                    // Check if this segment is the last, and add the handler
-                    if is_last {
-                        // Assumes the inserted or existing route has HashMap as associated data
-                        match entry {
-                            Route::Dynamic(ref mut map) |
-                            Route::Static(ref mut map) => {
-                                if let Some(endpoint) = map.get_mut(END_OF_ROUTE) { 
-                                    match endpoint { 
-                                        Route::Handler(ref mut methods) => 
-                                            methods.insert(method.clone(), handler.clone()),
-                                        _ => unreachable!()
-                                    };
-                                } else { 
-                                    map.insert(
-                                        END_OF_ROUTE.into(), 
-                                        Route::Handler(HashMap::from([
-                                            (method.clone(), handler.clone())
-                                        ]))
-                                    );
-                                }
-                            },
-                            _ => ()
-                        }
-                    }
-
-                    entry // Continue traversing or inserting into this entry
-                },
+                Route::Static(map) | Route::Dynamic(map) => map.get_or_insert(segment),
                 Route::Handler(_) => panic!("Attempt to insert a route under a handler"),
             };
         }
+        match current {
+            Route::Static(map) | Route::Dynamic(map) => {
+                map.endpoints_mut().insert(method, RouteEndpoint::new(handler, pattern));
+            },
+            Route::Handler(_) => panic!("Attempt to insert a route under a handler"),
+        }
     }
 
-    pub(crate) fn find(&self, path_segments: &[String]) -> Option<RouteParams> {
-        let mut current = Some(self);
+    pub(crate) fn find(&self, path_segments: &[String]) -> Option<RouteParams<'_>> {
         let mut params = Vec::new();
-        for (index, segment) in path_segments.iter().enumerate() {
-            let is_last = index == path_segments.len() - 1;
+        self.find_handler(path_segments, &mut params, false)
+            .map(|route| RouteParams { route, params })
+    }
 
+    /// Finds the route as if consecutive slashes were merged, i.e. skipping empty path segments,
+    /// while catch-all parameters still bind the rest of the path with them
+    pub(crate) fn find_skipping_empty(&self, path_segments: &[String]) -> Option<RouteParams<'_>> {
+        let mut params = Vec::new();
+        self.find_handler(path_segments, &mut params, true)
+            .map(|route| RouteParams { route, params })
+    }
+
+    /// Recursively finds the handler route that matches the `path_segments`
+    /// 
+    /// Tries a static segment first, then dynamic ones in precedence order 
+    /// (constrained parameters take precedence over unconstrained ones) and then the catch-all route, 
+    /// falling back to the next candidate if the rest of the path doesn't match.
+    fn find_handler<'route>(
+        &'route self, 
+        path_segments: &[String], 
+        params: &mut PathArguments, 
+        skip_empty: bool
+    ) -> Option<&'route Route> {
+        let map = match self {
+            Route::Static(map) | Route::Dynamic(map) => map,
+            Route::Handler(_) => return path_segments.is_empty().then_some(self)
+        };
+        
+        let Some((segment, rest)) = path_segments.split_first() else {
+            return map.handler();
+        };
+        
+        if skip_empty && segment.is_empty() && !rest.is_empty() {
+            return self.find_handler(rest, params, skip_empty);
+        }
+
+        // Trying direct match first
+        if let Some(route) = map.routes.get(segment).and_then(|route| route.find_handler(rest, params, skip_empty)) {
+            return Some(route);
+        }
+
+        // If no direct match, try dynamic routes, they are already sorted in precedence order
+        for (param, route) in map.params.iter() {
+            if param.constraint.is_some_and(|constraint| !constraint.is_match(segment)) {
+                continue;
+            }
+            params.push((param.name.clone(), segment.clone()));
+            if let Some(route) = route.find_handler(rest, params, skip_empty) {
+                return Some(route);
+            }
+            params.pop();
+        }
+
+        // The catch-all route is used only if there is no more specific route
+        Self::find_catch_all(map, path_segments, params)
+    }
+
+    /// Finds a catch-all route in the `map` that binds the `rest` of the path segments
+    #[inline]
+    fn find_catch_all<'route>(
+        map: &'route RouteMap, 
+        rest: &[String], 
+        params: &mut PathArguments
+    ) -> Option<&'route Route> {
+        map.catch_alls
+            .iter()
+            .find_map(|(param, route)| match route {
+                Route::Static(map) | Route::Dynamic(map) => map
+                    .handler()
+                    .map(|route| (param, route)),
+                Route::Handler(_) => None
+            })
+            .map(|(param, route)| {
+                params.push((param.name.clone(), rest.join(PATH_SEPARATOR)));
+                route
+            })
+    }
+
+    /// Gets a mutable reference to the endpoint mapped exactly to the `path_segments` and `method`
+    pub(crate) fn get_mut(&mut self, path_segments: &[String], method: &Method) -> Option<&mut RouteEndpoint> {
+        let mut current = self;
+        for segment in path_segments {
             current = match current {
-                Some(Route::Static(map)) | Some(Route::Dynamic(map)) => {
-                    // Trying direct match first
-                    let direct_match = map.get(segment);
-
-                    // If no direct match, try dynamic route resolution
-                    let resolved_route = direct_match.or_else(|| {
-                        map.iter()
-                            .filter(|(key, _)| Self::is_dynamic_segment(key))
-                            .map(|(key, route)| {
-                                if let Some(param_name) = key.strip_prefix(OPEN_BRACKET).and_then(|k| k.strip_suffix(CLOSE_BRACKET)) {
-                                    params.push((param_name.to_string(), segment.clone()));
-                                }
-                                route
-                            })
-                            .next()
-                    });
-
-                    // Retrieve handler or further route if this is the last segment
-                    if let Some(route) = resolved_route {
-                        if is_last {
-                            match route {
-                                Route::Dynamic(inner_map) | Route::Static(inner_map) => {
-                                    // Attempt to get handler directly if no further routing is possible
-                                    inner_map.get(END_OF_ROUTE).or(Some(route))
-                                },
-                                handler @ Route::Handler(_) => Some(handler), // Direct handler return
-                            }
-                        } else {
-                            Some(route) // Continue on non-terminal routes
-                        }
-                    } else {
-                        None // No route resolved
-                    }
-                },
-                _ => None,
+                Route::Static(map) | Route::Dynamic(map) => map.get_mut(segment)?,
+                Route::Handler(_) => return None
             };
         }
+        match current {
+            Route::Static(map) | Route::Dynamic(map) => match map.routes.get_mut(END_OF_ROUTE)? {
+                Route::Handler(methods) => methods.get_mut(method),
+                _ => None
+            },
+            Route::Handler(_) => None
+        }
+    }
 
-        current.map(|route| RouteParams { route, params })
+    /// Collects all HTTP Verbs and path segments of the mapped endpoints
+    pub(crate) fn collect<'a>(&'a self, path_segments: &mut Vec<&'a str>, routes: &mut Vec<(Method, Vec<&'a str>)>) {
+        match self {
+            Route::Static(map) | Route::Dynamic(map) => {
+                for (segment, route) in map.iter() {
+                    if let Route::Handler(_) = route {
+                        route.collect(path_segments, routes);
+                    } else {
+                        path_segments.push(segment);
+                        route.collect(path_segments, routes);
+                        path_segments.pop();
+                    }
+                }
+            },
+            Route::Handler(methods) => routes.extend(methods
+                .keys()
+                .map(|method| (method.clone(), path_segments.clone()))),
+        }
     }
 
     #[inline]
@@ -126,16 +320,27 @@ impl Route {
         segment.starts_with(OPEN_BRACKET) && 
         segment.ends_with(CLOSE_BRACKET)
     }
+
+    #[inline]
+    fn is_catch_all_segment(segment: &str) -> bool {
+        Self::is_dynamic_segment(segment) && 
+        Self::param(segment).starts_with(CATCH_ALL)
+    }
+
+    /// Strips the brackets of the dynamic `segment`
+    #[inline]
+    fn param(segment: &str) -> &str {
+        &segment[OPEN_BRACKET.len_utf8()..segment.len() - CLOSE_BRACKET.len_utf8()]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
     use hyper::Method;
-    
+
     use crate::ok;
     use crate::http::endpoints::handlers::Func;
-    use crate::http::endpoints::route::Route;
+    use crate::http::endpoints::route::{Route, RouteMap};
 
     #[test]
     fn it_inserts_and_finds_route() {
@@ -144,7 +349,7 @@ mod tests {
         
         let path = ["test".into()];
         
-        let mut route = Route::Static(HashMap::new());
+        let mut route = Route::Static(RouteMap::default());
         route.insert(&path, Method::GET, handler);
         
         let route_params = route.find(&path);
@@ -159,7 +364,7 @@ mod tests {
 
         let path = ["test".into(), "{value}".into()];
 
-        let mut route = Route::Static(HashMap::new());
+        let mut route = Route::Static(RouteMap::default());
         route.insert(&path, Method::GET, handler);
 
         let path = ["test".into(), "some".into()];
@@ -170,4 +375,146 @@ mod tests {
         
         assert_eq!(val, "some");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn it_matches_route_constraints() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["test".into(), "{id:integer}".into()], Method::GET, handler.clone());
+        route.insert(&["test".into(), "{id:uuid}".into()], Method::PUT, handler);
+
+        let route_params = route.find(&["test".into(), "42".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("id".to_string(), "42".to_string())]);
+        assert!(matches!(route_params.route, Route::Handler(methods) if methods.contains_key(&Method::GET)));
+
+        let route_params = route.find(&["test".into(), "67e55044-10b1-426f-9247-bb680e5fe0c8".into()]).unwrap();
+        assert!(matches!(route_params.route, Route::Handler(methods) if methods.contains_key(&Method::PUT)));
+
+        assert!(route.find(&["test".into(), "abc".into()]).is_none());
+    }
+
+    #[test]
+    fn it_prefers_constrained_route_over_unconstrained() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["test".into(), "{name}".into()], Method::GET, handler.clone());
+        route.insert(&["test".into(), "{id:integer}".into()], Method::GET, handler);
+
+        let route_params = route.find(&["test".into(), "42".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("id".to_string(), "42".to_string())]);
+
+        let route_params = route.find(&["test".into(), "abc".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("name".to_string(), "abc".to_string())]);
+    }
+
+    #[test]
+    fn it_matches_catch_all_route() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["files".into(), "{*path}".into()], Method::GET, handler.clone());
+        route.insert(&["files".into(), "readme".into()], Method::GET, handler.clone());
+        route.insert(&["files".into(), "{name}".into(), "info".into()], Method::GET, handler);
+
+        let route_params = route.find(&["files".into(), "a".into(), "b".into(), "c".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("path".to_string(), "a/b/c".to_string())]);
+
+        let route_params = route.find(&["files".into(), "readme".into()]).unwrap();
+        assert!(route_params.params.is_empty());
+
+        let route_params = route.find(&["files".into(), "a".into(), "info".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("name".to_string(), "a".to_string())]);
+
+        let route_params = route.find(&["files".into(), "a".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("path".to_string(), "a".to_string())]);
+
+        let route_params = route.find(&["files".into()]);
+        assert!(!matches!(route_params.map(|params| params.route), Some(Route::Handler(_))));
+    }
+
+    #[test]
+    fn it_falls_back_to_next_dynamic_route_if_rest_of_path_does_not_match() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["users".into(), "{id:integer}".into(), "posts".into()], Method::GET, handler.clone());
+        route.insert(&["users".into(), "{name}".into(), "profile".into()], Method::GET, handler.clone());
+        route.insert(&["users".into(), "admin".into(), "settings".into()], Method::GET, handler);
+
+        let route_params = route.find(&["users".into(), "42".into(), "posts".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("id".to_string(), "42".to_string())]);
+
+        let route_params = route.find(&["users".into(), "42".into(), "profile".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("name".to_string(), "42".to_string())]);
+
+        let route_params = route.find(&["users".into(), "admin".into(), "profile".into()]).unwrap();
+        assert_eq!(route_params.params, vec![("name".to_string(), "admin".to_string())]);
+
+        assert!(route.find(&["users".into(), "42".into(), "settings".into()]).is_none());
+    }
+
+    #[test]
+    fn it_skips_empty_segments_but_keeps_them_in_catch_all() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["a".into(), "b".into()], Method::GET, handler.clone());
+        route.insert(&["files".into(), "{*path}".into()], Method::GET, handler);
+
+        let path = ["a".into(), "".into(), "b".into()];
+        assert!(route.find(&path).is_none());
+        assert!(route.find_skipping_empty(&path).is_some());
+
+        let route_params = route
+            .find_skipping_empty(&["files".into(), "".into(), "x".into(), "".into(), "y".into()])
+            .unwrap();
+        assert_eq!(route_params.params, vec![("path".to_string(), "x//y".to_string())]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_catch_all_is_not_last_segment() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["files".into(), "{*path}".into(), "info".into()], Method::GET, handler);
+    }
+
+    #[test]
+    fn it_attaches_metadata_to_route() {
+        let handler = || async { ok!() };
+        let handler = Func::new(handler);
+
+        let path = ["test".into(), "{value}".into()];
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&path, Method::GET, handler);
+        
+        route.get_mut(&path, &Method::GET).unwrap().metadata.insert(42u32);
+        
+        assert!(route.get_mut(&path, &Method::POST).is_none());
+        assert_eq!(route.get_mut(&path, &Method::GET).unwrap().metadata.get::<u32>(), Some(&42));
+    }
+
+    #[test]
+    fn it_collects_all_routes() {
+        let handler = Func::new(|| async { ok!() });
+
+        let mut route = Route::Static(RouteMap::default());
+        route.insert(&["test".into()], Method::GET, handler.clone());
+        route.insert(&["test".into(), "{value}".into()], Method::GET, handler.clone());
+        route.insert(&["test".into(), "{value}".into()], Method::PUT, handler);
+
+        let mut routes = Vec::new();
+        route.collect(&mut Vec::new(), &mut routes);
+        routes.sort_by_key(|(method, segments)| (segments.clone(), method.to_string()));
+
+        assert_eq!(routes, vec![
+            (Method::GET, vec!["test"]),
+            (Method::GET, vec!["test", "{value}"]),
+            (Method::PUT, vec!["test", "{value}"]),
+        ]);
+    }
+}
@@ -1,4 +1,5 @@
 ﻿use std::ops::{Deref, DerefMut};
+use bytes::Bytes;
 use hyper::{
     body::Incoming,
     http::request::Parts,
@@ -12,12 +13,20 @@ use crate::{
     UnsyncBoxBody,
     BoxBody
 };
-use crate::http::{endpoints::args::FromRequestRef, request::request_body_limit::RequestBodyLimit};
+use crate::http::{
+    endpoints::{args::{FromRequestRef, uri_parts::UriParts}, route::RouteMetadata},
+    request::{request_body_limit::RequestBodyLimit, body_drain::DrainBody, peek_body::PeekedBody}
+};
 
 #[cfg(feature = "di")]
 use crate::di::{Container, Inject};
 
 pub mod request_body_limit;
+pub mod request_timeout;
+pub(crate) mod merge_slashes;
+pub(crate) mod canonical_host;
+pub(crate) mod body_drain;
+pub(crate) mod peek_body;
 
 /// Wraps the incoming [`Request`] to enrich its functionality
 pub struct HttpRequest {
@@ -74,6 +83,36 @@ impl HttpRequest {
         }
     }
     
+    /// Turns [`HttpRequest's`] body into the one that drains up to `limit` bytes 
+    /// if it's dropped before being fully read
+    pub(crate) fn into_drained(mut self, limit: Option<usize>) -> Self {
+        if let Some(limit) = limit {
+            let body = std::mem::replace(self.body_mut(), HttpBody::empty());
+            *self.body_mut() = DrainBody::wrap(body, limit);
+        }
+        self
+    }
+    
+    /// Returns up to `n` first bytes of the request body without consuming them, 
+    /// so the request handler still receives the complete body.
+    /// 
+    /// The peeked bytes are buffered in memory and the body size limit is respected, 
+    /// so if the body exceeds it while peeking, an error is returned.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::HttpRequest;
+    ///
+    /// # async fn docs(mut req: HttpRequest) -> Result<(), volga::error::Error> {
+    /// let head = req.peek_body(1).await?;
+    /// let is_json = head.first() == Some(&b'{');
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn peek_body(&mut self, n: usize) -> Result<Bytes, Error> {
+        PeekedBody::peek(self.body_mut(), n).await
+    }
+    
     /// Unwraps the inner request
     #[inline]
     pub fn into_inner(self) -> Request<HttpBody> {
@@ -166,10 +205,38 @@ impl HttpRequest {
         T::from_request(self)
     }
 
+    /// Builds an absolute URL for the `path` using the scheme and host of the current request
+    /// 
+    /// If the trusted proxy mode is enabled with [`App::with_trusted_proxy`](crate::App::with_trusted_proxy),
+    /// the scheme and host are taken from the forwarded headers. See [`UriParts`] for more details.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::HttpRequest;
+    ///
+    /// # fn docs(req: HttpRequest) {
+    /// let location = req.absolute_url("/users/1");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn absolute_url(&self, path: &str) -> String {
+        UriParts::from_parts(self.uri(), self.headers(), self.extensions()).absolute_url(path)
+    }
+
     /// Inserts the [`Header<T>`] to HTTP request headers
     #[inline]
     pub fn insert_header<T: FromHeaders>(&mut self, header: Header<T>) {
         let (name, value) = header.into_parts();
         self.headers_mut().insert(name, value);
     }
+    
+    /// Returns a reference to the metadata of type `T` attached to the matched route
+    /// 
+    /// See [`RouteBuilder::with_metadata`](crate::routing::RouteBuilder::with_metadata) for more details.
+    #[inline]
+    pub fn route_metadata<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions()
+            .get::<RouteMetadata>()
+            .and_then(|metadata| metadata.get::<T>())
+    }
 }
@@ -0,0 +1,136 @@
+﻿//! Request Body Draining
+
+use bytes::Bytes;
+use http_body_util::BodyExt;
+use hyper::body::{Body, Frame, SizeHint};
+use tokio::runtime::Handle;
+use crate::{error::Error, HttpBody};
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+/// A body that reads the remainder of the `inner` one in background once it is dropped unfinished, 
+/// e.g. when a request handler responds early without consuming the request body.
+/// 
+/// It keeps the HTTP/1 connection reusable, since the next request can't be read 
+/// until the current one is fully received. If the remainder exceeds the `limit`, 
+/// the draining stops and the connection is closed.
+pub(crate) struct DrainBody {
+    inner: Option<HttpBody>,
+    limit: usize
+}
+
+impl DrainBody {
+    /// Wraps the `inner` body into [`HttpBody`] that drains up to `limit` bytes once dropped
+    pub(crate) fn wrap(inner: HttpBody, limit: usize) -> HttpBody {
+        HttpBody::boxed(Self { inner: Some(inner), limit })
+    }
+}
+
+impl Body for DrainBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    #[inline]
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.inner.as_mut() {
+            Some(inner) => Pin::new(inner).poll_frame(cx),
+            None => Poll::Ready(None)
+        }
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.inner
+            .as_ref()
+            .map_or(true, HttpBody::is_end_stream)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.inner
+            .as_ref()
+            .map_or_else(|| SizeHint::with_exact(0), HttpBody::size_hint)
+    }
+}
+
+impl Drop for DrainBody {
+    fn drop(&mut self) {
+        let Some(inner) = self.inner.take() else {
+            return;
+        };
+        if inner.is_end_stream() {
+            return;
+        }
+        if let Ok(handle) = Handle::try_current() {
+            handle.spawn(drain(inner, self.limit));
+        }
+    }
+}
+
+/// Reads and discards the `body` until it ends, fails or exceeds the `limit`
+async fn drain(mut body: HttpBody, limit: usize) {
+    let mut drained = 0;
+    while let Some(Ok(frame)) = body.frame().await {
+        if let Some(data) = frame.data_ref() {
+            drained += data.len();
+            if drained > limit {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("request body remainder exceeds the drain limit of {limit} bytes; closing the connection");
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::{atomic::{AtomicUsize, Ordering}, Arc}, time::Duration};
+    use futures_util::{stream, StreamExt};
+    use http_body_util::{BodyExt, StreamBody};
+    use hyper::body::Frame;
+    use crate::{error::Error, HttpBody};
+    use super::DrainBody;
+
+    fn counting_body(chunks: usize, polled: Arc<AtomicUsize>) -> HttpBody {
+        let frames = stream::iter(0..chunks).map(move |_| {
+            polled.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, Error>(Frame::data(bytes::Bytes::from_static(b"0123456789")))
+        });
+        HttpBody::boxed(StreamBody::new(frames))
+    }
+
+    #[tokio::test]
+    async fn it_passes_body_through() {
+        let body = DrainBody::wrap(HttpBody::full("Hello World!"), 1024);
+
+        let body = body.collect().await.unwrap().to_bytes();
+
+        assert_eq!(body, "Hello World!");
+    }
+
+    #[tokio::test]
+    async fn it_drains_unfinished_body_on_drop() {
+        let polled = Arc::new(AtomicUsize::new(0));
+        let mut body = DrainBody::wrap(counting_body(10, polled.clone()), 1024);
+
+        body.frame().await.unwrap().unwrap();
+        drop(body);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(polled.load(Ordering::SeqCst), 10);
+    }
+
+    #[tokio::test]
+    async fn it_stops_draining_once_limit_is_exceeded() {
+        let polled = Arc::new(AtomicUsize::new(0));
+        let body = DrainBody::wrap(counting_body(10, polled.clone()), 25);
+
+        drop(body);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(polled.load(Ordering::SeqCst), 3);
+    }
+}
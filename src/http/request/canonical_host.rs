@@ -0,0 +1,80 @@
+﻿//! Redirection between the `www` and apex variants of the canonical host
+
+use crate::UriParts;
+
+const WWW_PREFIX: &str = "www.";
+
+/// Holds the canonical host along with its `www` or apex alternative 
+/// that requests are redirected from
+#[derive(Debug, Clone)]
+pub(crate) struct CanonicalHost {
+    host: String,
+    alternative: String
+}
+
+impl CanonicalHost {
+    /// Creates the canonical host, e.g. for `example.com` the alternative is `www.example.com`
+    /// and vice versa
+    pub(crate) fn new(host: &str) -> Self {
+        let host = host.trim().to_ascii_lowercase();
+        let alternative = match host.strip_prefix(WWW_PREFIX) {
+            Some(apex) => apex.to_owned(),
+            None => format!("{WWW_PREFIX}{host}")
+        };
+        Self { host, alternative }
+    }
+
+    /// Returns the location to redirect the request to if it targets the alternative host,
+    /// preserving the scheme, port, path and query
+    pub(crate) fn redirect_location(&self, uri: &UriParts) -> Option<String> {
+        let host = uri.host()?;
+        if !host.eq_ignore_ascii_case(&self.alternative) {
+            return None;
+        }
+        let authority = match uri.port() {
+            Some(port) => format!("{}:{port}", self.host),
+            None => self.host.clone()
+        };
+        let query = uri.query().map_or(String::new(), |query| format!("?{query}"));
+        Some(format!("{}://{authority}{}{query}", uri.scheme(), uri.path()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::HOST, http::Extensions, HeaderMap, Uri};
+    use crate::UriParts;
+    use super::CanonicalHost;
+
+    fn uri_parts(host: &'static str, path: &'static str) -> UriParts {
+        let mut headers = HeaderMap::new();
+        headers.insert(HOST, host.parse().unwrap());
+        UriParts::from_parts(&Uri::from_static(path), &headers, &Extensions::new())
+    }
+
+    #[test]
+    fn it_redirects_www_to_apex() {
+        let canonical = CanonicalHost::new("example.com");
+
+        let location = canonical.redirect_location(&uri_parts("www.example.com", "/x?page=2"));
+
+        assert_eq!(location.as_deref(), Some("http://example.com/x?page=2"));
+    }
+
+    #[test]
+    fn it_redirects_apex_to_www() {
+        let canonical = CanonicalHost::new("www.example.com");
+
+        let location = canonical.redirect_location(&uri_parts("example.com:8080", "/x"));
+
+        assert_eq!(location.as_deref(), Some("http://www.example.com:8080/x"));
+    }
+
+    #[test]
+    fn it_does_not_redirect_other_hosts() {
+        let canonical = CanonicalHost::new("example.com");
+
+        assert!(canonical.redirect_location(&uri_parts("example.com", "/x")).is_none());
+        assert!(canonical.redirect_location(&uri_parts("api.example.com", "/x")).is_none());
+    }
+}
@@ -0,0 +1,65 @@
+﻿//! Normalization of duplicate slashes in request paths
+
+use hyper::{http::uri::PathAndQuery, Uri};
+
+/// Collapses consecutive slashes in the path of the `uri`, e.g. `/a//b` into `/a/b`
+///
+/// The query string is left as is.
+pub(crate) fn merge_slashes(uri: &mut Uri) {
+    if !uri.path().contains("//") {
+        return;
+    }
+
+    let mut path_and_query = String::with_capacity(uri.path().len());
+    for ch in uri.path().chars() {
+        if ch != '/' || !path_and_query.ends_with('/') {
+            path_and_query.push(ch);
+        }
+    }
+    if let Some(query) = uri.query() {
+        path_and_query.push('?');
+        path_and_query.push_str(query);
+    }
+
+    let Ok(path_and_query) = PathAndQuery::try_from(path_and_query) else {
+        return;
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(path_and_query);
+    if let Ok(merged) = Uri::from_parts(parts) {
+        *uri = merged;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::Uri;
+    use super::merge_slashes;
+
+    #[test]
+    fn it_merges_slashes() {
+        let mut uri: Uri = "/a//b///c/".parse().unwrap();
+
+        merge_slashes(&mut uri);
+
+        assert_eq!(uri, "/a/b/c/");
+    }
+
+    #[test]
+    fn it_keeps_query_and_authority() {
+        let mut uri: Uri = "http://localhost:7878//a//b?redirect=//c".parse().unwrap();
+
+        merge_slashes(&mut uri);
+
+        assert_eq!(uri, "http://localhost:7878/a/b?redirect=//c");
+    }
+
+    #[test]
+    fn it_keeps_normalized_path() {
+        let mut uri: Uri = "/a/b?c=d".parse().unwrap();
+
+        merge_slashes(&mut uri);
+
+        assert_eq!(uri, "/a/b?c=d");
+    }
+}
@@ -0,0 +1,132 @@
+﻿//! Request Body Peeking
+
+use bytes::{Bytes, BytesMut};
+use http_body_util::BodyExt;
+use hyper::body::{Body, Frame, SizeHint};
+use crate::{error::Error, HttpBody};
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll}
+};
+
+/// A body that yields the already buffered `prefix` first and then the remainder of the `inner` one, 
+/// so the peeked bytes are not lost for the request handler.
+pub(crate) struct PeekedBody {
+    prefix: Option<Bytes>,
+    trailers: Option<Frame<Bytes>>,
+    inner: HttpBody
+}
+
+impl PeekedBody {
+    /// Buffers at least `n` bytes of the `body` (or less if it ends earlier) 
+    /// and returns up to `n` first bytes of it.
+    /// 
+    /// The `body` is replaced with the one that re-exposes the buffered bytes, 
+    /// so it could be read in full afterward.
+    pub(crate) async fn peek(body: &mut HttpBody, n: usize) -> Result<Bytes, Error> {
+        let mut inner = std::mem::replace(body, HttpBody::empty());
+        let mut buffer = BytesMut::new();
+        let mut trailers = None;
+        let mut result = Ok(());
+        while buffer.len() < n {
+            match inner.frame().await {
+                None => break,
+                Some(Err(err)) => {
+                    result = Err(err);
+                    break;
+                },
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => buffer.extend_from_slice(&data),
+                    Err(frame) => {
+                        trailers = Some(frame);
+                        break;
+                    }
+                }
+            }
+        }
+
+        let prefix = buffer.freeze();
+        let peeked = prefix.slice(..n.min(prefix.len()));
+        *body = HttpBody::boxed(Self { 
+            prefix: (!prefix.is_empty()).then_some(prefix), 
+            trailers, 
+            inner
+        });
+        result.map(|_| peeked)
+    }
+}
+
+impl Body for PeekedBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    #[inline]
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if let Some(prefix) = self.prefix.take() {
+            return Poll::Ready(Some(Ok(Frame::data(prefix))));
+        }
+        if let Some(trailers) = self.trailers.take() {
+            return Poll::Ready(Some(Ok(trailers)));
+        }
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.prefix.is_none() && 
+        self.trailers.is_none() && 
+        self.inner.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        let prefix_len = self.prefix
+            .as_ref()
+            .map_or(0, |prefix| prefix.len() as u64);
+        let inner = self.inner.size_hint();
+        let mut hint = SizeHint::new();
+        hint.set_lower(inner.lower() + prefix_len);
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + prefix_len);
+        }
+        hint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+    use crate::HttpBody;
+    use super::PeekedBody;
+
+    #[tokio::test]
+    async fn it_peeks_first_bytes_and_keeps_full_body() {
+        let mut body = HttpBody::full("Hello, World!");
+
+        let peeked = PeekedBody::peek(&mut body, 5).await.unwrap();
+        assert_eq!(peeked, "Hello");
+
+        let collected = body.collect().await.unwrap().to_bytes();
+        assert_eq!(collected, "Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn it_peeks_whole_body_if_shorter() {
+        let mut body = HttpBody::full("Hi");
+
+        let peeked = PeekedBody::peek(&mut body, 5).await.unwrap();
+        assert_eq!(peeked, "Hi");
+
+        let collected = body.collect().await.unwrap().to_bytes();
+        assert_eq!(collected, "Hi");
+    }
+
+    #[tokio::test]
+    async fn it_returns_err_if_body_limit_exceeded() {
+        let body = HttpBody::full("Hello, World!");
+        let mut body = HttpBody::limited(body, 5);
+
+        assert!(PeekedBody::peek(&mut body, 10).await.is_err());
+    }
+}
@@ -1,5 +1,7 @@
 ﻿//! Request Body Limit
 
+use hyper::{header::CONTENT_LENGTH, HeaderMap};
+
 const DEFAULT_BODY_SIZE: usize = 5 * 1024 * 1024; // 5 MB
 
 /// Represents whether a request body has a configured limit of not
@@ -17,4 +19,37 @@ impl Default for RequestBodyLimit {
     fn default() -> Self {
         Self::Enabled(DEFAULT_BODY_SIZE)
     }
+}
+
+impl RequestBodyLimit {
+    /// Checks whether the declared `Content-Length` exceeds the limit, 
+    /// so the request could be rejected before reading the body
+    #[inline]
+    pub(crate) fn is_exceeded_by(&self, headers: &HeaderMap) -> bool {
+        let Self::Enabled(limit) = self else {
+            return false;
+        };
+        headers
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+            .is_some_and(|length| length > *limit as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyper::{header::CONTENT_LENGTH, HeaderMap};
+    use super::RequestBodyLimit;
+
+    #[test]
+    fn it_checks_content_length() {
+        let mut headers = HeaderMap::new();
+        assert!(!RequestBodyLimit::Enabled(10).is_exceeded_by(&headers));
+
+        headers.insert(CONTENT_LENGTH, "11".parse().unwrap());
+        assert!(RequestBodyLimit::Enabled(10).is_exceeded_by(&headers));
+        assert!(!RequestBodyLimit::Enabled(11).is_exceeded_by(&headers));
+        assert!(!RequestBodyLimit::Disabled.is_exceeded_by(&headers));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,119 @@
+﻿//! Request Timeout
+
+use bytes::Bytes;
+use hyper::body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+use tokio::time::{Instant, Sleep, sleep_until};
+use tokio_util::sync::CancellationToken;
+use crate::{error::Error, HttpBody};
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration
+};
+
+/// Represents whether a request handling has a configured timeout or not
+///
+/// Default: disabled
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum RequestTimeout {
+    /// Timeout completely disabled
+    #[default]
+    Disabled,
+    /// Limits the total request handling time, including streaming the response body
+    /// 
+    /// If it's configured app-wide, Server-Sent Events responses are not limited 
+    /// once their head is sent, unless the timeout is set for the route explicitly
+    Total(Duration),
+    /// Limits the time to the first byte, which is the time until the response head is ready,
+    /// streaming the response body afterward is not limited
+    FirstByte(Duration),
+}
+
+pin_project! {
+    /// A body that fails and cancels the request once the deadline is passed
+    pub(crate) struct TimeoutBody {
+        #[pin]
+        inner: HttpBody,
+        #[pin]
+        sleep: Sleep,
+        token: CancellationToken
+    }
+}
+
+impl TimeoutBody {
+    /// Wraps the `inner` body into [`HttpBody`] that fails and cancels the `token` once the `deadline` is passed
+    pub(crate) fn wrap(inner: HttpBody, deadline: Instant, token: CancellationToken) -> HttpBody {
+        HttpBody::boxed(Self { inner, sleep: sleep_until(deadline), token })
+    }
+}
+
+impl Body for TimeoutBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    #[inline]
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        if let Poll::Ready(frame) = this.inner.poll_frame(cx) {
+            return Poll::Ready(frame);
+        }
+        match this.sleep.poll(cx) {
+            Poll::Ready(()) => {
+                this.token.cancel();
+                Poll::Ready(Some(Err(Error::server_error("Request timeout: response body was not sent in time"))))
+            },
+            Poll::Pending => Poll::Pending
+        }
+    }
+
+    #[inline]
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use futures_util::stream;
+    use http_body_util::{BodyExt, StreamBody};
+    use hyper::body::Frame;
+    use tokio::time::Instant;
+    use tokio_util::sync::CancellationToken;
+    use crate::{error::Error, HttpBody};
+    use super::TimeoutBody;
+
+    #[tokio::test]
+    async fn it_passes_body_sent_in_time() {
+        let body = HttpBody::full("Hello World!");
+        let body = TimeoutBody::wrap(body, Instant::now() + Duration::from_secs(1), CancellationToken::new());
+
+        let body = body.collect().await.unwrap().to_bytes();
+
+        assert_eq!(body, "Hello World!");
+    }
+
+    #[tokio::test]
+    async fn it_fails_body_not_sent_in_time() {
+        let chunks = stream::unfold(0, |i| async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Some((Ok::<_, Error>(Frame::data(bytes::Bytes::from(format!("chunk #{i}")))), i + 1))
+        });
+        let body = HttpBody::boxed(StreamBody::new(chunks));
+        let token = CancellationToken::new();
+        let body = TimeoutBody::wrap(body, Instant::now() + Duration::from_millis(250), token.clone());
+
+        let result = body.collect().await;
+
+        assert!(result.is_err());
+        assert!(token.is_cancelled());
+    }
+}
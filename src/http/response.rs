@@ -26,8 +26,11 @@ pub mod form;
 pub mod file;
 pub mod stream;
 pub mod status;
+pub mod template;
 pub mod into_response;
+pub(crate) mod mapper;
 mod redirect;
+mod static_response;
 
 /// A customized response context with custom response `headers` and `content_type`
 /// > NOTE: This is not suitable for file response use the `file!` or `Results::file()` instead
@@ -14,6 +14,24 @@
 /// # Ok(())
 /// # }
 /// ```
+///
+/// The default `application/x-www-form-urlencoded` content type can be overridden, 
+/// e.g. to specify a charset:
+/// ```no_run
+/// use std::collections::HashMap;
+/// use volga::{form, headers::CONTENT_TYPE};
+///
+/// # async fn dox() -> std::io::Result<()> {
+/// let data = HashMap::from([
+///     ("key", "value")
+/// ]);
+///
+/// form!(data, [
+///     (CONTENT_TYPE, "application/x-www-form-urlencoded; charset=utf-8")
+/// ]);
+/// # Ok(())
+/// # }
+/// ```
 #[macro_export]
 macro_rules! form {
     // handles form!({ "key": "value" })
@@ -33,10 +51,10 @@ macro_rules! form {
             $crate::http::StatusCode::OK,
             $crate::HttpBody::form($crate::json::json_internal!({ $($json)* })),
             [
-                ($crate::headers::CONTENT_TYPE, "application/x-www-form-urlencoded"),
                 $( ($key, $value) ),*
             ]
         )
+        .map($crate::http::response::form::__form_content_type)
     };
     
     // handles form!(object, [("key", "val")])
@@ -45,10 +63,10 @@ macro_rules! form {
             $crate::http::StatusCode::OK,
             $crate::HttpBody::form($body),
             [
-                ($crate::headers::CONTENT_TYPE, "application/x-www-form-urlencoded"),
                 $( ($key, $value) ),*
             ]
         )
+        .map($crate::http::response::form::__form_content_type)
     };
     
     // handles form!(object)
@@ -63,6 +81,17 @@ macro_rules! form {
     };
 }
 
+/// Sets the Form Data content type unless it has been specified explicitly
+#[doc(hidden)]
+#[inline]
+pub fn __form_content_type(mut response: crate::HttpResponse) -> crate::HttpResponse {
+    response
+        .headers_mut()
+        .entry(crate::headers::CONTENT_TYPE)
+        .or_insert(crate::headers::HeaderValue::from_static("application/x-www-form-urlencoded"));
+    response
+}
+
 #[cfg(test)]
 mod tests {
     use http_body_util::BodyExt;
@@ -107,6 +136,23 @@ mod tests {
         assert_eq!(response.headers().get("x-req-id").unwrap(), "some req id");
     }
 
+    #[tokio::test]
+    async fn it_creates_form_data_response_with_overridden_content_type() {
+        let data = HashMap::from([
+            ("key", "some value"),
+        ]);
+        let response = form!(data, [
+            ("Content-Type", "application/x-www-form-urlencoded; charset=utf-8"),
+        ]);
+
+        let mut response = response.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(String::from_utf8_lossy(body), "key=some+value");
+        assert_eq!(response.headers().get_all("Content-Type").iter().count(), 1);
+        assert_eq!(response.headers().get("Content-Type").unwrap(), "application/x-www-form-urlencoded; charset=utf-8");
+    }
+
     #[tokio::test]
     async fn it_creates_form_data_untyped_response() {
         let response = form!({ "key": "value" });
@@ -5,6 +5,9 @@ use crate::http::StatusCode;
 use crate::headers::CONTENT_TYPE;
 use mime::TEXT_PLAIN_UTF_8;
 
+#[cfg(feature = "prost")]
+use crate::{Protobuf, http::endpoints::args::protobuf::PROTOBUF_CONTENT_TYPE};
+
 use std::{
     io::Error as IoError,
     convert::Infallible,
@@ -115,6 +118,18 @@ impl<T: Serialize> IntoResponse for Form<T> {
     }
 }
 
+#[cfg(feature = "prost")]
+impl<T: prost::Message> IntoResponse for Protobuf<T> {
+    #[inline]
+    fn into_response(self) -> HttpResult {
+        response!(
+            StatusCode::OK,
+            HttpBody::full(self.into_inner().encode_to_vec()),
+            [(CONTENT_TYPE, PROTOBUF_CONTENT_TYPE)]
+        )
+    }
+}
+
 impl<T: Serialize> IntoResponse for ResponseContext<T> {
     #[inline]
     fn into_response(self) -> HttpResult {
@@ -0,0 +1,78 @@
+﻿//! Global response mapping
+
+use futures_util::future::BoxFuture;
+use std::{future::Future, sync::Arc};
+
+use crate::{
+    App,
+    http::IntoResponse,
+    HttpResponse,
+    HttpResult
+};
+
+/// Trait for types that represents a global response mapper
+pub(crate) trait ResponseMapper {
+    fn call(&self, response: HttpResponse) -> BoxFuture<'_, HttpResult>;
+}
+
+/// Owns a closure that maps a response
+struct MapResponseFunc<F>(F);
+
+impl<F, R, Fut> ResponseMapper for MapResponseFunc<F>
+where
+    F: Fn(HttpResponse) -> Fut + Send + Sync,
+    R: IntoResponse,
+    Fut: Future<Output = R> + Send,
+{
+    #[inline]
+    fn call(&self, response: HttpResponse) -> BoxFuture<'_, HttpResult> {
+        Box::pin(async move {
+            self.0(response).await.into_response()
+        })
+    }
+}
+
+/// Holds a reference to global response mapper
+pub(crate) type PipelineResponseMapper = Arc<
+    dyn ResponseMapper
+    + Send
+    + Sync
+>;
+
+impl App {
+    /// Adds a global response mapper that is applied to every response,
+    /// including the error ones, after the request handler, middlewares and error handler
+    /// 
+    /// Unlike a middleware, it has no access to the request and runs only once the response is ready,
+    /// multiple mappers are applied in the order they were added.
+    /// 
+    /// If a mapper fails, its error is turned into a response by the error handler 
+    /// configured with [`App::map_err`] or [`App::with_error_format`], 
+    /// and the following mappers are applied to that error response.
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::{App, headers::HeaderValue};
+    /// 
+    /// # #[tokio::main]
+    /// # async fn main() -> std::io::Result<()> {
+    /// let mut app = App::new();
+    /// 
+    /// app.map_response(|mut resp| async move {
+    ///     resp.headers_mut().insert("x-api-version", HeaderValue::from_static("1.0"));
+    ///     resp
+    /// });
+    /// # app.run().await
+    /// # }
+    /// ```
+    pub fn map_response<F, R, Fut>(&mut self, mapper: F) -> &mut Self
+    where
+        F: Fn(HttpResponse) -> Fut + Send + Sync + 'static,
+        R: IntoResponse,
+        Fut: Future<Output = R> + Send
+    {
+        self.pipeline
+            .add_response_mapper(Arc::new(MapResponseFunc(mapper)));
+        self
+    }
+}
@@ -0,0 +1,53 @@
+﻿/// Produces a request handler that always responds with the specified status, body and headers
+///
+/// Accepts the same arguments as [`status!`](crate::status) does. 
+/// The arguments are evaluated on each request, so they should be constants or literals.
+///
+/// # Examples
+/// ```no_run
+/// use volga::{App, static_response};
+///
+///# #[tokio::main]
+///# async fn main() -> std::io::Result<()> {
+/// let mut app = App::new();
+///
+/// app.map_get("/ping", static_response!(200, "pong"));
+/// app.map_get("/legacy", static_response!(410));
+/// app.map_get("/health", static_response!(204, [
+///     ("cache-control", "no-store")
+/// ]));
+///# app.run().await
+///# }
+/// ```
+#[macro_export]
+macro_rules! static_response {
+    ($($args:tt)*) => {
+        || async { $crate::status!($($args)*) }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+
+    #[tokio::test]
+    async fn it_creates_handler_with_body() {
+        let handler = static_response!(200, "pong");
+
+        let mut response = handler().await.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(response.status(), 200);
+        assert_eq!(String::from_utf8_lossy(body), "\"pong\"");
+    }
+
+    #[tokio::test]
+    async fn it_creates_handler_with_headers() {
+        let handler = static_response!(204, [("cache-control", "no-store")]);
+
+        let response = handler().await.unwrap();
+
+        assert_eq!(response.status(), 204);
+        assert_eq!(response.headers().get("cache-control").unwrap(), "no-store");
+    }
+}
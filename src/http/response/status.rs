@@ -26,6 +26,12 @@
 /// let error = ErrorMessage { error: "some error message".into() };
 /// status!(401, error);
 /// ```
+/// ## Custom reason phrase
+/// ```no_run
+/// use volga::status;
+///
+/// status!(499, reason: "Client Closed Request");
+/// ```
 #[macro_export]
 macro_rules! status {
     ($status:expr, { $($json:tt)* }) => {
@@ -38,6 +44,15 @@ macro_rules! status {
         )
     };
     
+    ($status:expr, reason: $reason:expr) => {
+        $crate::status!($status).and_then(|mut response| {
+            let reason = $crate::http::ReasonPhrase::try_from($reason.as_bytes())
+                .map_err($crate::error::Error::server_error)?;
+            response.extensions_mut().insert(reason);
+            Ok(response)
+        })
+    };
+    
     ($status:expr) => {
         $crate::response!(
             $crate::http::StatusCode::from_u16($status).unwrap_or($crate::http::StatusCode::OK), 
@@ -339,4 +354,26 @@ mod tests {
         assert_eq!(response.headers().get("x-api-key").unwrap(), "some api key");
         assert_eq!(response.headers().get("x-req-id").unwrap(), "some req id");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn it_creates_499_response_with_custom_reason_phrase() {
+        let response = status!(499, reason: "Client Closed Request");
+
+        assert!(response.is_ok());
+
+        let mut response = response.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+        let reason = response.extensions().get::<crate::http::ReasonPhrase>().unwrap();
+
+        assert_eq!(body.len(), 0);
+        assert_eq!(response.status(), 499);
+        assert_eq!(reason.as_bytes(), b"Client Closed Request");
+    }
+
+    #[test]
+    fn it_returns_error_for_invalid_reason_phrase() {
+        let response = status!(499, reason: "Client\r\nClosed");
+
+        assert!(response.is_err());
+    }
+}
@@ -40,6 +40,50 @@ macro_rules! stream {
     };
 }
 
+/// Produces `OK 200` response with the body streamed from an [`AsyncRead`](tokio::io::AsyncRead) source
+/// 
+/// # Examples
+/// ## Default usage
+///```no_run
+/// use volga::read_stream;
+/// use tokio::fs::File;
+///
+/// # async fn dox() -> std::io::Result<()> {
+/// let reader = File::open("example.txt").await?;
+/// read_stream!(reader);
+/// # Ok(())
+/// # }
+/// ```
+/// ## Custom headers
+///```no_run
+/// use volga::read_stream;
+/// use tokio::fs::File;
+///
+/// # async fn dox() -> std::io::Result<()> {
+/// let reader = File::open("example.txt").await?;
+/// read_stream!(reader, [
+///    ("Content-Type", "text/plain")
+/// ]);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! read_stream {
+    ($reader:expr) => {
+        $crate::response!(
+            $crate::http::StatusCode::OK,
+            $crate::HttpBody::wrap_reader($reader)
+        )
+    };
+    ($reader:expr, [ $( ($key:expr, $value:expr) ),* $(,)? ]) => {
+        $crate::response!(
+            $crate::http::StatusCode::OK, 
+            $crate::HttpBody::wrap_reader($reader),
+            [ $( ($key, $value) ),* ]
+        )
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::Path;
@@ -83,4 +127,38 @@ mod tests {
         assert_eq!(response.headers()["x-api-key"], "some api key");
         assert_eq!(response.status(), 200);
     }
+
+    #[tokio::test]
+    async fn it_creates_read_stream_response() {
+        let content = "Hello, this is some streamed content!".repeat(1024);
+        let reader = std::io::Cursor::new(content.clone().into_bytes());
+
+        let response = read_stream!(reader);
+
+        assert!(response.is_ok());
+
+        let mut response = response.unwrap();
+        let body = read_file_bytes(&mut response).await;
+
+        assert_eq!(String::from_utf8_lossy(body.as_slice()), content);
+        assert_eq!(response.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn it_creates_read_stream_response_with_custom_headers() {
+        let reader: &'static [u8] = b"Hello, this is some streamed content!";
+
+        let response = read_stream!(reader, [
+            ("Content-Type", "text/plain")
+        ]);
+
+        assert!(response.is_ok());
+
+        let mut response = response.unwrap();
+        let body = read_file_bytes(&mut response).await;
+
+        assert_eq!(String::from_utf8_lossy(body.as_slice()), "Hello, this is some streamed content!");
+        assert_eq!(response.headers()["Content-Type"], "text/plain");
+        assert_eq!(response.status(), 200);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,198 @@
+﻿//! Tools for rendering templates into HTTP responses
+
+use serde::Serialize;
+use crate::error::Error;
+
+/// Describes a template engine that is able to render a named template with a serializable context
+/// 
+/// Implement this trait to plug a custom template engine into the [`render!`](crate::render) macro.
+/// 
+/// # Example
+/// ```no_run
+/// use volga::{error::Error, render, HttpResult};
+/// use volga::http::response::template::Template;
+/// use serde::Serialize;
+///
+/// struct Greeter;
+///
+/// impl Template for Greeter {
+///     fn render_template<C: Serialize>(&self, _name: &str, context: &C) -> Result<String, Error> {
+///         let context = serde_json::to_value(context).map_err(Error::server_error)?;
+///         Ok(format!("<h1>Hello, {}!</h1>", context["name"].as_str().unwrap_or("World")))
+///     }
+/// }
+///
+/// async fn handle() -> HttpResult {
+///     render!(Greeter, "hello", { "name": "John" })
+/// }
+/// ```
+pub trait Template {
+    /// Renders a template with the specified `name` using the `context`
+    fn render_template<C: Serialize>(&self, name: &str, context: &C) -> Result<String, Error>;
+}
+
+/// Renders templates with [Tera](https://keats.github.io/tera/)
+#[cfg(feature = "template-tera")]
+impl Template for tera::Tera {
+    #[inline]
+    fn render_template<C: Serialize>(&self, name: &str, context: &C) -> Result<String, Error> {
+        let context = tera::Context::from_serialize(context)
+            .map_err(TemplateError::from_tera_error)?;
+        self.render(name, &context)
+            .map_err(TemplateError::from_tera_error)
+    }
+}
+
+/// Describes errors of template rendering
+#[cfg(feature = "template-tera")]
+struct TemplateError;
+
+#[cfg(feature = "template-tera")]
+impl TemplateError {
+    #[inline]
+    fn from_tera_error(error: tera::Error) -> Error {
+        Error::server_error(format!("Template rendering error: {error:?}"))
+    }
+}
+
+/// Produces `OK 200` response with HTML body rendered from a named template
+/// 
+/// The first argument is a template engine that implements the 
+/// [`Template`](crate::http::response::template::Template) trait, 
+/// the second one is a template name, and the third one is an optional context.
+///
+/// # Examples
+/// ## With anonymous context
+/// ```no_run
+/// # #[cfg(feature = "template-tera")] {
+/// use volga::render;
+/// use tera::Tera;
+///
+/// let tera = Tera::new("templates/**/*.html").unwrap();
+/// render!(tera, "hello.html", { "name": "John" });
+/// # }
+/// ```
+/// ## With serializable context
+/// ```no_run
+/// # #[cfg(feature = "template-tera")] {
+/// use volga::render;
+/// use serde::Serialize;
+/// use tera::Tera;
+///
+/// #[derive(Serialize)]
+/// struct User {
+///     name: String
+/// }
+///
+/// let tera = Tera::new("templates/**/*.html").unwrap();
+/// let user = User { name: "John".into() };
+/// render!(tera, "hello.html", user);
+/// # }
+/// ```
+/// ## Without context
+/// ```no_run
+/// # #[cfg(feature = "template-tera")] {
+/// use volga::render;
+/// use tera::Tera;
+///
+/// let tera = Tera::new("templates/**/*.html").unwrap();
+/// render!(tera, "index.html");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! render {
+    ($engine:expr, $name:expr, { $($json:tt)* }) => {
+        $crate::render!($engine, $name, $crate::json::json_internal!({ $($json)* }))
+    };
+    
+    ($engine:expr, $name:expr, $context:expr) => {{
+        use $crate::http::response::template::Template as _;
+        $engine
+            .render_template($name, &$context)
+            .and_then(|html| $crate::response!(
+                $crate::http::StatusCode::OK,
+                $crate::HttpBody::full(html),
+                [
+                    ($crate::headers::CONTENT_TYPE, "text/html; charset=utf-8"),
+                ]
+            ))
+    }};
+    
+    ($engine:expr, $name:expr) => {
+        $crate::render!($engine, $name, {})
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use http_body_util::BodyExt;
+    use serde::Serialize;
+    use crate::error::Error;
+    use super::Template;
+
+    struct TestEngine;
+    
+    impl Template for TestEngine {
+        fn render_template<C: Serialize>(&self, name: &str, context: &C) -> Result<String, Error> {
+            let context = serde_json::to_value(context).map_err(Error::server_error)?;
+            match name { 
+                "hello" => Ok(format!("<p>Hello, {}!</p>", context["name"].as_str().unwrap_or_default())),
+                _ => Err(Error::server_error(format!("Template not found: {name}")))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn it_renders_template_with_custom_engine() {
+        let response = render!(TestEngine, "hello", { "name": "John" });
+
+        assert!(response.is_ok());
+
+        let mut response = response.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(String::from_utf8_lossy(body), "<p>Hello, John!</p>");
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers().get("Content-Type").unwrap(), "text/html; charset=utf-8");
+    }
+
+    #[test]
+    fn it_returns_error_for_unknown_template() {
+        let response = render!(TestEngine, "unknown");
+
+        assert!(response.is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "template-tera")]
+    async fn it_renders_tera_template() {
+        #[derive(Serialize)]
+        struct User {
+            name: String
+        }
+        
+        let mut tera = tera::Tera::default();
+        tera.add_raw_template("hello.html", "<h1>Hello, {{ name }}!</h1>").unwrap();
+        
+        let user = User { name: "John".into() };
+        let response = render!(tera, "hello.html", user);
+
+        assert!(response.is_ok());
+
+        let mut response = response.unwrap();
+        let body = &response.body_mut().collect().await.unwrap().to_bytes();
+
+        assert_eq!(String::from_utf8_lossy(body), "<h1>Hello, John!</h1>");
+        assert_eq!(response.headers().get("Content-Type").unwrap(), "text/html; charset=utf-8");
+    }
+
+    #[test]
+    #[cfg(feature = "template-tera")]
+    fn it_returns_error_for_missing_tera_template() {
+        let tera = tera::Tera::default();
+        
+        let response = render!(tera, "missing.html", { "name": "John" });
+
+        assert!(response.is_err());
+    }
+}
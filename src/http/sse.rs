@@ -0,0 +1,472 @@
+﻿//! Tools for Server-Sent Events
+
+use bytes::Bytes;
+use hyper::body::{Body, Frame};
+use crate::{error::Error, HttpBody};
+
+use std::{
+    collections::VecDeque,
+    fmt::Write,
+    future::poll_fn,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    time::Duration
+};
+
+/// Content type of the Server-Sent Events stream
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// Represents a single Server-Sent Event
+///
+/// # Example
+/// ```no_run
+/// use volga::http::sse::Message;
+///
+/// let message = Message::new()
+///     .event("greeting")
+///     .id("1")
+///     .data("Hello, World!");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Message {
+    event: Option<String>,
+    id: Option<String>,
+    retry: Option<Duration>,
+    data: Option<String>,
+    comment: Option<String>
+}
+
+impl Message {
+    /// Creates a new empty message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `data` field of the message, multiline data is split into several `data` lines
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Sets the `event` field which is the event type of the message
+    pub fn event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Sets the `id` field which is the last event ID of the message
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the `retry` field which is the client reconnection time
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sets a comment which is ignored by clients but could be used to keep the connection alive
+    pub fn comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Encodes the message into the `text/event-stream` format
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buf = String::new();
+        if let Some(comment) = &self.comment {
+            comment.lines().for_each(|line| { let _ = writeln!(buf, ": {line}"); });
+        }
+        if let Some(event) = &self.event {
+            let _ = writeln!(buf, "event: {}", single_line(event));
+        }
+        if let Some(id) = &self.id {
+            let _ = writeln!(buf, "id: {}", single_line(id));
+        }
+        if let Some(retry) = &self.retry {
+            let _ = writeln!(buf, "retry: {}", retry.as_millis());
+        }
+        if let Some(data) = &self.data {
+            if data.is_empty() {
+                buf.push_str("data: \n");
+            }
+            data.lines().for_each(|line| { let _ = writeln!(buf, "data: {line}"); });
+        }
+        buf.push('\n');
+        Bytes::from(buf)
+    }
+}
+
+impl From<&str> for Message {
+    #[inline]
+    fn from(data: &str) -> Self {
+        Self::new().data(data)
+    }
+}
+
+impl From<String> for Message {
+    #[inline]
+    fn from(data: String) -> Self {
+        Self::new().data(data)
+    }
+}
+
+#[inline]
+fn single_line(value: &str) -> &str {
+    value.lines().next().unwrap_or_default()
+}
+
+/// Describes what happens when a message is sent to a subscriber whose buffer is full,
+/// e.g. because the client reads the stream slower than the messages are sent
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SlowSubscriberPolicy {
+    /// The oldest buffered message is dropped to make room for the new one
+    #[default]
+    DropOldest,
+    /// The subscriber is disconnected, so its stream ends
+    Disconnect
+}
+
+/// A buffer of encoded messages of a single subscriber
+#[derive(Debug)]
+struct Queue {
+    messages: VecDeque<Bytes>,
+    capacity: Option<usize>,
+    policy: SlowSubscriberPolicy,
+    waker: Option<Waker>,
+    closed: bool
+}
+
+impl Queue {
+    /// Pushes the message into the buffer, returns `false` if the subscriber is disconnected
+    fn push(&mut self, message: Bytes) -> bool {
+        if self.closed {
+            return false;
+        }
+        if self.capacity.is_some_and(|capacity| self.messages.len() >= capacity) {
+            match self.policy {
+                SlowSubscriberPolicy::DropOldest => _ = self.messages.pop_front(),
+                SlowSubscriberPolicy::Disconnect => {
+                    self.close();
+                    return false;
+                }
+            }
+        }
+        self.messages.push_back(message);
+        self.wake();
+        true
+    }
+
+    #[inline]
+    fn close(&mut self) {
+        self.closed = true;
+        self.messages.clear();
+        self.wake();
+    }
+
+    #[inline]
+    fn wake(&mut self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+type SharedQueue = Arc<Mutex<Queue>>;
+
+/// Holds the buffers of all subscribers
+type Subscribers = Arc<Mutex<Vec<SharedQueue>>>;
+
+/// A channel that broadcasts [`Message`]s to all connected Server-Sent Events clients
+///
+/// It is cheap to clone and can be registered as a singleton in DI,
+/// so the messages could be pushed from anywhere in the app.
+///
+/// By default, each subscriber buffers an unlimited number of messages.
+/// Use [`SseChannel::bounded`] to limit the buffers, so a slow client doesn't hold 
+/// an ever-growing backlog, while other subscribers keep receiving messages.
+///
+/// # Example
+/// ```no_run
+/// use volga::{App, http::sse::SseChannel, sse_from_channel};
+///
+/// # #[tokio::main]
+/// # async fn main() -> std::io::Result<()> {
+/// let mut app = App::new();
+/// let channel = SseChannel::new();
+/// let sender = channel.sender();
+///
+/// app.map_get("/events", move || {
+///     let rx = channel.subscribe();
+///     async move { sse_from_channel!(rx) }
+/// });
+///
+/// app.map_post("/notify/{text}", move |text: String| {
+///     sender.send(text);
+///     async {}
+/// });
+/// # app.run().await
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SseChannel {
+    subscribers: Subscribers,
+    capacity: Option<usize>,
+    policy: SlowSubscriberPolicy
+}
+
+impl SseChannel {
+    /// Creates a new channel without subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new channel where each subscriber buffers up to `capacity` messages, 
+    /// once the buffer is full, the `policy` is applied
+    ///
+    /// # Panics
+    /// If `capacity` is zero
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::http::sse::{SseChannel, SlowSubscriberPolicy};
+    ///
+    /// let channel = SseChannel::bounded(64, SlowSubscriberPolicy::Disconnect);
+    /// ```
+    pub fn bounded(capacity: usize, policy: SlowSubscriberPolicy) -> Self {
+        assert!(capacity > 0, "SSE channel capacity must be greater than zero");
+        Self {
+            subscribers: Subscribers::default(),
+            capacity: Some(capacity),
+            policy
+        }
+    }
+
+    /// Returns a sender that pushes messages into this channel
+    #[inline]
+    pub fn sender(&self) -> SseSender {
+        SseSender { subscribers: self.subscribers.clone() }
+    }
+
+    /// Subscribes a new client to the channel
+    ///
+    /// The subscription is removed once the returned [`SseReceiver`] 
+    /// or the response body created from it is dropped, e.g. when the client disconnects.
+    pub fn subscribe(&self) -> SseReceiver {
+        let queue = Arc::new(Mutex::new(Queue {
+            messages: VecDeque::new(),
+            capacity: self.capacity,
+            policy: self.policy,
+            waker: None,
+            closed: false
+        }));
+        lock(&self.subscribers).push(queue.clone());
+        SseReceiver { queue }
+    }
+
+    /// Sends the message to all subscribers and returns the number of subscribers it was sent to
+    #[inline]
+    pub fn send(&self, message: impl Into<Message>) -> usize {
+        self.sender().send(message)
+    }
+
+    /// Returns the number of subscribers
+    pub fn subscribers_count(&self) -> usize {
+        let mut subscribers = lock(&self.subscribers);
+        subscribers.retain(|queue| !lock(queue).closed);
+        subscribers.len()
+    }
+}
+
+/// Pushes [`Message`]s into the [`SseChannel`]
+#[derive(Debug, Clone)]
+pub struct SseSender {
+    subscribers: Subscribers
+}
+
+impl SseSender {
+    /// Sends the message to all subscribers and returns the number of subscribers it was sent to
+    pub fn send(&self, message: impl Into<Message>) -> usize {
+        let message = message.into().to_bytes();
+        let mut subscribers = lock(&self.subscribers);
+        subscribers.retain(|queue| lock(queue).push(message.clone()));
+        subscribers.len()
+    }
+}
+
+#[inline]
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// A subscription to the [`SseChannel`] 
+/// 
+/// Use [`sse_from_channel!`](crate::sse_from_channel) to stream it to the client
+#[derive(Debug)]
+pub struct SseReceiver {
+    queue: SharedQueue
+}
+
+impl SseReceiver {
+    /// Receives the next encoded message, `None` if the subscriber is disconnected
+    pub async fn recv(&mut self) -> Option<Bytes> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    fn poll_recv(&self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let mut queue = lock(&self.queue);
+        if let Some(message) = queue.messages.pop_front() {
+            return Poll::Ready(Some(message));
+        }
+        if queue.closed {
+            return Poll::Ready(None);
+        }
+        queue.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Converts the subscription into the `text/event-stream` response body
+    pub fn into_body(self) -> HttpBody {
+        HttpBody::boxed(self)
+    }
+}
+
+impl Body for SseReceiver {
+    type Data = Bytes;
+    type Error = Error;
+
+    #[inline]
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.poll_recv(cx)
+            .map(|message| message.map(|message| Ok(Frame::data(message))))
+    }
+}
+
+impl Drop for SseReceiver {
+    #[inline]
+    fn drop(&mut self) {
+        lock(&self.queue).close();
+    }
+}
+
+/// Produces `OK 200` response that streams Server-Sent Events from the [`SseReceiver`]
+///
+/// # Example
+/// ```no_run
+/// use volga::{http::sse::SseChannel, sse_from_channel};
+///
+/// let channel = SseChannel::new();
+/// let response = sse_from_channel!(channel.subscribe());
+/// ```
+#[macro_export]
+macro_rules! sse_from_channel {
+    ($rx:expr) => {
+        $crate::response!(
+            $crate::http::StatusCode::OK,
+            $crate::http::sse::SseReceiver::into_body($rx),
+            [
+                ($crate::headers::CONTENT_TYPE, $crate::http::sse::SSE_CONTENT_TYPE),
+                ($crate::headers::CACHE_CONTROL, "no-cache")
+            ]
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use super::{Message, SlowSubscriberPolicy, SseChannel};
+
+    #[test]
+    fn it_encodes_message() {
+        let message = Message::new()
+            .event("greeting")
+            .id("1")
+            .retry(Duration::from_secs(3))
+            .data("Hello,\nWorld!");
+
+        assert_eq!(message.to_bytes(), "event: greeting\nid: 1\nretry: 3000\ndata: Hello,\ndata: World!\n\n");
+    }
+
+    #[test]
+    fn it_encodes_comment() {
+        let message = Message::new().comment("keep-alive");
+
+        assert_eq!(message.to_bytes(), ": keep-alive\n\n");
+    }
+
+    #[tokio::test]
+    async fn it_broadcasts_messages_to_all_subscribers() {
+        let channel = SseChannel::new();
+        let mut first = channel.subscribe();
+        let mut second = channel.subscribe();
+
+        assert_eq!(channel.sender().send("Hello"), 2);
+
+        assert_eq!(first.recv().await.unwrap(), "data: Hello\n\n");
+        assert_eq!(second.recv().await.unwrap(), "data: Hello\n\n");
+    }
+
+    #[test]
+    fn it_removes_dropped_subscribers() {
+        let channel = SseChannel::new();
+        let subscriber = channel.subscribe();
+
+        assert_eq!(channel.subscribers_count(), 1);
+
+        drop(subscriber);
+
+        assert_eq!(channel.send("Hello"), 0);
+        assert_eq!(channel.subscribers_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_drops_oldest_messages_of_slow_subscriber() {
+        let channel = SseChannel::bounded(2, SlowSubscriberPolicy::DropOldest);
+        let mut slow = channel.subscribe();
+        let mut fast = channel.subscribe();
+
+        for i in 0..3 {
+            assert_eq!(channel.send(format!("{i}")), 2);
+            assert_eq!(fast.recv().await.unwrap(), format!("data: {i}\n\n"));
+        }
+
+        assert_eq!(slow.recv().await.unwrap(), "data: 1\n\n");
+        assert_eq!(slow.recv().await.unwrap(), "data: 2\n\n");
+    }
+
+    #[tokio::test]
+    async fn it_disconnects_slow_subscriber() {
+        let channel = SseChannel::bounded(2, SlowSubscriberPolicy::Disconnect);
+        let mut slow = channel.subscribe();
+        let mut fast = channel.subscribe();
+
+        assert_eq!(channel.send("0"), 2);
+        assert_eq!(channel.send("1"), 2);
+        assert_eq!(fast.recv().await.unwrap(), "data: 0\n\n");
+        assert_eq!(fast.recv().await.unwrap(), "data: 1\n\n");
+
+        assert_eq!(channel.send("2"), 1);
+        assert_eq!(fast.recv().await.unwrap(), "data: 2\n\n");
+        assert!(slow.recv().await.is_none());
+        assert_eq!(channel.subscribers_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn it_wakes_waiting_subscriber() {
+        let channel = SseChannel::new();
+        let mut subscriber = channel.subscribe();
+        let sender = channel.sender();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sender.send("Hello");
+        });
+
+        assert_eq!(subscriber.recv().await.unwrap(), "data: Hello\n\n");
+    }
+}
@@ -58,11 +58,21 @@ pub use crate::http::{
     response::builder::{RESPONSE_ERROR, SERVER_NAME},
     endpoints::args::{
         cancellation_token::CancellationToken,
+        conn_info::ConnInfo,
         file::File,
         json::Json,
         path::Path,
-        query::Query,
+        query::{Query, RawQuery},
         form::Form,
+        http_version::HttpVersion,
+        if_range::IfRangeCondition,
+        lines::Lines,
+        merge_patch::MergePatch,
+        json_patch::JsonPatch,
+        pagination::Pagination,
+        received_at::ReceivedAt,
+        subdomain::Subdomain,
+        uri_parts::UriParts,
     },
     BoxBody,
     UnsyncBoxBody,
@@ -78,8 +88,17 @@ pub use crate::http::{
 #[cfg(feature = "multipart")]
 pub use crate::http::endpoints::args::multipart::Multipart;
 
+#[cfg(feature = "prost")]
+pub use crate::http::endpoints::args::protobuf::Protobuf;
+
 pub mod routing {
-    pub use crate::app::router::RouteGroup;
+    pub use crate::app::router::{RouteBuilder, RouteGroup, RouteInfo, RoutePatterns};
+    pub use crate::app::router::concurrency::ConcurrencyLimiter;
+    
+    #[cfg(feature = "macros")]
+    pub use crate::app::router::attribute::MapRoute;
+    #[cfg(feature = "macros")]
+    pub use volga_macros::{get, post, put, patch, delete, head, options, trace, connect};
 }
 
 
@@ -25,7 +25,10 @@ pub mod compress;
     feature = "decompression-full"
 ))]
 pub mod decompress;
+#[cfg(feature = "checksum")]
+pub mod checksum;
 pub mod http_context;
+pub mod response_time;
 
 /// Points to the next middleware or request handler
 pub type Next = Arc<
@@ -42,13 +45,19 @@ type MiddlewareFn = Arc<
 >;
 
 pub(super) struct Middlewares {
-    pipeline: Vec<MiddlewareFn>
+    pipeline: Vec<MiddlewareFn>,
+    #[cfg(feature = "tracing")]
+    names: Vec<&'static str>
 }
 
 impl Middlewares {
     /// Initializes a new middleware pipeline
     pub(super) fn new() -> Self {
-        Self { pipeline: Vec::new() }
+        Self { 
+            pipeline: Vec::new(),
+            #[cfg(feature = "tracing")]
+            names: Vec::new()
+        }
     }
 
     /// Returns `true` if there are no middlewares,
@@ -57,6 +66,12 @@ impl Middlewares {
         self.pipeline.is_empty()
     }
 
+    /// Returns the names of registered middlewares in the order of registration
+    #[cfg(feature = "tracing")]
+    pub(super) fn names(&self) -> &[&'static str] {
+        &self.names
+    }
+
     /// Composes middlewares into a "Linked List" and returns head
     pub(super) fn compose(&self) -> Option<Next> {
         if self.pipeline.is_empty() {
@@ -108,6 +123,17 @@ impl App {
     ///# }
     /// ```
     pub fn use_middleware<F, Fut>(&mut self, middleware: F) -> &mut Self
+    where
+        F: Fn(HttpContext, Next) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = HttpResult> + Send,
+    {
+        self.use_named_middleware("custom", middleware)
+    }
+
+    /// Adds a middleware handler to the application request pipeline 
+    /// under the `name` that is reported in the startup summary
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn use_named_middleware<F, Fut>(&mut self, name: &'static str, middleware: F) -> &mut Self
     where
         F: Fn(HttpContext, Next) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = HttpResult> + Send,
@@ -118,17 +144,17 @@ impl App {
             Box::pin(async move { middleware(ctx, next).await })
         });
 
-        self.pipeline
-            .middlewares_mut()
-            .pipeline
-            .push(mw);
+        let middlewares = self.pipeline.middlewares_mut();
+        middlewares.pipeline.push(mw);
+        #[cfg(feature = "tracing")]
+        middlewares.names.push(name);
         self
     }
 
     /// Registers default middleware
     pub(super) fn use_endpoints(&mut self) {
         if self.pipeline.has_middleware_pipeline() {
-            self.use_middleware(|ctx, _| async move {
+            self.use_named_middleware("endpoints", |ctx, _| async move {
                 ctx.execute().await
             });
         }
@@ -0,0 +1,188 @@
+﻿//! Upload checksum middleware
+//!
+//! Middleware that verifies the SHA-256 checksum of HTTP request body
+
+use bytes::{Bytes, BytesMut};
+use http_body_util::BodyExt;
+use hyper::header::{HeaderName, HeaderValue, TRAILER};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    App,
+    error::Error,
+    middleware::HttpContext,
+    HttpRequest,
+    HttpBody
+};
+
+impl App {
+    /// Registers a middleware that verifies the hex-encoded SHA-256 checksum of request body,
+    /// e.g. `X-Content-SHA256: 7f83b165...`
+    /// 
+    /// The checksum is read from the request header with the specified `name`, or from the trailer
+    /// if the request announces it with the `Trailer` header. The digest is computed while reading 
+    /// the body, and requests with mismatched checksum are rejected with `400 Bad Request` 
+    /// before the request handler is called. Requests without the checksum are passed as is.
+    /// 
+    /// # Panics
+    /// If the `name` is not a valid header name
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    ///
+    /// let mut app = App::new();
+    /// app.verify_upload_checksum("x-content-sha256");
+    /// ```
+    pub fn verify_upload_checksum(&mut self, name: &str) -> &mut Self {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .unwrap_or_else(|_| panic!("invalid checksum header name: {name}"));
+        
+        self.use_named_middleware("checksum", move |ctx, next| {
+            let name = name.clone();
+            async move {
+                let headers = ctx.request.headers();
+                let expected = headers.get(&name).cloned();
+                if expected.is_none() && !has_trailer(headers.get_all(TRAILER).iter(), &name) {
+                    return next(ctx).await;
+                }
+                
+                let (req, handler, error_handler) = ctx.into_parts();
+                let req = verify(req, &name, expected).await?;
+                next(HttpContext::new(req, handler, error_handler)).await
+            }
+        })
+    }
+}
+
+#[cfg(feature = "di")]
+async fn verify(request: HttpRequest, name: &HeaderName, expected: Option<HeaderValue>) -> Result<HttpRequest, Error> {
+    let (parts, body, container) = request.into_parts();
+    let body = read_verified(body, name, expected).await?;
+    Ok(HttpRequest::from_parts(parts, HttpBody::full(body), container))
+}
+
+#[cfg(not(feature = "di"))]
+async fn verify(request: HttpRequest, name: &HeaderName, expected: Option<HeaderValue>) -> Result<HttpRequest, Error> {
+    let (parts, body) = request.into_parts();
+    let body = read_verified(body, name, expected).await?;
+    Ok(HttpRequest::from_parts(parts, HttpBody::full(body)))
+}
+
+/// Reads the `body` while computing its SHA-256 digest, and compares it with the `expected` checksum
+/// or the one from the trailer with the specified `name`
+async fn read_verified(mut body: HttpBody, name: &HeaderName, expected: Option<HeaderValue>) -> Result<Bytes, Error> {
+    let mut hasher = Sha256::new();
+    let mut buffer = BytesMut::new();
+    let mut expected = expected;
+    while let Some(frame) = body.frame().await {
+        let frame = match frame?.into_data() {
+            Ok(data) => {
+                hasher.update(&data);
+                buffer.extend_from_slice(&data);
+                continue;
+            }
+            Err(frame) => frame
+        };
+        if let Ok(mut trailers) = frame.into_trailers() {
+            expected = expected.or_else(|| trailers.remove(name));
+        }
+    }
+
+    let expected = expected.ok_or_else(ChecksumError::missing)?;
+    if !is_match(&hasher.finalize(), expected.as_bytes()) {
+        return Err(ChecksumError::mismatch());
+    }
+    Ok(buffer.freeze())
+}
+
+#[inline]
+fn has_trailer<'a>(mut trailers: impl Iterator<Item = &'a HeaderValue>, name: &HeaderName) -> bool {
+    trailers.any(|value| value
+        .to_str()
+        .is_ok_and(|value| value
+            .split(',')
+            .any(|trailer| trailer.trim().eq_ignore_ascii_case(name.as_str()))))
+}
+
+/// Compares the `digest` with the hex-encoded `checksum`
+#[inline]
+fn is_match(digest: &[u8], checksum: &[u8]) -> bool {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    checksum.len() == digest.len() * 2 && digest
+        .iter()
+        .flat_map(|byte| [HEX[(byte >> 4) as usize], HEX[(byte & 0x0f) as usize]])
+        .zip(checksum)
+        .all(|(expected, actual)| expected == actual.to_ascii_lowercase())
+}
+
+struct ChecksumError;
+
+impl ChecksumError {
+    #[inline]
+    fn missing() -> Error {
+        Error::client_error("Checksum error: checksum is missing")
+    }
+
+    #[inline]
+    fn mismatch() -> Error {
+        Error::client_error("Checksum error: checksum does not match the request body")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use http_body_util::StreamBody;
+    use hyper::{body::Frame, header::HeaderName, HeaderMap};
+    use crate::{error::Error, HttpBody};
+    use super::read_verified;
+
+    const NAME: HeaderName = HeaderName::from_static("x-content-sha256");
+    const CHECKSUM: &str = "7f83b1657ff1fc53b92dc18148a1d65dfc2d4b1fa3d677284addd200126d9069";
+
+    fn body_with_trailer(checksum: &'static str) -> HttpBody {
+        let mut trailers = HeaderMap::new();
+        trailers.insert(NAME, checksum.parse().unwrap());
+        let frames = stream::iter([
+            Ok::<_, Error>(Frame::data(bytes::Bytes::from_static(b"Hello "))),
+            Ok(Frame::data(bytes::Bytes::from_static(b"World!"))),
+            Ok(Frame::trailers(trailers))
+        ]);
+        HttpBody::boxed(StreamBody::new(frames))
+    }
+
+    #[tokio::test]
+    async fn it_verifies_checksum_from_header() {
+        let body = HttpBody::boxed(HttpBody::full("Hello World!"));
+
+        let body = read_verified(body, &NAME, Some(CHECKSUM.to_uppercase().parse().unwrap())).await.unwrap();
+
+        assert_eq!(body, "Hello World!");
+    }
+
+    #[tokio::test]
+    async fn it_verifies_checksum_from_trailer() {
+        let body = read_verified(body_with_trailer(CHECKSUM), &NAME, None).await.unwrap();
+
+        assert_eq!(body, "Hello World!");
+    }
+
+    #[tokio::test]
+    async fn it_rejects_mismatched_checksum_from_trailer() {
+        let checksum = "0000000000000000000000000000000000000000000000000000000000000000";
+
+        let err = read_verified(body_with_trailer(checksum), &NAME, None).await.unwrap_err();
+
+        assert!(err.is_client_error());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_missing_checksum() {
+        let body = HttpBody::boxed(HttpBody::full("Hello World!"));
+
+        let err = read_verified(body, &NAME, None).await.unwrap_err();
+
+        assert!(err.is_client_error());
+    }
+}
@@ -3,29 +3,27 @@
 //! Middleware that compress HTTP response body
 
 use std::{
-    collections::HashSet,
     cmp::Ordering,
     str::FromStr,
 };
 
 #[cfg(feature = "compression-brotli")]
-use async_compression::tokio::bufread::BrotliEncoder;
+use async_compression::tokio::write::BrotliEncoder;
 
 #[cfg(feature = "compression-gzip")]
-use async_compression::tokio::bufread::{ZlibEncoder, GzipEncoder};
+use async_compression::tokio::write::{ZlibEncoder, GzipEncoder};
 
 #[cfg(feature = "compression-zstd")]
-use async_compression::tokio::bufread::ZstdEncoder;
+use async_compression::tokio::write::ZstdEncoder;
 
 use async_compression::Level;
-use futures_util::TryStreamExt;
+use bytes::Bytes;
+use futures_util::stream::try_unfold;
 use http_body_util::{BodyExt, StreamBody};
 use hyper::body::Frame;
 use hyper::http::response::Parts;
-use tokio_util::io::{
-    ReaderStream, 
-    StreamReader
-};
+use mime::TEXT_EVENT_STREAM;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
     App,
@@ -33,10 +31,12 @@ use crate::{
     headers::{
         AcceptEncoding,
         Header,
+        HeaderMap,
+        HeaderValue,
         Encoding,
-        Quality,
+        quality::Ranked,
         ACCEPT_ENCODING, ACCEPT_RANGES,
-        CONTENT_ENCODING, CONTENT_LENGTH,
+        CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE,
         VARY
     },
     HttpResponse,
@@ -57,41 +57,222 @@ static SUPPORTED_ENCODINGS: &[Encoding] = &[
     Encoding::Zstd,
 ];
 
+/// Describes an encoder that writes compressed data into an in-memory buffer
+trait StreamEncoder: AsyncWrite + Unpin + Send + Sync + 'static {
+    /// Takes the compressed data produced so far out of the buffer
+    fn take(&mut self) -> Bytes;
+}
+
 macro_rules! impl_compressor {
-    ($algo:ident, $encoder:ident, $level:expr) => {
+    ($algo:ident, $encoder:ident) => {
+        impl StreamEncoder for $encoder<Vec<u8>> {
+            #[inline]
+            fn take(&mut self) -> Bytes {
+                std::mem::take(self.get_mut()).into()
+            }
+        }
+        
         #[inline]
-        fn $algo(body: HttpBody) -> HttpBody {
-            let stream_reader = StreamReader::new(body.into_data_stream());
-            let encoder = $encoder::with_quality(stream_reader, $level);
-            let compressed_body = ReaderStream::new(encoder);
-            HttpBody::boxed(StreamBody::new(compressed_body
-                .map_err(Error::server_error)
-                .map_ok(Frame::data)))
+        fn $algo(body: HttpBody, level: Level) -> HttpBody {
+            compress_stream(body, $encoder::with_quality(Vec::new(), level))
         }
     };
 }
 
+/// Number of uncompressed bytes after which the encoder is flushed, 
+/// so a long stream is sent to the client in parts rather than once the encoder is finished
+const FLUSH_THRESHOLD: usize = 64 * 1024;
+
+/// Describes the state of the compressed stream
+enum CompressState<E> {
+    /// Reading the body and writing it into the encoder, 
+    /// holds the number of bytes written since the last flush
+    Streaming(HttpBody, E, usize),
+    /// The body has been compressed, holds its trailers to be sent after the compressed data
+    Trailers(HeaderMap),
+    /// The stream has ended
+    Done
+}
+
+/// Compresses the body chunk by chunk, the encoder is flushed once 
+/// [`FLUSH_THRESHOLD`] bytes are written since the last flush and when the body ends.
+/// The trailers of the body are passed through as is.
+fn compress_stream<E: StreamEncoder>(body: HttpBody, encoder: E) -> HttpBody {
+    let stream = try_unfold(CompressState::Streaming(body, encoder, 0), |state| async move {
+        let (mut body, mut encoder, mut pending) = match state {
+            CompressState::Streaming(body, encoder, pending) => (body, encoder, pending),
+            CompressState::Trailers(trailers) => return Ok(Some((Frame::trailers(trailers), CompressState::Done))),
+            CompressState::Done => return Ok(None)
+        };
+        loop {
+            let frame = match body.frame().await.transpose()? {
+                Some(frame) => frame,
+                None => {
+                    encoder.shutdown().await?;
+                    return Ok(Some((Frame::data(encoder.take()), CompressState::Done)));
+                }
+            };
+            let data = match frame.into_data() {
+                Ok(data) => data,
+                Err(frame) => match frame.into_trailers() {
+                    Ok(trailers) => {
+                        encoder.shutdown().await?;
+                        return Ok(Some((Frame::data(encoder.take()), CompressState::Trailers(trailers))));
+                    },
+                    Err(_) => continue
+                }
+            };
+            encoder.write_all(&data).await?;
+            pending += data.len();
+            if pending >= FLUSH_THRESHOLD {
+                encoder.flush().await?;
+                pending = 0;
+            }
+            let compressed = encoder.take();
+            if !compressed.is_empty() {
+                return Ok::<_, Error>(Some((Frame::data(compressed), CompressState::Streaming(body, encoder, pending))));
+            }
+        }
+    });
+    HttpBody::boxed(StreamBody::new(stream))
+}
+
 #[cfg(feature = "compression-gzip")]
-impl_compressor!(gzip, GzipEncoder, Level::Default);
+impl_compressor!(gzip, GzipEncoder);
 
 #[cfg(feature = "compression-gzip")]
-impl_compressor!(deflate, ZlibEncoder, Level::Default);
+impl_compressor!(deflate, ZlibEncoder);
 
 #[cfg(feature = "compression-brotli")]
-impl_compressor!(brotli, BrotliEncoder, Level::Precise(4));
+impl_compressor!(brotli, BrotliEncoder);
 
 #[cfg(feature = "compression-zstd")]
-impl_compressor!(zstd, ZstdEncoder, Level::Default);
+impl_compressor!(zstd, ZstdEncoder);
+
+/// Represents a compression configuration
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionConfig {
+    /// Compression level of the `gzip` and `deflate` encoders
+    /// 
+    /// Default: `6`
+    #[cfg(feature = "compression-gzip")]
+    gzip_level: Level,
+
+    /// Compression quality of the `brotli` encoder
+    /// 
+    /// Default: `4`
+    #[cfg(feature = "compression-brotli")]
+    brotli_quality: Level,
+
+    /// Compression level of the `zstd` encoder
+    /// 
+    /// Default: `3`
+    #[cfg(feature = "compression-zstd")]
+    zstd_level: Level,
+}
+
+impl Default for CompressionConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "compression-gzip")]
+            gzip_level: Level::Default,
+            #[cfg(feature = "compression-brotli")]
+            brotli_quality: Level::Precise(4),
+            #[cfg(feature = "compression-zstd")]
+            zstd_level: Level::Default,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Creates a default compression configuration
+    /// 
+    /// Defaults:
+    /// - gzip/deflate level: `6`
+    /// - brotli quality: `4`
+    /// - zstd level: `3`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression level of the `gzip` and `deflate` encoders, 
+    /// from `0` (no compression, fastest) to `9` (best compression, slowest)
+    /// 
+    /// Default: `6`
+    /// 
+    /// # Panics
+    /// If the `level` is out of the `0..=9` range
+    #[cfg(feature = "compression-gzip")]
+    pub fn with_gzip_level(mut self, level: u32) -> Self {
+        assert!(level <= 9, "gzip compression level must be in range 0..=9, got: {level}");
+        self.gzip_level = Level::Precise(level as i32);
+        self
+    }
+
+    /// Sets the compression quality of the `brotli` encoder, 
+    /// from `0` (fastest) to `11` (best compression, slowest)
+    /// 
+    /// Default: `4`
+    /// 
+    /// # Panics
+    /// If the `quality` is out of the `0..=11` range
+    #[cfg(feature = "compression-brotli")]
+    pub fn with_brotli_quality(mut self, quality: u32) -> Self {
+        assert!(quality <= 11, "brotli compression quality must be in range 0..=11, got: {quality}");
+        self.brotli_quality = Level::Precise(quality as i32);
+        self
+    }
+
+    /// Sets the compression level of the `zstd` encoder, 
+    /// from `1` (fastest) to `22` (best compression, slowest)
+    /// 
+    /// Default: `3`
+    /// 
+    /// # Panics
+    /// If the `level` is out of the `1..=22` range
+    #[cfg(feature = "compression-zstd")]
+    pub fn with_zstd_level(mut self, level: i32) -> Self {
+        assert!((1..=22).contains(&level), "zstd compression level must be in range 1..=22, got: {level}");
+        self.zstd_level = Level::Precise(level);
+        self
+    }
+}
 
 impl App {
+    /// Configures the compression middleware with specific compression levels
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::{App, middleware::compress::CompressionConfig};
+    ///
+    /// let mut app = App::new().with_compression(CompressionConfig::new()
+    ///     .with_gzip_level(4)
+    ///     .with_brotli_quality(2));
+    /// 
+    /// app.use_compression();
+    /// ```
+    pub fn with_compression(mut self, config: CompressionConfig) -> Self {
+        self.compression_config = config;
+        self
+    }
+
     /// Registers a middleware that applies a default compression algorithm
+    /// 
+    /// The middleware uses the configuration set with [`App::with_compression`], 
+    /// regardless of whether it is set before or after the middleware is registered.
     pub fn use_compression(&mut self) -> &mut Self {
-        self.use_middleware(|ctx, next| async move {
+        self.use_named_middleware("compression", |ctx, next| async move {
+            let config = ctx.request
+                .extensions()
+                .get::<CompressionConfig>()
+                .copied()
+                .unwrap_or_default();
             let accept_encoding = ctx.extract::<Header<AcceptEncoding>>();
             let http_result = next(ctx).await;
             
             if let Ok(accept_encoding) = accept_encoding { 
-                Self::negotiate(accept_encoding, http_result)
+                Self::negotiate(&config, accept_encoding, http_result)
             } else { 
                 http_result
             }  
@@ -99,76 +280,99 @@ impl App {
         self
     }
     
-    fn negotiate(accept_encoding: Header<AcceptEncoding>, http_result: HttpResult) -> HttpResult {
+    fn negotiate(
+        config: &CompressionConfig, 
+        accept_encoding: Header<AcceptEncoding>, 
+        http_result: HttpResult
+    ) -> HttpResult {
         let accept_encoding = accept_encoding.into_inner();
         if  accept_encoding.is_empty() {
             return http_result;
         }
         
-        let mut encodings_with_weights = vec![];
-        if let Ok(header_value) = accept_encoding.to_str() {
-            for part in header_value.split(',') {
-                if let Ok(quality) = Quality::<Encoding>::from_str(part.trim()) {
-                    encodings_with_weights.push(quality);
-                }
-            }
-            encodings_with_weights
-                .sort_by(|a, b| b.value
-                    .partial_cmp(&a.value)
-                    .unwrap_or(Ordering::Equal)
-                );
-        }
-        
-        if !encodings_with_weights.is_empty() && encodings_with_weights[0].item.is_any() {
-            #[cfg(feature = "compression-brotli")]
-            return Self::compress(Encoding::Brotli, http_result);
-            
-            #[cfg(all(
-                feature = "compression-gzip", 
-                not(feature = "compression-brotli"
-            )))]
-            return Self::compress(Encoding::Gzip, http_result);
-            
-            #[cfg(all(
-                feature = "compression-zstd", 
-                not(feature = "compression-brotli"), 
-                not(feature = "compression-gzip"
-            )))]
-            return Self::compress(Encoding::Gzip, http_result);
-            
-            #[cfg(all(
-                not(feature = "compression-brotli"), 
-                not(feature = "compression-gzip"), 
-                not(feature = "compression-zstd"), 
-                not(feature = "compression-full"
-            )))]
+        let Ok(header_value) = accept_encoding.to_str() else { 
             return http_result;
+        };
+        
+        match Self::select_encoding(header_value) {
+            Some(encoding) => Self::compress(config, encoding, http_result),
+            None => status!(406, [
+                (VARY, ACCEPT_ENCODING),
+                (ACCEPT_ENCODING, Encoding::stringify(SUPPORTED_ENCODINGS))
+            ])
         }
+    }
 
-        let supported = SUPPORTED_ENCODINGS
+    /// Picks the supported encoding with the highest q-value, preferring the better-ranked one on ties.
+    /// 
+    /// Unknown and malformed entries are ignored and `*` covers every encoding that is not listed explicitly. 
+    /// Returns `None` if no supported encoding is acceptable, including `identity`.
+    fn select_encoding(header_value: &str) -> Option<Encoding> {
+        let preferences = header_value
+            .split(',')
+            .filter_map(Self::parse_preference)
+            .collect::<Vec<_>>();
+        
+        let explicit = |encoding: Encoding| preferences
             .iter()
-            .collect::<HashSet<_>>();
+            .find(|(item, _)| *item == encoding)
+            .map(|(_, q)| *q);
+        let any = explicit(Encoding::Any);
         
-        for encoding in encodings_with_weights {
-            if supported.contains(&encoding.item) { 
-                return Self::compress(encoding.item, http_result);
-            }
+        let compressed = SUPPORTED_ENCODINGS
+            .iter()
+            .filter(|encoding| **encoding != Encoding::Identity)
+            .filter_map(|encoding| explicit(*encoding)
+                .or(any)
+                .filter(|q| *q > 0.0)
+                .map(|q| (*encoding, q)))
+            .max_by(|(a, a_q), (b, b_q)| a_q
+                .partial_cmp(b_q)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.rank().cmp(&b.rank())));
+        
+        // Identity is always acceptable unless it is explicitly refused with "identity;q=0" or "*;q=0"
+        let identity = explicit(Encoding::Identity).or(any).unwrap_or(f32::MIN_POSITIVE);
+        match compressed {
+            Some((_, q)) if identity > q => Some(Encoding::Identity),
+            Some((encoding, _)) => Some(encoding),
+            None if identity > 0.0 => Some(Encoding::Identity),
+            None => None
         }
+    }
 
-        status!(406, [
-            (VARY, ACCEPT_ENCODING),
-            (ACCEPT_ENCODING, Encoding::stringify(SUPPORTED_ENCODINGS))
-        ])
+    /// Parses a single `Accept-Encoding` entry, e.g. `gzip;q=0.5`, into an encoding and its q-value
+    fn parse_preference(part: &str) -> Option<(Encoding, f32)> {
+        let mut params = part.split(';').map(str::trim);
+        let encoding = params.next()
+            .map(str::to_ascii_lowercase)
+            .and_then(|encoding| Encoding::from_str(&encoding).ok())?;
+        
+        let mut q = 1.0;
+        for param in params {
+            if let Some(value) = param.strip_prefix("q=").or_else(|| param.strip_prefix("Q=")) {
+                q = value.parse::<f32>().ok().filter(|q| (0.0..=1.0).contains(q))?;
+            }
+        }
+        Some((encoding, q))
     }
     
-    fn compress(encoding: Encoding, http_result: HttpResult) -> HttpResult {
-        if let Ok(response) = http_result {
+    fn compress(config: &CompressionConfig, encoding: Encoding, http_result: HttpResult) -> HttpResult {
+        if let Ok(mut response) = http_result {
+            // The response depends on Accept-Encoding even if it's sent as is, 
+            // so shared caches must not serve it for other encodings
+            Self::merge_vary(response.headers_mut(), ACCEPT_ENCODING.as_str());
+            
+            // Server-Sent Events are latency-sensitive and already small, so leave them as they are
+            if Self::is_event_stream(&response) { 
+                return Ok(response);
+            }
+            
             let (mut parts, body) = response.into_parts();
             parts.headers.remove(CONTENT_LENGTH);
             parts.headers.remove(ACCEPT_RANGES);
-            parts.headers.append(VARY, ACCEPT_ENCODING.into());
             
-            let body = Self::compress_body(&mut parts, encoding, body);
+            let body = Self::compress_body(config, &mut parts, encoding, body);
             
             Ok(HttpResponse::from_parts(parts, body))
         } else { 
@@ -176,27 +380,61 @@ impl App {
         }
     }
 
-    fn compress_body(parts: &mut Parts, encoding: Encoding, body: HttpBody) -> HttpBody {
+    #[inline]
+    fn is_event_stream(response: &HttpResponse) -> bool {
+        response.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with(TEXT_EVENT_STREAM.essence_str()))
+    }
+
+    /// Adds the `value` to the `Vary` header, merging it with the values that are already there
+    fn merge_vary(headers: &mut HeaderMap, value: &str) {
+        let mut values = headers
+            .get_all(VARY)
+            .iter()
+            .filter_map(|vary| vary.to_str().ok())
+            .flat_map(|vary| vary.split(','))
+            .map(str::trim)
+            .filter(|vary| !vary.is_empty())
+            .collect::<Vec<_>>();
+        
+        if values.iter().any(|vary| *vary == "*" || vary.eq_ignore_ascii_case(value)) { 
+            return;
+        }
+        
+        values.push(value);
+        if let Ok(vary) = HeaderValue::from_str(&values.join(", ")) { 
+            headers.insert(VARY, vary);
+        }
+    }
+
+    fn compress_body(
+        config: &CompressionConfig, 
+        parts: &mut Parts, 
+        encoding: Encoding, 
+        body: HttpBody
+    ) -> HttpBody {
         match encoding {
             #[cfg(feature = "compression-brotli")]
             Encoding::Brotli => {
                 parts.headers.append(CONTENT_ENCODING, Encoding::Brotli.into());
-                brotli(body)
+                brotli(body, config.brotli_quality)
             },
             #[cfg(feature = "compression-gzip")]
             Encoding::Gzip => {
                 parts.headers.append(CONTENT_ENCODING, Encoding::Gzip.into());
-                gzip(body)
+                gzip(body, config.gzip_level)
             },
             #[cfg(feature = "compression-gzip")]
             Encoding::Deflate => {
                 parts.headers.append(CONTENT_ENCODING, Encoding::Deflate.into());
-                deflate(body)
+                deflate(body, config.gzip_level)
             },
             #[cfg(feature = "compression-zstd")]
             Encoding::Zstd => {
                 parts.headers.append(CONTENT_ENCODING, Encoding::Zstd.into());
-                zstd(body)
+                zstd(body, config.zstd_level)
             },
             _ => body
         }
@@ -216,7 +454,7 @@ mod tests {
         use async_compression::tokio::write::BrotliDecoder;
         
         let body = HttpBody::json(json!({ "age": 33, "name": "John" }));
-        let body = brotli(body);
+        let body = brotli(body, Level::Default);
 
         let mut decoder = BrotliDecoder::new(Vec::new());
         decoder.write_all(&body.collect().await.unwrap().to_bytes()).await.unwrap();
@@ -232,7 +470,7 @@ mod tests {
         use async_compression::tokio::write::GzipDecoder;
 
         let body = HttpBody::json(json!({ "age": 33, "name": "John" }));
-        let body = gzip(body);
+        let body = gzip(body, Level::Default);
 
         let mut decoder = GzipDecoder::new(Vec::new());
         decoder.write_all(&body.collect().await.unwrap().to_bytes()).await.unwrap();
@@ -248,7 +486,7 @@ mod tests {
         use async_compression::tokio::write::ZlibDecoder;
 
         let body = HttpBody::json(json!({ "age": 33, "name": "John" }));
-        let body = deflate(body);
+        let body = deflate(body, Level::Default);
 
         let mut decoder = ZlibDecoder::new(Vec::new());
         decoder.write_all(&body.collect().await.unwrap().to_bytes()).await.unwrap();
@@ -264,7 +502,7 @@ mod tests {
         use async_compression::tokio::write::ZstdDecoder;
 
         let body = HttpBody::json(json!({ "age": 33, "name": "John" }));
-        let body = zstd(body);
+        let body = zstd(body, Level::Default);
 
         let mut decoder = ZstdDecoder::new(Vec::new());
         decoder.write_all(&body.collect().await.unwrap().to_bytes()).await.unwrap();
@@ -273,4 +511,223 @@ mod tests {
 
         assert_eq!(body, b"{\"age\":33,\"name\":\"John\"}".to_vec());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn it_adds_vary_header() {
+        let mut headers = HeaderMap::new();
+        
+        App::merge_vary(&mut headers, "accept-encoding");
+        
+        assert_eq!(headers.get(VARY).unwrap(), "accept-encoding");
+    }
+
+    #[test]
+    fn it_merges_vary_header_with_existing_values() {
+        let mut headers = HeaderMap::new();
+        headers.append(VARY, HeaderValue::from_static("origin"));
+        headers.append(VARY, HeaderValue::from_static("accept, cookie"));
+
+        App::merge_vary(&mut headers, "accept-encoding");
+
+        assert_eq!(headers.get_all(VARY).iter().count(), 1);
+        assert_eq!(headers.get(VARY).unwrap(), "origin, accept, cookie, accept-encoding");
+    }
+
+    #[test]
+    fn it_does_not_duplicate_vary_header_value() {
+        let mut headers = HeaderMap::new();
+        headers.insert(VARY, HeaderValue::from_static("Accept-Encoding, origin"));
+
+        App::merge_vary(&mut headers, "accept-encoding");
+
+        assert_eq!(headers.get(VARY).unwrap(), "Accept-Encoding, origin");
+    }
+
+    #[test]
+    fn it_keeps_wildcard_vary_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(VARY, HeaderValue::from_static("*"));
+
+        App::merge_vary(&mut headers, "accept-encoding");
+
+        assert_eq!(headers.get(VARY).unwrap(), "*");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "compression-gzip")]
+    async fn it_compresses_streamed_body() {
+        use async_compression::tokio::write::GzipDecoder;
+        use futures_util::stream;
+
+        let chunks = (0..100)
+            .map(|i| Ok::<_, Error>(Frame::data(Bytes::from(format!("chunk #{i};").repeat(100)))))
+            .collect::<Vec<_>>();
+        let body = HttpBody::boxed(StreamBody::new(stream::iter(chunks)));
+        let mut body = gzip(body, Level::Default);
+
+        let mut frames = 0;
+        let mut decoder = GzipDecoder::new(Vec::new());
+        while let Some(frame) = body.frame().await {
+            let data = frame.unwrap().into_data().unwrap();
+            decoder.write_all(&data).await.unwrap();
+            frames += 1;
+        }
+        decoder.shutdown().await.unwrap();
+        let decompressed = decoder.into_inner();
+
+        let expected = (0..100)
+            .map(|i| format!("chunk #{i};").repeat(100))
+            .collect::<String>();
+        
+        assert!(frames < 100);
+        assert_eq!(String::from_utf8(decompressed).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "compression-gzip")]
+    async fn it_flushes_compressed_chunk_over_threshold_before_stream_ends() {
+        use async_compression::tokio::write::GzipDecoder;
+        use futures_util::stream;
+        use tokio::sync::mpsc;
+
+        let (tx, rx) = mpsc::channel::<Result<Frame<Bytes>, Error>>(1);
+        let chunks = stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|chunk| (chunk, rx))
+        });
+        let body = HttpBody::boxed(StreamBody::new(chunks));
+        let mut body = gzip(body, Level::Default);
+        
+        let chunk = "a".repeat(FLUSH_THRESHOLD);
+        tx.send(Ok(Frame::data(Bytes::from(chunk.clone())))).await.unwrap();
+        
+        let mut decoder = GzipDecoder::new(Vec::new());
+        while decoder.get_ref().len() < chunk.len() {
+            let data = body.frame().await.unwrap().unwrap().into_data().unwrap();
+            decoder.write_all(&data).await.unwrap();
+            decoder.flush().await.unwrap();
+        }
+        
+        assert_eq!(decoder.get_ref().as_slice(), chunk.as_bytes());
+        
+        drop(tx);
+        
+        assert!(body.frame().await.is_some());
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "compression-gzip")]
+    async fn it_passes_trailers_through() {
+        use async_compression::tokio::write::GzipDecoder;
+        use futures_util::stream;
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+        let frames = vec![
+            Ok::<_, Error>(Frame::data(Bytes::from_static(b"first chunk;"))),
+            Ok(Frame::data(Bytes::from_static(b"second chunk"))),
+            Ok(Frame::trailers(trailers))
+        ];
+        let body = HttpBody::boxed(StreamBody::new(stream::iter(frames)));
+        let mut body = gzip(body, Level::Default);
+
+        let mut decoder = GzipDecoder::new(Vec::new());
+        let mut received = None;
+        while let Some(frame) = body.frame().await {
+            match frame.unwrap().into_data() {
+                Ok(data) => decoder.write_all(&data).await.unwrap(),
+                Err(frame) => received = frame.into_trailers().ok()
+            }
+        }
+        decoder.shutdown().await.unwrap();
+
+        assert_eq!(decoder.into_inner(), b"first chunk;second chunk");
+        assert_eq!(received.unwrap().get("x-checksum").unwrap(), "abc");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "compression-gzip")]
+    async fn it_does_not_compress_event_stream() {
+        let response = crate::stream!(HttpBody::full("data: hello\n\n").into_boxed(), [
+            ("content-type", "text/event-stream")
+        ]);
+        
+        let response = App::compress(&CompressionConfig::default(), Encoding::Gzip, response).unwrap();
+        
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept-encoding");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "compression-gzip")]
+    async fn it_compresses_with_configured_level() {
+        use async_compression::tokio::write::GzipDecoder;
+
+        let words = ["lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit"];
+        let data = (0..20000usize)
+            .map(|i| words[(i * i + i / 7) % words.len()])
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let fast = CompressionConfig::new().with_gzip_level(1);
+        let best = CompressionConfig::new().with_gzip_level(9);
+        
+        let fast = gzip(HttpBody::full(data.clone()), fast.gzip_level)
+            .collect().await.unwrap().to_bytes();
+        let best = gzip(HttpBody::full(data.clone()), best.gzip_level)
+            .collect().await.unwrap().to_bytes();
+        
+        assert!(fast.len() > best.len());
+        
+        for compressed in [fast, best] {
+            let mut decoder = GzipDecoder::new(Vec::new());
+            decoder.write_all(&compressed).await.unwrap();
+            decoder.shutdown().await.unwrap();
+            
+            assert_eq!(String::from_utf8(decoder.into_inner()).unwrap(), data);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "compression-gzip")]
+    #[should_panic]
+    fn it_panics_if_gzip_level_is_out_of_range() {
+        _ = CompressionConfig::new().with_gzip_level(10);
+    }
+
+    #[test]
+    #[cfg(feature = "compression-brotli")]
+    #[should_panic]
+    fn it_panics_if_brotli_quality_is_out_of_range() {
+        _ = CompressionConfig::new().with_brotli_quality(12);
+    }
+
+    #[test]
+    #[cfg(feature = "compression-zstd")]
+    #[should_panic]
+    fn it_panics_if_zstd_level_is_out_of_range() {
+        _ = CompressionConfig::new().with_zstd_level(0);
+    }
+
+    #[test]
+    #[cfg(feature = "compression-gzip")]
+    fn it_refuses_when_identity_is_not_acceptable() {
+        assert_eq!(App::select_encoding("gzip;q=0, identity;q=0"), None);
+        assert_eq!(App::select_encoding("*;q=0"), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "compression-brotli", feature = "compression-gzip"))]
+    fn it_selects_encoding_with_highest_q_value() {
+        assert_eq!(App::select_encoding("br;q=1.0, gzip;q=0.5"), Some(Encoding::Brotli));
+        assert_eq!(App::select_encoding("br;q=0.5, gzip"), Some(Encoding::Gzip));
+        assert_eq!(App::select_encoding("identity, gzip;q=0.5"), Some(Encoding::Identity));
+    }
+
+    #[test]
+    #[cfg(feature = "compression-gzip")]
+    fn it_ignores_unknown_and_malformed_encodings() {
+        assert_eq!(App::select_encoding("unknown, gzip;q=abc"), Some(Encoding::Identity));
+        assert_eq!(App::select_encoding("unknown;q=1, GZIP;q=0.8"), Some(Encoding::Gzip));
+        assert_eq!(App::select_encoding("gzip;q"), Some(Encoding::Gzip));
+    }
+}
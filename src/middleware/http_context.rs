@@ -1,4 +1,5 @@
-﻿use crate::http::endpoints::{
+﻿use bytes::Bytes;
+use crate::http::endpoints::{
     handlers::RouteHandler,
     args::FromRequestRef
 };
@@ -78,12 +79,65 @@ impl HttpContext {
         self.request.resolve_ref::<T>().await
     }
     
+    /// Builds an absolute URL for the `path` using the scheme and host of the current request
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::middleware::HttpContext;
+    ///
+    /// # fn docs(ctx: HttpContext) {
+    /// let location = ctx.absolute_url("/users/1");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn absolute_url(&self, path: &str) -> String {
+        self.request.absolute_url(path)
+    }
+
+    /// Returns up to `n` first bytes of the request body without consuming them, 
+    /// so the request handler still receives the complete body
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::middleware::HttpContext;
+    ///
+    /// # async fn docs(mut ctx: HttpContext) -> Result<(), volga::error::Error> {
+    /// let head = ctx.peek_body(1).await?;
+    /// let is_json = head.first() == Some(&b'{');
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub async fn peek_body(&mut self, n: usize) -> Result<Bytes, Error> {
+        self.request.peek_body(n).await
+    }
+
     /// Inserts the [`Header<T>`] to HTTP request headers
     #[inline]
     pub fn insert_header<T: FromHeaders>(&mut self, header: Header<T>) {
         self.request.insert_header(header)
     }
 
+    /// Returns a reference to the metadata of type `T` attached to the matched route
+    ///
+    /// # Example
+    /// ```no_run
+    /// use volga::middleware::HttpContext;
+    ///
+    /// #[derive(Clone)]
+    /// struct AuthLevel(u8);
+    ///
+    /// # fn docs(ctx: HttpContext) {
+    /// let level = ctx.route_metadata::<AuthLevel>()
+    ///     .map(|level| level.0)
+    ///     .unwrap_or_default();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn route_metadata<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.request.route_metadata::<T>()
+    }
+
     /// Executes the request handler for current HTTP request
     #[inline]
     pub(crate) async fn execute(self) -> HttpResult {
@@ -0,0 +1,49 @@
+﻿//! Response time middleware
+//!
+//! Middleware that adds the request handling duration as a response header
+
+use futures_util::TryFutureExt;
+use hyper::header::HeaderName;
+use std::time::Instant;
+
+use crate::{App, headers::HeaderValue, error::handler::call_weak_err_handler};
+
+impl App {
+    /// Registers a middleware that adds the duration of request handling, in milliseconds, 
+    /// as a response header with the specified `name`, e.g. `X-Response-Time: 12ms`
+    /// 
+    /// The duration covers the request handler and all middlewares registered after this one.
+    /// Errors are turned into responses by the error handler first, so they get the header as well.
+    /// 
+    /// # Panics
+    /// If the `name` is not a valid header name
+    /// 
+    /// # Example
+    /// ```no_run
+    /// use volga::App;
+    ///
+    /// let mut app = App::new();
+    /// app.use_response_time_header("x-response-time");
+    /// ```
+    pub fn use_response_time_header(&mut self, name: &str) -> &mut Self {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .unwrap_or_else(|_| panic!("invalid response time header name: {name}"));
+        
+        self.use_named_middleware("response_time", move |ctx, next| {
+            let name = name.clone();
+            async move {
+                let start = Instant::now();
+                let uri = ctx.request.uri().clone();
+                let error_handler = ctx.error_handler.clone();
+                let mut response = next(ctx)
+                    .or_else(|err| async move { call_weak_err_handler(error_handler, &uri, err).await })
+                    .await?;
+                let elapsed = format!("{}ms", start.elapsed().as_millis());
+                if let Ok(elapsed) = HeaderValue::from_str(&elapsed) {
+                    response.headers_mut().insert(name, elapsed);
+                }
+                Ok(response)
+            }
+        })
+    }
+}
@@ -2,7 +2,11 @@
 
 use std::sync::Weak;
 use hyper::rt::{Read, Write};
-use crate::app::{AppInstance, scope::Scope};
+use hyper_util::server::graceful::Watcher;
+use crate::{
+    app::{AppInstance, scope::Scope},
+    http::endpoints::args::conn_info::ConnInfo
+};
 
 #[cfg(all(feature = "http1", not(feature = "http2")))]
 pub(super) mod http1;
@@ -11,6 +15,7 @@ pub(super) mod http1;
     all(feature = "http2", not(feature = "http1"))
 ))]
 pub(super) mod http2;
+pub(super) mod proxy_protocol;
 
 pub(super) struct Server<I: Read + Write + Unpin> {
     io: I
@@ -23,10 +28,11 @@ impl<I: Read + Write + Unpin + 'static> Server<I> {
     }
 
     #[inline]
-    pub(super) async fn serve(self, app_instance: Weak<AppInstance>) {
+    pub(super) async fn serve(self, app_instance: Weak<AppInstance>, conn_info: ConnInfo, watcher: Watcher) {
         if let Some(instance) = app_instance.upgrade() {
-            let scope = Scope::new(app_instance);
-            self.serve_core(scope, instance).await;
+            let cancellation_token = instance.shutdown_token.child_token();
+            let scope = Scope::new(app_instance, conn_info, cancellation_token);
+            self.serve_core(scope, instance, watcher).await;
         } else {
             #[cfg(feature = "tracing")]
             tracing::warn!("app instance could not be upgraded; aborting...");
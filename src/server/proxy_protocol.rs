@@ -0,0 +1,229 @@
+﻿//! PROXY protocol v1/v2 header parsing
+//!
+//! See the [specification](https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt) for details.
+
+use std::{
+    io::{Error, ErrorKind},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr}
+};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const V1_PREFIX: &[u8] = b"PROXY ";
+const V1_MAX_LEN: usize = 107;
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+const V2_VERSION: u8 = 0x2;
+const V2_LOCAL: u8 = 0x0;
+const V2_PROXY: u8 = 0x1;
+const V2_INET: u8 = 0x1;
+const V2_INET6: u8 = 0x2;
+
+/// Holds the original connection addresses passed by a proxy
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct ProxyHeader {
+    /// Address of the client
+    pub(crate) source: SocketAddr,
+    /// Address the client connected to
+    pub(crate) destination: SocketAddr
+}
+
+/// Reads the PROXY protocol header from the beginning of the stream, 
+/// leaving the rest of the stream untouched.
+/// 
+/// Returns `None` if the header doesn't carry the original addresses, 
+/// e.g. for `UNKNOWN` or `LOCAL` connections, and an error if the header is malformed.
+pub(crate) async fn read_header<R: AsyncRead + Unpin>(io: &mut R) -> Result<Option<ProxyHeader>, Error> {
+    // Both v1 header and v2 signature are at least 12 bytes long
+    let mut prefix = [0u8; 12];
+    io.read_exact(&mut prefix).await?;
+    
+    if &prefix == V2_SIGNATURE {
+        read_v2(io).await
+    } else if prefix.starts_with(V1_PREFIX) {
+        read_v1(io, prefix).await
+    } else {
+        Err(invalid("missing PROXY protocol header"))
+    }
+}
+
+async fn read_v1<R: AsyncRead + Unpin>(io: &mut R, prefix: [u8; 12]) -> Result<Option<ProxyHeader>, Error> {
+    let mut line = prefix.to_vec();
+    while !line.ends_with(b"\r\n") {
+        if line.len() >= V1_MAX_LEN {
+            return Err(invalid("PROXY protocol v1 header is too long"));
+        }
+        line.push(io.read_u8().await?);
+    }
+    
+    let line = std::str::from_utf8(&line[..line.len() - 2])
+        .map_err(|_| invalid("PROXY protocol v1 header is not valid ASCII"))?;
+    let mut parts = line.split(' ').skip(1);
+    
+    let is_v6 = match parts.next() {
+        Some("TCP4") => false,
+        Some("TCP6") => true,
+        Some("UNKNOWN") => return Ok(None),
+        _ => return Err(invalid("unsupported PROXY protocol v1 transport"))
+    };
+    
+    let (Some(src), Some(dst), Some(src_port), Some(dst_port), None) = 
+        (parts.next(), parts.next(), parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid("malformed PROXY protocol v1 header"));
+    };
+    
+    let parse_ip = |ip: &str| ip.parse::<IpAddr>()
+        .ok()
+        .filter(|ip| ip.is_ipv6() == is_v6)
+        .ok_or_else(|| invalid("invalid PROXY protocol v1 address"));
+    let parse_port = |port: &str| port.parse::<u16>()
+        .map_err(|_| invalid("invalid PROXY protocol v1 port"));
+    
+    Ok(Some(ProxyHeader {
+        source: SocketAddr::new(parse_ip(src)?, parse_port(src_port)?),
+        destination: SocketAddr::new(parse_ip(dst)?, parse_port(dst_port)?)
+    }))
+}
+
+async fn read_v2<R: AsyncRead + Unpin>(io: &mut R) -> Result<Option<ProxyHeader>, Error> {
+    let version_command = io.read_u8().await?;
+    let family = io.read_u8().await?;
+    let len = io.read_u16().await? as usize;
+    
+    let mut addresses = vec![0u8; len];
+    io.read_exact(&mut addresses).await?;
+    
+    if version_command >> 4 != V2_VERSION {
+        return Err(invalid("unsupported PROXY protocol version"));
+    }
+    match version_command & 0x0f {
+        V2_LOCAL => return Ok(None),
+        V2_PROXY => (),
+        _ => return Err(invalid("unsupported PROXY protocol v2 command"))
+    }
+    
+    let header = match family >> 4 {
+        V2_INET if len >= 12 => {
+            let ip = |i: usize| IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(&addresses[i..i + 4]).unwrap()));
+            let port = |i: usize| u16::from_be_bytes([addresses[i], addresses[i + 1]]);
+            ProxyHeader {
+                source: SocketAddr::new(ip(0), port(8)),
+                destination: SocketAddr::new(ip(4), port(10))
+            }
+        },
+        V2_INET6 if len >= 36 => {
+            let ip = |i: usize| IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(&addresses[i..i + 16]).unwrap()));
+            let port = |i: usize| u16::from_be_bytes([addresses[i], addresses[i + 1]]);
+            ProxyHeader {
+                source: SocketAddr::new(ip(0), port(32)),
+                destination: SocketAddr::new(ip(16), port(34))
+            }
+        },
+        V2_INET | V2_INET6 => return Err(invalid("malformed PROXY protocol v2 addresses")),
+        // UNSPEC and UNIX sockets carry no usable IP addresses
+        _ => return Ok(None)
+    };
+    Ok(Some(header))
+}
+
+#[inline]
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use tokio::io::AsyncReadExt;
+    use super::{read_header, ProxyHeader};
+
+    async fn read(data: &[u8]) -> (Result<Option<ProxyHeader>, std::io::Error>, Vec<u8>) {
+        let mut io = data;
+        let header = read_header(&mut io).await;
+        let mut rest = Vec::new();
+        io.read_to_end(&mut rest).await.unwrap();
+        (header, rest)
+    }
+
+    #[tokio::test]
+    async fn it_reads_v1_tcp4_header() {
+        let (header, rest) = read(b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\nGET / HTTP/1.1\r\n").await;
+
+        assert_eq!(header.unwrap(), Some(ProxyHeader {
+            source: "192.168.0.1:56324".parse::<SocketAddr>().unwrap(),
+            destination: "192.168.0.11:443".parse::<SocketAddr>().unwrap()
+        }));
+        assert_eq!(rest, b"GET / HTTP/1.1\r\n");
+    }
+
+    #[tokio::test]
+    async fn it_reads_v1_tcp6_header() {
+        let (header, _) = read(b"PROXY TCP6 2001:db8::1 2001:db8::2 56324 443\r\n").await;
+
+        assert_eq!(header.unwrap().unwrap().source, "[2001:db8::1]:56324".parse::<SocketAddr>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn it_reads_v1_unknown_header() {
+        let (header, rest) = read(b"PROXY UNKNOWN\r\nGET").await;
+
+        assert_eq!(header.unwrap(), None);
+        assert_eq!(rest, b"GET");
+    }
+
+    #[tokio::test]
+    async fn it_rejects_malformed_v1_header() {
+        let (header, _) = read(b"PROXY TCP4 192.168.0.1 2001:db8::2 56324 443\r\n").await;
+        assert!(header.is_err());
+
+        let (header, _) = read(b"PROXY TCP4 192.168.0.1 192.168.0.11 56324\r\n").await;
+        assert!(header.is_err());
+
+        let (header, _) = read(format!("PROXY TCP4 {}\r\n", "1".repeat(128)).as_bytes()).await;
+        assert!(header.is_err());
+    }
+
+    #[tokio::test]
+    async fn it_rejects_missing_header() {
+        let (header, _) = read(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+
+        assert!(header.is_err());
+    }
+
+    #[tokio::test]
+    async fn it_reads_v2_inet_header() {
+        let mut data = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+        data.extend_from_slice(&[0x21, 0x11, 0x00, 0x0c]);
+        data.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+        data.extend_from_slice(&56324u16.to_be_bytes());
+        data.extend_from_slice(&443u16.to_be_bytes());
+        data.extend_from_slice(b"GET");
+
+        let (header, rest) = read(&data).await;
+
+        assert_eq!(header.unwrap(), Some(ProxyHeader {
+            source: "10.0.0.1:56324".parse::<SocketAddr>().unwrap(),
+            destination: "10.0.0.2:443".parse::<SocketAddr>().unwrap()
+        }));
+        assert_eq!(rest, b"GET");
+    }
+
+    #[tokio::test]
+    async fn it_reads_v2_local_header() {
+        let mut data = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+        data.extend_from_slice(&[0x20, 0x00, 0x00, 0x00]);
+
+        let (header, _) = read(&data).await;
+
+        assert_eq!(header.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn it_rejects_unsupported_v2_version() {
+        let mut data = b"\r\n\r\n\0\r\nQUIT\n".to_vec();
+        data.extend_from_slice(&[0x31, 0x11, 0x00, 0x0c]);
+        data.extend_from_slice(&[0; 12]);
+
+        let (header, _) = read(&data).await;
+
+        assert!(header.is_err());
+    }
+}
@@ -11,7 +11,7 @@ use std::{
     net::SocketAddr, 
     path::{Path, PathBuf},
     sync::Arc,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use tokio::{
@@ -34,7 +34,10 @@ use tokio_rustls::{
     TlsAcceptor
 };
 
-use crate::tls::https_redirect::HttpsRedirectionMiddleware;
+use crate::tls::{
+    cert_expiry::CertExpiry,
+    https_redirect::HttpsRedirectionMiddleware
+};
 
 #[cfg(any(
     all(feature = "http1", feature = "http2"),
@@ -52,11 +55,13 @@ use hyper_util::rt::TokioExecutor;
 use hyper::server::conn::http1;
 
 pub(super) mod https_redirect;
+mod cert_expiry;
 
 const CERT_FILE_NAME: &str = "cert.pem";
 const KEY_FILE_NAME: &str = "key.pem";
 const DEFAULT_PORT: u16 = 7879;
 const DEFAULT_MAX_AGE: u64 = 30 * 24 * 60 * 60; // 30 days = 2,592,000 seconds
+const DEFAULT_CERT_EXPIRY_WARNING_DAYS: u64 = 30;
 
 /// Represents a TLS (Transport Layer Security) configuration options
 pub struct TlsConfig {
@@ -74,6 +79,18 @@ pub struct TlsConfig {
     
     /// Client Auth options
     client_auth: ClientAuth,
+    
+    /// Certificate expiry check options
+    cert_expiry_config: CertExpiryConfig,
+}
+
+/// Represents a certificate expiry check configuration options
+struct CertExpiryConfig {
+    /// Specifies how many days before the certificate expiry to start warning
+    warning_days: u64,
+    
+    /// Specifies whether to refuse to start with an expired certificate
+    reject_expired: bool
 }
 
 /// Represents an HTTPS redirection configuration options
@@ -139,6 +156,15 @@ impl Default for HstsConfig {
     }
 }
 
+impl Default for CertExpiryConfig {
+    fn default() -> Self {
+        Self {
+            warning_days: DEFAULT_CERT_EXPIRY_WARNING_DAYS,
+            reject_expired: false
+        }
+    }
+}
+
 impl Default for TlsConfig {
     fn default() -> Self {
         let path = std::env::current_dir().unwrap_or_default();
@@ -148,6 +174,7 @@ impl Default for TlsConfig {
             https_redirection_config: RedirectionConfig::default(),
             client_auth: ClientAuth::None,
             hsts_config: HstsConfig::default(),
+            cert_expiry_config: CertExpiryConfig::default(),
             key, 
             cert, 
         }
@@ -187,6 +214,7 @@ impl TlsConfig {
             https_redirection_config: RedirectionConfig::default(),
             client_auth: ClientAuth::None,
             hsts_config: HstsConfig::default(),
+            cert_expiry_config: CertExpiryConfig::default(),
             key, 
             cert, 
         }
@@ -200,6 +228,7 @@ impl TlsConfig {
             client_auth: ClientAuth::None,
             https_redirection_config: RedirectionConfig::default(),
             hsts_config: HstsConfig::default(),
+            cert_expiry_config: CertExpiryConfig::default(),
         }
     }
     
@@ -277,10 +306,30 @@ impl TlsConfig {
         self
     }
 
+    /// Configures how many days before the certificate expiry to start logging a warning at startup
+    /// 
+    /// Default: 30 days
+    pub fn with_cert_expiry_warning(mut self, days: u64) -> Self {
+        self.cert_expiry_config.warning_days = days;
+        self
+    }
+
+    /// Configures whether to refuse to start with an expired certificate
+    /// 
+    /// Default: `false`
+    pub fn with_expired_cert_rejection(mut self, reject: bool) -> Self {
+        self.cert_expiry_config.reject_expired = reject;
+        self
+    }
+
     pub(super) fn build(self) -> Result<ServerConfig, Error> {
         let certs = Self::load_cert_file(&self.cert)?;
         let key = Self::load_key_file(&self.key)?;
         
+        if let Some(cert) = certs.first() {
+            self.cert_expiry_config.check(cert)?;
+        }
+        
         let builder = match self.client_auth { 
             ClientAuth::None => ServerConfig::builder().with_no_client_auth(),
             ClientAuth::Optional(trust_anchor) => {
@@ -358,6 +407,34 @@ impl From<tokio_rustls::rustls::server::VerifierBuilderError> for Error {
     }
 }
 
+impl CertExpiryConfig {
+    /// Logs a warning if the certificate is expired or expires soon, 
+    /// returns [`Error`] if it's expired and such certificates are rejected
+    fn check(&self, cert: &[u8]) -> Result<(), Error> {
+        let threshold = Duration::from_secs(self.warning_days * 24 * 60 * 60);
+        match CertExpiry::check(cert, threshold, SystemTime::now()) {
+            Some(CertExpiry::Expired) if self.reject_expired => 
+                Err(Error::server_error("TLS config error: certificate has expired")),
+            Some(CertExpiry::Expired) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("TLS certificate has expired");
+                Ok(())
+            },
+            Some(CertExpiry::ExpiresSoon(_days)) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("TLS certificate expires in {_days} day(s)");
+                Ok(())
+            },
+            Some(CertExpiry::Valid) => Ok(()),
+            None => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("TLS certificate validity could not be parsed");
+                Ok(())
+            }
+        }
+    }
+}
+
 /// TLS specific impl for [`AppInstance`]
 impl AppInstance {
     #[inline]
@@ -394,7 +471,7 @@ impl App {
                 false
             };
             
-            self.use_middleware(move |ctx, next| {
+            self.use_named_middleware("hsts", move |ctx, next| {
                 let hsts_header = STRICT_TRANSPORT_SECURITY.clone();
                 let hsts_header_value = hsts_header_value.clone();
                 let is_excluded = is_excluded.clone();
@@ -426,6 +503,7 @@ impl App {
     pub(super) fn run_https_redirection_middleware(
         socket: SocketAddr, 
         http_port: u16,
+        shutdown_timeout: Duration,
         shutdown_tx: Arc<watch::Sender<()>>
     ) {
         tokio::spawn(async move {
@@ -444,7 +522,7 @@ impl App {
                     Self::serve_http_redirection(https_port, stream, &graceful_shutdown);
                 }
                 tokio::select! {
-                    _ = sleep(Duration::from_secs(super::app::GRACEFUL_SHUTDOWN_TIMEOUT)) => (),
+                    _ = sleep(shutdown_timeout) => (),
                     _ = graceful_shutdown.shutdown() => {
                         #[cfg(feature = "tracing")]
                         tracing::info!("shutting down HTTPS redirection...");
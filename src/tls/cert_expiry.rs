@@ -0,0 +1,102 @@
+﻿//! Certificate expiry checks
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use x509_parser::parse_x509_certificate;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Describes the validity state of a certificate
+#[derive(Debug, PartialEq)]
+pub(super) enum CertExpiry {
+    /// The certificate is valid for longer than the warning threshold
+    Valid,
+    /// The certificate expires within the warning threshold, holds the number of full days left
+    ExpiresSoon(u64),
+    /// The certificate has already expired
+    Expired
+}
+
+impl CertExpiry {
+    /// Checks the DER-encoded certificate validity against the `threshold` at the moment of `now`
+    ///
+    /// Returns `None` if the certificate validity could not be parsed
+    pub(super) fn check(cert: &[u8], threshold: Duration, now: SystemTime) -> Option<Self> {
+        let not_after = not_after(cert)?;
+        let expiry = match not_after.duration_since(now) {
+            Err(_) => Self::Expired,
+            Ok(left) if left <= threshold => Self::ExpiresSoon(left.as_secs() / SECS_PER_DAY),
+            Ok(_) => Self::Valid
+        };
+        Some(expiry)
+    }
+}
+
+/// Reads the `notAfter` field of the DER-encoded X.509 certificate
+pub(super) fn not_after(cert: &[u8]) -> Option<SystemTime> {
+    let (_, cert) = parse_x509_certificate(cert).ok()?;
+    let secs = u64::try_from(cert.validity().not_after.timestamp()).ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use rcgen::{date_time_ymd, CertificateParams, KeyPair};
+    use super::{not_after, CertExpiry, SECS_PER_DAY};
+
+    // notAfter=Jan 15 00:00:00 2030 GMT
+    const CERT_NOT_AFTER: u64 = 1894665600;
+
+    fn cert() -> Vec<u8> {
+        let mut params = CertificateParams::new(vec!["localhost".into()]).unwrap();
+        params.not_after = date_time_ymd(2030, 1, 15);
+        let key = KeyPair::generate().unwrap();
+        params.self_signed(&key).unwrap().der().to_vec()
+    }
+
+    #[test]
+    fn it_reads_not_after() {
+        let not_after = not_after(&cert()).unwrap();
+
+        assert_eq!(not_after, UNIX_EPOCH + Duration::from_secs(CERT_NOT_AFTER));
+    }
+
+    #[test]
+    fn it_detects_expired_cert() {
+        let now = UNIX_EPOCH + Duration::from_secs(CERT_NOT_AFTER + 1);
+        let threshold = Duration::from_secs(30 * SECS_PER_DAY);
+
+        assert_eq!(CertExpiry::check(&cert(), threshold, now), Some(CertExpiry::Expired));
+    }
+
+    #[test]
+    fn it_detects_cert_expiring_soon() {
+        let now = UNIX_EPOCH + Duration::from_secs(CERT_NOT_AFTER - 10 * SECS_PER_DAY);
+        let threshold = Duration::from_secs(30 * SECS_PER_DAY);
+
+        assert_eq!(CertExpiry::check(&cert(), threshold, now), Some(CertExpiry::ExpiresSoon(10)));
+    }
+
+    #[test]
+    fn it_detects_valid_cert() {
+        let now = UNIX_EPOCH + Duration::from_secs(CERT_NOT_AFTER - 60 * SECS_PER_DAY);
+        let threshold = Duration::from_secs(30 * SECS_PER_DAY);
+
+        assert_eq!(CertExpiry::check(&cert(), threshold, now), Some(CertExpiry::Valid));
+    }
+
+    #[test]
+    fn it_returns_none_for_malformed_cert() {
+        assert_eq!(not_after(&[0x30, 0x82, 0x01]), None);
+    }
+
+    #[test]
+    fn it_returns_none_for_non_ascii_time() {
+        let mut cert = cert();
+        let time = b"300115000000Z";
+        let pos = cert.windows(time.len()).position(|w| w == time).unwrap();
+        cert[pos..pos + 3].copy_from_slice("é0".as_bytes());
+
+        assert_eq!(not_after(&cert), None);
+    }
+}
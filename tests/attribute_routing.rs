@@ -0,0 +1,46 @@
+﻿use std::time::Duration;
+use volga::{App, HttpResult, Json, ok, register_handlers};
+use volga::routing::{get, post};
+
+/// Greets the user by name
+#[get("/hello/{name}")]
+async fn hello(name: String) -> HttpResult {
+    ok!("Hello {name}!")
+}
+
+#[post("/users")]
+async fn create_user(user: Json<serde_json::Value>) -> HttpResult {
+    ok!(user.into_inner())
+}
+
+#[tokio::test]
+async fn it_maps_attribute_routed_handlers() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7967");
+        register_handlers!(app, hello, create_user);
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let greeting = client
+        .get("http://127.0.0.1:7967/hello/John")
+        .send()
+        .await
+        .unwrap();
+    let user = client
+        .post("http://127.0.0.1:7967/users")
+        .json(&serde_json::json!({ "name": "John" }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(greeting.text().await.unwrap(), "\"Hello John!\"");
+    assert_eq!(user.text().await.unwrap(), "{\"name\":\"John\"}");
+}
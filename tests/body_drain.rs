@@ -0,0 +1,73 @@
+﻿#![cfg(all(feature = "http1", not(feature = "http2")))]
+
+use std::time::Duration;
+use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpStream};
+use volga::{App, ok, status};
+
+const BODY_SIZE: usize = 1024 * 1024;
+
+async fn send_early_rejected_upload(port: u16) -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let head = format!("POST /upload HTTP/1.1\r\nhost: localhost\r\ncontent-length: {BODY_SIZE}\r\n\r\n");
+    stream.write_all(head.as_bytes()).await.unwrap();
+
+    let (mut reader, mut writer) = stream.into_split();
+    let writer = tokio::spawn(async move {
+        let _ = writer.write_all(&vec![b'a'; BODY_SIZE]).await;
+        let _ = writer.write_all(b"GET /health HTTP/1.1\r\nhost: localhost\r\n\r\n").await;
+        writer
+    });
+
+    let mut response = Vec::new();
+    let mut buffer = [0; 1024];
+    let _ = tokio::time::timeout(Duration::from_secs(5), async {
+        while !String::from_utf8_lossy(&response).ends_with("\"healthy\"") {
+            match reader.read(&mut buffer).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => response.extend_from_slice(&buffer[..n])
+            }
+        }
+    }).await;
+
+    drop(writer.await.unwrap());
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[tokio::test]
+async fn it_drains_body_and_keeps_connection_alive() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7965")
+            .with_body_drain_limit(2 * BODY_SIZE);
+        app.map_post("/upload", || async { status!(403) });
+        app.map_get("/health", || async { ok!("healthy") });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = send_early_rejected_upload(7965).await;
+
+    assert!(response.starts_with("HTTP/1.1 403"));
+    assert!(response.contains("HTTP/1.1 200"));
+    assert!(response.ends_with("\"healthy\""));
+}
+
+#[tokio::test]
+async fn it_closes_connection_if_body_remainder_exceeds_drain_limit() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7966")
+            .with_body_drain_limit(1024);
+        app.map_post("/upload", || async { status!(403) });
+        app.map_get("/health", || async { ok!("healthy") });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let response = send_early_rejected_upload(7966).await;
+
+    assert!(response.starts_with("HTTP/1.1 403"));
+    assert!(!response.contains("HTTP/1.1 200"));
+}
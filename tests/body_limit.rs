@@ -0,0 +1,69 @@
+﻿use volga::{App, HttpRequest, Results};
+use http_body_util::BodyExt;
+
+#[tokio::test]
+async fn it_applies_route_body_limit() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7989")
+            .with_body_limit(1024);
+
+        app.map_post("/small", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            Results::text(&String::from_utf8_lossy(&body))
+        })
+        .body_limit(8);
+
+        app.map_post("/large", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            Results::text(&String::from_utf8_lossy(&body))
+        });
+
+        app.run().await
+    });
+
+    let (small, small_ok, large) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let small = client.post("http://127.0.0.1:7989/small").body("Hello, World!").send().await.unwrap();
+        let small_ok = client.post("http://127.0.0.1:7989/small").body("Hello").send().await.unwrap();
+        let large = client.post("http://127.0.0.1:7989/large").body("Hello, World!").send().await.unwrap();
+        (small, small_ok, large)
+    }).await.unwrap();
+
+    assert_eq!(small.status(), 413);
+    assert_eq!(small_ok.text().await.unwrap(), "Hello");
+    assert_eq!(large.text().await.unwrap(), "Hello, World!");
+}
+
+#[tokio::test]
+async fn it_disables_body_limit_for_route() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7990")
+            .with_body_limit(4);
+
+        app.map_post("/upload", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            Results::text(&body.len().to_string())
+        })
+        .no_body_limit();
+
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.post("http://127.0.0.1:7990/upload").body("Hello, World!").send().await
+    }).await.unwrap().unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "13");
+}
@@ -0,0 +1,43 @@
+﻿use volga::{App, ok};
+
+#[tokio::test]
+async fn it_redirects_to_canonical_host() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7979")
+            .with_canonical_host("example.com");
+        app.map_get("/x", || async { ok!() });
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge()
+    };
+    let client = client
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve("example.com", ([127, 0, 0, 1], 7979).into())
+        .resolve("www.example.com", ([127, 0, 0, 1], 7979).into())
+        .build()
+        .unwrap();
+
+    let response = client
+        .get("http://www.example.com:7979/x?page=2")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 301);
+    assert_eq!(response.headers().get("location").unwrap(), "http://example.com:7979/x?page=2");
+
+    let response = client
+        .get("http://example.com:7979/x")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+}
@@ -1,4 +1,7 @@
-﻿use volga::{App, ok};
+﻿use bytes::Bytes;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use volga::{App, ok, stream, middleware::compress::CompressionConfig};
 
 #[tokio::test]
 async fn it_returns_brotli_compressed() {
@@ -232,6 +235,168 @@ async fn it_returns_default_brotli_compressed() {
     assert_eq!(response.json::<Vec<serde_json::Value>>().await.unwrap(), get_test_data());
 }
 
+#[tokio::test]
+async fn it_merges_vary_header_of_compressed_response() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7933");
+        app.use_compression();
+        app.map_get("/compressed", || async {
+            let values= get_test_data();
+            ok!(values, [
+                ("vary", "origin")
+            ])
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7933/compressed")
+            .header("accept-encoding", "gzip")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    assert_eq!(response.headers().get_all("vary").iter().count(), 1);
+    assert_eq!(response.headers().get("vary").unwrap(), "origin, accept-encoding");
+    assert_eq!(response.json::<Vec<serde_json::Value>>().await.unwrap(), get_test_data());
+}
+
+#[tokio::test]
+async fn it_returns_not_acceptable_if_identity_is_refused() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7994");
+        app.use_compression();
+        app.map_get("/compressed", || async {
+            let values= get_test_data();
+            ok!(values)
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7994/compressed")
+            .header("accept-encoding", "gzip;q=0, identity;q=0")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    assert_eq!(response.status(), 406);
+    assert_eq!(response.headers().get("vary").unwrap(), "accept-encoding");
+}
+
+#[tokio::test]
+async fn it_returns_compressed_stream() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7940");
+        app.use_compression();
+        app.map_get("/stream", || async {
+            let chunks = (0..1000)
+                .map(|i| Ok::<_, volga::error::Error>(Frame::data(Bytes::from(format!("chunk #{i}\n")))));
+            let body = StreamBody::new(futures_util::stream::iter(chunks));
+            stream!(BodyExt::boxed(body))
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7940/stream")
+            .header("accept-encoding", "gzip")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    let expected = (0..1000)
+        .map(|i| format!("chunk #{i}\n"))
+        .collect::<String>();
+
+    assert_eq!(response.headers().get("vary").unwrap(), "accept-encoding");
+    assert_eq!(response.text().await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn it_returns_compressed_with_configured_level() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7944")
+            .with_compression(CompressionConfig::new()
+                .with_gzip_level(1)
+                .with_brotli_quality(11));
+        app.use_compression();
+        app.map_get("/compressed", || async {
+            let values= get_test_data();
+            ok!(values)
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7944/compressed")
+            .header("accept-encoding", "gzip")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    assert_eq!(response.headers().get("vary").unwrap(), "accept-encoding");
+    assert_eq!(response.json::<Vec<serde_json::Value>>().await.unwrap(), get_test_data());
+}
+
+#[tokio::test]
+async fn it_applies_compression_config_set_after_middleware() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7896");
+        app.use_compression();
+        let mut app = app.with_compression(CompressionConfig::new().with_gzip_level(0));
+        app.map_get("/compressed", || async {
+            let values= get_test_data();
+            ok!(values)
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().no_gzip().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().no_gzip().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7896/compressed")
+            .header("accept-encoding", "gzip")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    let json = serde_json::to_vec(&get_test_data()).unwrap();
+
+    assert_eq!(response.headers().get("content-encoding").unwrap(), "gzip");
+    // The level 0 stores the data uncompressed
+    assert!(response.bytes().await.unwrap().len() > json.len());
+}
+
 fn get_test_data() -> Vec<serde_json::Value> {
     let mut values: Vec<serde_json::Value> = Vec::new();
     for i in 0..10000 {
@@ -0,0 +1,65 @@
+﻿use std::time::Duration;
+use volga::{App, ok};
+
+#[tokio::test]
+async fn it_rejects_requests_over_concurrency_limit() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7970");
+        app.map_get("/slow", || async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            ok!()
+        })
+        .max_concurrency(1);
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let first = client.get("http://127.0.0.1:7970/slow").send();
+    let second = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        client.get("http://127.0.0.1:7970/slow").send().await
+    };
+    let (first, second) = tokio::join!(first, second);
+
+    assert_eq!(first.unwrap().status(), 200);
+    assert_eq!(second.unwrap().status(), 503);
+
+    let third = client.get("http://127.0.0.1:7970/slow").send().await.unwrap();
+
+    assert_eq!(third.status(), 200);
+}
+
+#[tokio::test]
+async fn it_waits_for_free_slot_within_timeout() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7971");
+        app.map_get("/slow", || async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            ok!()
+        })
+        .max_concurrency_with_timeout(1, Duration::from_secs(2));
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let first = client.get("http://127.0.0.1:7971/slow").send();
+    let second = client.get("http://127.0.0.1:7971/slow").send();
+    let (first, second) = tokio::join!(first, second);
+
+    assert_eq!(first.unwrap().status(), 200);
+    assert_eq!(second.unwrap().status(), 200);
+}
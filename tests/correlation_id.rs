@@ -0,0 +1,44 @@
+﻿use volga::{App, ok, http::CorrelationId};
+
+#[tokio::test]
+async fn it_exposes_correlation_id_to_handler() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7972")
+            .with_correlation_id("x-correlation-id");
+        app.map_get("/id", || async {
+            let id = CorrelationId::current()
+                .and_then(|id| id.as_str().map(str::to_owned))
+                .unwrap_or_else(|| "none".into());
+            ok!("{id}")
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7972/id")
+        .header("x-correlation-id", "123-321-456")
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "\"123-321-456\"");
+
+    let response = client
+        .get("http://127.0.0.1:7972/id")
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "\"none\"");
+}
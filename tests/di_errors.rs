@@ -0,0 +1,49 @@
+﻿use volga::{App, ok, di::Dc, error::{Error, ErrorFormat}};
+
+#[derive(Default, Clone)]
+struct UnregisteredService;
+
+#[tokio::test]
+async fn it_returns_server_error_with_unresolved_type_name() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7973")
+            .with_error_format(ErrorFormat::Json);
+        app.map_get("/service", |_service: Dc<UnregisteredService>| async { ok!() });
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7973/service")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 500);
+
+    let body = response.text().await.unwrap();
+
+    assert!(body.contains("service not registered"));
+    assert!(body.contains("UnregisteredService"));
+}
+
+#[tokio::test]
+async fn it_fails_to_start_if_async_singleton_initialization_fails() {
+    let mut app = App::new().bind("127.0.0.1:7974");
+    app.add_singleton_async(|_| async {
+        Err::<UnregisteredService, _>(Error::server_error("connection refused"))
+    });
+    app.map_get("/service", |_service: Dc<UnregisteredService>| async { ok!() });
+
+    let result = app.run().await;
+
+    assert!(result.is_err());
+}
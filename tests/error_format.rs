@@ -0,0 +1,133 @@
+﻿use volga::{App, Query, error::ErrorFormat};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Params {
+    #[allow(dead_code)]
+    id: u32
+}
+
+#[tokio::test]
+async fn it_returns_json_not_found_error() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7934")
+            .with_error_format(ErrorFormat::Json);
+        app.map_get("/test", || async {});
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7934/unknown").send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.text().await.unwrap(), "{\"error\":\"Not Found\",\"status\":404}");
+}
+
+#[tokio::test]
+async fn it_returns_json_method_not_allowed_error() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7935")
+            .with_error_format(ErrorFormat::Json);
+        app.map_get("/test", || async {});
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.post("http://127.0.0.1:7935/test").send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 405);
+    let allow = response.headers().get("allow").unwrap().to_str().unwrap();
+    assert!(allow.contains("GET") && allow.contains("HEAD"));
+    assert_eq!(response.text().await.unwrap(), "{\"error\":\"Method Not Allowed\",\"status\":405}");
+}
+
+#[tokio::test]
+async fn it_returns_json_bad_request_error() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7936")
+            .with_error_format(ErrorFormat::Json);
+        app.map_get("/test", |_params: Query<Params>| async {});
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7936/test?id=abc").send().await
+    }).await.unwrap().unwrap();
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    
+    assert_eq!(body["status"], 400);
+    assert!(body["error"].as_str().unwrap().starts_with("Query parsing error"));
+}
+
+#[tokio::test]
+async fn it_returns_plain_not_found_error_by_default() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7937");
+        app.map_get("/test", || async {});
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7937/unknown").send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.text().await.unwrap(), "");
+}
+
+#[tokio::test]
+#[cfg(feature = "problem-details")]
+async fn it_returns_problem_details_not_found_error() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7981")
+            .with_error_format(ErrorFormat::ProblemDetails);
+        app.map_get("/test", || async {});
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7981/unknown").send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/problem+json");
+
+    let body: serde_json::Value = response.json().await.unwrap();
+
+    assert_eq!(body["status"], 404);
+    assert_eq!(body["title"], "Not Found");
+    assert_eq!(body["instance"], "/unknown");
+}
@@ -0,0 +1,119 @@
+﻿use std::{
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    time::Duration
+};
+use tokio::sync::Notify;
+use volga::App;
+
+#[tokio::test]
+async fn it_drains_in_flight_requests_and_runs_shutdown_hook_once() {
+    let notify = Arc::new(Notify::new());
+    let hook_calls = Arc::new(AtomicUsize::new(0));
+    
+    let trigger = notify.clone();
+    let mut app = App::new()
+        .bind("127.0.0.1:7995")
+        .with_shutdown_signal(async move { trigger.notified().await })
+        .with_shutdown_timeout(Duration::from_secs(5));
+    
+    let calls = hook_calls.clone();
+    app.on_shutdown(move || async move {
+        calls.fetch_add(1, Ordering::SeqCst);
+    });
+    let started = Arc::new(Notify::new());
+    let handler_started = started.clone();
+    app.map_get("/slow", move || {
+        let started = handler_started.clone();
+        async move {
+            started.notify_one();
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            "done"
+        }
+    });
+    
+    let server = tokio::spawn(async {
+        app.run().await
+    });
+
+    let request = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7995/slow").send().await
+    });
+    
+    started.notified().await;
+    notify.notify_one();
+    
+    let response = request.await.unwrap().unwrap();
+    
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "done");
+    
+    let result = tokio::time::timeout(Duration::from_secs(5), server).await;
+    
+    assert!(result.unwrap().unwrap().is_ok());
+    assert_eq!(hook_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn it_closes_keep_alive_connections_while_draining() {
+    let notify = Arc::new(Notify::new());
+    let hook_calls = Arc::new(AtomicUsize::new(0));
+    
+    let trigger = notify.clone();
+    let mut app = App::new()
+        .bind("127.0.0.1:7999")
+        .with_shutdown_signal(async move { trigger.notified().await })
+        .with_shutdown_timeout(Duration::from_secs(5));
+    
+    let calls = hook_calls.clone();
+    app.on_shutdown(move || async move {
+        calls.fetch_add(1, Ordering::SeqCst);
+    });
+    let started = Arc::new(Notify::new());
+    let handler_started = started.clone();
+    app.map_get("/slow", move || {
+        let started = handler_started.clone();
+        async move {
+            started.notify_one();
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            "done"
+        }
+    });
+    
+    let server = tokio::spawn(async {
+        app.run().await
+    });
+
+    let requests = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let first = client.get("http://127.0.0.1:7999/slow").send().await.unwrap();
+        let connection = first.headers().get("connection").cloned();
+        let body = first.text().await.unwrap();
+        let second = client.get("http://127.0.0.1:7999/slow").send().await;
+        (connection, body, second)
+    });
+    
+    started.notified().await;
+    notify.notify_one();
+    
+    let (connection, body, second) = requests.await.unwrap();
+    
+    assert_eq!(body, "done");
+    if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        assert_eq!(connection.unwrap(), "close");
+    }
+    assert!(second.is_err());
+    
+    let result = tokio::time::timeout(Duration::from_secs(1), server).await;
+    
+    assert!(result.unwrap().unwrap().is_ok());
+    assert_eq!(hook_calls.load(Ordering::SeqCst), 1);
+}
@@ -77,4 +77,45 @@ async fn it_writes_headers() {
     assert!(response.status().is_success());
     assert_eq!(response.headers().get("x-api-key").unwrap(), "some-api-key");
     assert_eq!(response.text().await.unwrap(), "\"ok!\"");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn it_serves_range_only_if_validator_matches() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7948");
+        app.map_get("/file", |if_range: volga::IfRangeCondition| async move {
+            let content = "Hello, World!";
+            match if_range.range(Some("\"v1\""), None) {
+                Some("bytes=0-4") => volga::status!(206, &content[..5], [
+                    ("content-range", "bytes 0-4/13")
+                ]),
+                _ => ok!(content)
+            }
+        });
+        app.run().await
+    });
+
+    let (matching, stale) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let matching = client
+            .get("http://127.0.0.1:7948/file")
+            .header("range", "bytes=0-4")
+            .header("if-range", "\"v1\"")
+            .send().await.unwrap();
+        let stale = client
+            .get("http://127.0.0.1:7948/file")
+            .header("range", "bytes=0-4")
+            .header("if-range", "\"v0\"")
+            .send().await.unwrap();
+        (matching, stale)
+    }).await.unwrap();
+
+    assert_eq!(matching.status(), 206);
+    assert_eq!(matching.text().await.unwrap(), "\"Hello\"");
+    assert_eq!(stale.status(), 200);
+    assert_eq!(stale.text().await.unwrap(), "\"Hello, World!\"");
+}
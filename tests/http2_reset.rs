@@ -0,0 +1,66 @@
+﻿use std::time::Duration;
+use h2::{client::SendRequest, Reason};
+use hyper::{body::Bytes, Request};
+use tokio::{net::TcpStream, task::JoinHandle};
+use volga::{App, app::HttpConfig, ok};
+
+const RESETS: usize = 1000;
+
+async fn connect(port: u16, config: fn(HttpConfig) -> HttpConfig) -> (SendRequest<Bytes>, JoinHandle<Result<(), h2::Error>>) {
+    tokio::spawn(async move {
+        let mut app = App::new()
+            .bind(([127, 0, 0, 1], port))
+            .with_http_config(config);
+        app.map_get("/", || async { ok!() });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let tcp = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let (client, connection) = h2::client::handshake(tcp).await.unwrap();
+    (client, tokio::spawn(connection))
+}
+
+async fn send_rapid_resets(client: &SendRequest<Bytes>) {
+    for _ in 0..RESETS {
+        let request = Request::get("/").body(()).unwrap();
+        let sent = match client.clone().ready().await {
+            Ok(mut client) => client.send_request(request, true),
+            Err(err) => Err(err)
+        };
+        match sent {
+            Ok((_, mut stream)) => stream.send_reset(Reason::CANCEL),
+            Err(_) => break
+        }
+    }
+}
+
+#[tokio::test]
+async fn it_closes_connection_on_rapid_resets() {
+    let (client, connection) = connect(7968, |config| config.with_max_reset_streams(5)).await;
+
+    send_rapid_resets(&client).await;
+
+    let result = tokio::time::timeout(Duration::from_secs(5), connection)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn it_keeps_connection_if_resets_are_below_threshold() {
+    let (client, connection) = connect(7969, |config| config.with_max_reset_streams(2 * RESETS)).await;
+
+    send_rapid_resets(&client).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let request = Request::get("/").body(()).unwrap();
+    let (response, _) = client.ready().await.unwrap().send_request(request, true).unwrap();
+    let response = response.await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert!(!connection.is_finished());
+}
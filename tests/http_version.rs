@@ -0,0 +1,33 @@
+﻿use volga::{App, HttpVersion, ok};
+
+#[tokio::test]
+async fn it_reports_negotiated_http_version() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7996");
+        app.map_get("/version", |version: HttpVersion| async move {
+            ok!("{version}")
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client
+            .get("http://127.0.0.1:7996/version")
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    let expected = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Version::HTTP_11
+    } else {
+        reqwest::Version::HTTP_2
+    };
+
+    assert_eq!(response.version(), expected);
+    assert_eq!(response.json::<String>().await.unwrap(), format!("{expected:?}"));
+}
@@ -0,0 +1,58 @@
+﻿use volga::{App, HttpRequest};
+
+#[tokio::test]
+async fn it_round_trips_large_body_with_configured_io_buffer_size() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7945")
+            .with_io_buffer_size(16 * 1024)
+            .without_body_limit();
+        app.map_post("/echo", |req: HttpRequest| async move {
+            volga::stream!(req.into_boxed_body())
+        });
+        app.run().await
+    });
+
+    let body = "volga".repeat(1024 * 1024);
+    let response = tokio::spawn({
+        let body = body.clone();
+        async move {
+            let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+                reqwest::Client::builder().http1_only().build().unwrap()
+            } else {
+                reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+            };
+            client
+                .post("http://127.0.0.1:7945/echo")
+                .body(body)
+                .send()
+                .await.unwrap()
+        }
+    }).await.unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), body);
+}
+
+#[tokio::test]
+#[cfg(all(feature = "http1", not(feature = "http2")))]
+async fn it_rejects_headers_larger_than_io_buffer_size() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7946")
+            .with_io_buffer_size(8192);
+        app.map_get("/test", || async { "Pass!" });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = reqwest::Client::builder().http1_only().build().unwrap();
+        client
+            .get("http://127.0.0.1:7946/test")
+            .header("x-large", "a".repeat(16 * 1024))
+            .send()
+            .await.unwrap()
+    }).await.unwrap();
+
+    assert_eq!(response.status(), 431);
+}
@@ -1,5 +1,5 @@
 ﻿use serde::{Deserialize, Serialize};
-use volga::{App, ok, Results, Json};
+use volga::{App, HttpRequest, ok, status, Results, Json, error::BodyAlreadyConsumed};
 
 #[derive(Deserialize, Serialize)]
 struct User {
@@ -111,4 +111,93 @@ async fn it_writes_untyped_json_response() {
 
     assert_eq!(response.name, "John");
     assert_eq!(response.age, 35);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn it_distinguishes_malformed_and_unprocessable_json() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7951");
+
+        app.map_post("/test", |user: Json<User>| async move {
+            ok!("My name is: {}", user.name)
+        });
+
+        app.run().await
+    });
+
+    let (malformed, unprocessable) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let malformed = client.post("http://127.0.0.1:7951/test")
+            .header("content-type", "application/json")
+            .body("{\"name\": \"John\",")
+            .send()
+            .await;
+        let unprocessable = client.post("http://127.0.0.1:7951/test")
+            .header("content-type", "application/json")
+            .body("{\"name\": \"John\"}")
+            .send()
+            .await;
+        (malformed, unprocessable)
+    }).await.unwrap();
+
+    assert_eq!(malformed.unwrap().status(), 400);
+    assert_eq!(unprocessable.unwrap().status(), 422);
+}
+
+#[tokio::test]
+async fn it_reads_json_payload_of_patch_request() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7993");
+        app.map_patch("/users/{id}", |id: u32, user: Json<User>| async move {
+            Results::text(&format!("Updated #{id}: {} {}", user.name, user.age))
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let user = User { name: String::from("John"), age: 36 };
+        client.patch("http://127.0.0.1:7993/users/1").json(&user).send().await
+    }).await.unwrap().unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "Updated #1: John 36");
+}
+
+#[tokio::test]
+async fn it_returns_error_if_body_already_consumed() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7988");
+        app.map_post("/test", |user: Json<User>, _req: HttpRequest| async move {
+            ok!(user.into_inner())
+        });
+        app.map_err(|err| async move {
+            match err.into_inner().downcast::<BodyAlreadyConsumed>() {
+                Ok(_) => status!(500, "body consumed twice"),
+                Err(_) => status!(500)
+            }
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let user = User { name: String::from("John"), age: 35 };
+        client.post("http://127.0.0.1:7988/test").json(&user).send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 500);
+    assert_eq!(response.text().await.unwrap(), "\"body consumed twice\"");
+}
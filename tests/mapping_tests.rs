@@ -239,4 +239,190 @@ async fn it_overrides_default_head_map() {
     assert!(response.status().is_success());
     assert_eq!(response.headers().get("x-header").unwrap(), "Hello from HEAD");
     assert_eq!(response.text().await.unwrap(), "");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn it_maps_same_handler_to_multiple_paths() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7932");
+        app.map_get(["/a", "/b"], || async {
+            "Pass!"
+        });
+        app.map_group("/group")
+            .map_post(["/c", "/d"], || async {
+                "Pass!"
+            });
+        app.run().await
+    });
+
+    let responses = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        [
+            client.get("http://127.0.0.1:7932/a").send().await.unwrap(),
+            client.get("http://127.0.0.1:7932/b").send().await.unwrap(),
+            client.post("http://127.0.0.1:7932/group/c").send().await.unwrap(),
+            client.post("http://127.0.0.1:7932/group/d").send().await.unwrap(),
+        ]
+    }).await.unwrap();
+
+    for response in responses {
+        assert!(response.status().is_success());
+        assert_eq!(response.text().await.unwrap(), "Pass!");
+    }
+}
+
+#[tokio::test]
+async fn it_rejects_not_accepted_content_type() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7939");
+        app.map_post("/test", || async {
+            "Pass!"
+        })
+        .accepts(["application/json"]);
+        app.run().await
+    });
+
+    let (allowed, disallowed) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let allowed = client
+            .post("http://127.0.0.1:7939/test")
+            .header("content-type", "application/json")
+            .body("{}")
+            .send().await.unwrap();
+        let disallowed = client
+            .post("http://127.0.0.1:7939/test")
+            .header("content-type", "text/plain")
+            .body("{}")
+            .send().await.unwrap();
+        (allowed, disallowed)
+    }).await.unwrap();
+
+    assert_eq!(disallowed.status(), 415);
+    assert!(allowed.status().is_success());
+    assert_eq!(allowed.text().await.unwrap(), "Pass!");
+}
+
+#[test]
+fn it_lists_all_mapped_routes() {
+    let mut app = App::new();
+    app.map_get("/", || async { Results::ok() });
+    app.map_post("/user", || async { Results::ok() });
+    app.map_group("/user")
+        .map_put("/{id}", |_id: i32| async { Results::ok() })
+        .map_delete("/{id}", |_id: i32| async { Results::ok() });
+
+    let routes = app
+        .routes()
+        .into_iter()
+        .map(|route| (route.method, route.pattern))
+        .collect::<Vec<_>>();
+
+    assert_eq!(routes, vec![
+        (Method::GET, "/".to_string()),
+        (Method::HEAD, "/".to_string()),
+        (Method::POST, "/user".to_string()),
+        (Method::DELETE, "/user/{id}".to_string()),
+        (Method::PUT, "/user/{id}".to_string()),
+    ]);
+}
+
+#[test]
+fn it_maps_connect_routes() {
+    let mut app = App::new();
+    app.map_connect("/", || async { Results::ok() });
+    app.map_group("/tunnel")
+        .map_connect("/{id}", |_id: i32| async { Results::ok() });
+
+    let routes = app
+        .routes()
+        .into_iter()
+        .map(|route| (route.method, route.pattern))
+        .collect::<Vec<_>>();
+
+    assert_eq!(routes, vec![
+        (Method::CONNECT, "/".to_string()),
+        (Method::CONNECT, "/tunnel/{id}".to_string()),
+    ]);
+}
+
+#[tokio::test]
+async fn it_uses_fallback_for_prefix() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7943");
+        app.map_get("/api/test", || async { "Pass!" });
+        app.map_fallback_for("/api", || async {
+            volga::status!(404, { "error": "not found" })
+        });
+        app.map_fallback_for("/api/v2", || async {
+            volga::status!(404, { "error": "not found in v2" })
+        });
+        app.run().await
+    });
+
+    let (api, api_v2, other, found) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let api = client.get("http://127.0.0.1:7943/api/x").send().await.unwrap();
+        let api_v2 = client.get("http://127.0.0.1:7943/api/v2/x").send().await.unwrap();
+        let other = client.get("http://127.0.0.1:7943/other").send().await.unwrap();
+        let found = client.get("http://127.0.0.1:7943/api/test").send().await.unwrap();
+        (api, api_v2, other, found)
+    }).await.unwrap();
+
+    assert_eq!(api.status(), 404);
+    assert_eq!(api.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(api.text().await.unwrap(), "{\"error\":\"not found\"}");
+    
+    assert_eq!(api_v2.status(), 404);
+    assert_eq!(api_v2.text().await.unwrap(), "{\"error\":\"not found in v2\"}");
+    
+    assert_eq!(other.status(), 404);
+    assert_eq!(other.text().await.unwrap(), "");
+    
+    assert_eq!(found.status(), 200);
+    assert_eq!(found.text().await.unwrap(), "Pass!");
+}
+
+#[tokio::test]
+async fn it_returns_method_not_allowed_with_allow_header() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7983");
+        app.map_get("/api/test", || async {
+            Results::text("Pass!")
+        });
+        app.map_fallback_for("/api", || async {
+            Results::text("Fallback")
+        });
+        app.run().await
+    });
+
+    let (not_allowed, fallback) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let not_allowed = client.post("http://127.0.0.1:7983/api/test").send().await.unwrap();
+        let fallback = client.post("http://127.0.0.1:7983/api/other").send().await.unwrap();
+        (not_allowed, fallback)
+    }).await.unwrap();
+
+    assert_eq!(not_allowed.status(), 405);
+    let allow = not_allowed.headers().get("allow").unwrap().to_str().unwrap();
+    assert!(allow.split(',').any(|method| method.trim() == "GET"));
+    assert!(!allow.contains("POST"));
+
+    assert_eq!(fallback.status(), 200);
+    assert_eq!(fallback.text().await.unwrap(), "Fallback");
+}
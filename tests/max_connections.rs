@@ -0,0 +1,44 @@
+﻿use std::time::Duration;
+use tokio::net::TcpStream;
+use volga::App;
+
+#[tokio::test]
+async fn it_queues_connections_beyond_the_limit() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7941")
+            .with_max_connections(1);
+        app.map_get("/test", || async { "Pass!" });
+        app.run().await
+    });
+    
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    
+    let held_connection = TcpStream::connect("127.0.0.1:7941").await.unwrap();
+    
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+    
+    let queued = client
+        .get("http://127.0.0.1:7941/test")
+        .timeout(Duration::from_millis(300))
+        .send()
+        .await;
+    
+    assert!(queued.unwrap_err().is_timeout());
+    
+    drop(held_connection);
+    
+    let response = client
+        .get("http://127.0.0.1:7941/test")
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "Pass!");
+}
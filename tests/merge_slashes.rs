@@ -0,0 +1,84 @@
+﻿use std::time::Duration;
+use volga::{App, ok};
+
+#[tokio::test]
+async fn it_merges_slashes_when_enabled() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7959")
+            .with_merge_slashes(true);
+        app.map_get("/a/b", || async { ok!("matched") });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7959/a//b")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "\"matched\"");
+}
+
+#[tokio::test]
+async fn it_does_not_merge_slashes_by_default() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7960");
+        app.map_get("/a/b", || async { ok!("matched") });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7960/a//b")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn it_preserves_slashes_in_catch_all_when_configured() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7928")
+            .with_merge_slashes(true)
+            .with_preserved_catch_all_slashes(true);
+        app.map_get("/files/{*path}", |path: String| async move { ok!("{path}") });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7928/files//a//b")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "\"a//b\"");
+}
@@ -1,4 +1,5 @@
-﻿use volga::{App, Results};
+﻿use http_body_util::BodyExt;
+use volga::{App, HttpRequest, Results, status};
 
 #[tokio::test]
 async fn it_adds_middleware_request() {
@@ -30,4 +31,154 @@ async fn it_adds_middleware_request() {
 
     assert!(response.status().is_success());
     assert_eq!(response.text().await.unwrap(), "Pass!");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn it_reads_route_metadata_in_middleware() {
+    #[derive(Clone)]
+    struct AuthLevel(u8);
+    
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7938");
+
+        app.use_middleware(|context, next| async move {
+            match context.route_metadata::<AuthLevel>() {
+                Some(AuthLevel(level)) if *level > 1 => status!(403),
+                _ => next(context).await
+            }
+        });
+
+        app.map_get("/public", || async {
+            Results::text("Pass!")
+        });
+        app.map_get("/admin", || async {
+            Results::text("Unreachable!")
+        })
+        .with_metadata(AuthLevel(2));
+
+        app.run().await
+    });
+
+    let (public, admin) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let public = client.get("http://127.0.0.1:7938/public").send().await.unwrap();
+        let admin = client.get("http://127.0.0.1:7938/admin").send().await.unwrap();
+        (public, admin)
+    }).await.unwrap();
+
+    assert_eq!(admin.status(), 403);
+    assert!(public.status().is_success());
+    assert_eq!(public.text().await.unwrap(), "Pass!");
+}
+
+#[tokio::test]
+async fn it_reads_grouped_route_metadata_in_middleware() {
+    #[derive(Clone)]
+    struct AuthLevel(u8);
+    
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:8001");
+
+        app.use_middleware(|context, next| async move {
+            match context.route_metadata::<AuthLevel>() {
+                Some(AuthLevel(level)) if *level > 1 => status!(403),
+                _ => next(context).await
+            }
+        });
+
+        app.map_group("/api")
+            .map_get("/admin", || async {
+                Results::text("Unreachable!")
+            })
+            .with_metadata(AuthLevel(2))
+            .map_get("/public", || async {
+                Results::text("Pass!")
+            });
+
+        app.run().await
+    });
+
+    let (public, admin) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let public = client.get("http://127.0.0.1:8001/api/public").send().await.unwrap();
+        let admin = client.get("http://127.0.0.1:8001/api/admin").send().await.unwrap();
+        (public, admin)
+    }).await.unwrap();
+
+    assert_eq!(admin.status(), 403);
+    assert!(public.status().is_success());
+    assert_eq!(public.text().await.unwrap(), "Pass!");
+}
+
+#[tokio::test]
+async fn it_peeks_request_body_in_middleware() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7984");
+
+        app.use_middleware(|mut context, next| async move {
+            let head = context.peek_body(1).await?;
+            if head.first() != Some(&b'{') {
+                return status!(415);
+            }
+            next(context).await
+        });
+
+        app.map_post("/test", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            Results::text(&String::from_utf8_lossy(&body))
+        });
+
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.post("http://127.0.0.1:7984/test").body("{\"name\":\"John\"}").send().await
+    }).await.unwrap().unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "{\"name\":\"John\"}");
+}
+
+#[tokio::test]
+async fn it_respects_body_limit_when_peeking_request_body() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7985")
+            .with_body_limit(4);
+
+        app.use_middleware(|mut context, next| async move {
+            context.peek_body(16).await?;
+            next(context).await
+        });
+
+        app.map_post("/test", || async {
+            Results::text("Unreachable!")
+        });
+
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.post("http://127.0.0.1:7985/test").body("Hello, World!").send().await
+    }).await.unwrap().unwrap();
+
+    assert!(response.status().is_client_error());
+}
@@ -0,0 +1,31 @@
+﻿#![cfg(all(feature = "http1", not(feature = "http2")))]
+
+use std::time::Duration;
+use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpStream};
+use volga::App;
+
+#[tokio::test]
+async fn it_limits_pipelined_requests_per_connection() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7992")
+            .with_http_config(|config| config.with_max_requests_per_connection(3));
+        app.map_get("/test", || async { "Pass!" });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut stream = TcpStream::connect("127.0.0.1:7992").await.unwrap();
+    let requests = "GET /test HTTP/1.1\r\nhost: localhost\r\n\r\n".repeat(5);
+    stream.write_all(requests.as_bytes()).await.unwrap();
+
+    let mut response = Vec::new();
+    let read = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response)).await;
+    let response = String::from_utf8_lossy(&response).to_lowercase();
+
+    assert!(read.is_ok(), "the connection must be closed by the server");
+    assert_eq!(response.matches("http/1.1 200 ok").count(), 3);
+    assert_eq!(response.matches("connection: close").count(), 1);
+    assert!(response.ends_with("pass!"));
+}
@@ -0,0 +1,77 @@
+﻿use std::time::Duration;
+use tokio::{io::AsyncWriteExt, net::{TcpListener, TcpStream}};
+use volga::{App, ConnInfo, ok};
+
+/// Emulates an L4 load balancer that prepends the PROXY v1 header to each connection
+async fn run_proxy(listen: &'static str, upstream: &'static str) {
+    let listener = TcpListener::bind(listen).await.unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut client, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut server = TcpStream::connect(upstream).await.unwrap();
+                server
+                    .write_all(b"PROXY TCP4 203.0.113.7 192.168.0.11 56324 443\r\n")
+                    .await
+                    .unwrap();
+                let _ = tokio::io::copy_bidirectional(&mut client, &mut server).await;
+            });
+        }
+    });
+}
+
+#[tokio::test]
+async fn it_reads_client_address_from_proxy_header() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7955")
+            .with_proxy_protocol();
+        app.map_get("/ip", |conn: ConnInfo| async move {
+            ok!("{}", conn.remote_addr())
+        });
+        app.run().await
+    });
+    run_proxy("127.0.0.1:7956", "127.0.0.1:7955").await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7956/ip")
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "\"203.0.113.7:56324\"");
+}
+
+#[tokio::test]
+async fn it_rejects_connection_without_proxy_header() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7957")
+            .with_proxy_protocol();
+        app.map_get("/ip", || async { "Pass!" });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7957/ip")
+        .send()
+        .await;
+
+    assert!(response.is_err());
+}
@@ -0,0 +1,32 @@
+﻿use std::time::{Duration, Instant, SystemTime};
+use volga::{App, ReceivedAt, ok};
+
+#[tokio::test]
+async fn it_extracts_request_receipt_timestamp() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7978");
+        app.map_get("/received", |received_at: ReceivedAt| async move {
+            let now = Instant::now();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let is_monotonic = received_at.instant() <= now && received_at.elapsed() >= Duration::from_millis(20);
+            let is_recent = SystemTime::now()
+                .duration_since(received_at.system_time())
+                .is_ok_and(|age| age < Duration::from_secs(5));
+            ok!("{}", is_monotonic && is_recent)
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client.get("http://127.0.0.1:7978/received").send().await.unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "\"true\"");
+}
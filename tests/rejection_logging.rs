@@ -0,0 +1,105 @@
+﻿use std::time::Duration;
+use volga::{App, ok};
+use common::logs;
+
+mod common;
+
+/// Returns the rejection events logged for the requests to the paths starting with any of the `prefixes`
+fn rejections(prefixes: &[&str]) -> Vec<String> {
+    logs().contents()
+        .lines()
+        .filter(|line| line.contains("request rejected"))
+        .filter(|line| prefixes.iter().any(|prefix| line.contains(&format!("path=\"{prefix}"))))
+        .map(String::from)
+        .collect()
+}
+
+#[tokio::test]
+async fn it_logs_rejected_requests_with_reason() {
+    logs();
+
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7977");
+        app.map_get("/slow", || async {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            ok!()
+        })
+        .max_concurrency(1);
+        app.map_get("/versioned", || async { ok!() })
+            .require_headers(["x-api-version"]);
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let first = client.get("http://127.0.0.1:7977/slow").send();
+    let second = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        client.get("http://127.0.0.1:7977/slow").send().await
+    };
+    let (first, second) = tokio::join!(first, second);
+
+    assert_eq!(first.unwrap().status(), 200);
+    assert_eq!(second.unwrap().status(), 503);
+
+    let response = client.get("http://127.0.0.1:7977/versioned").send().await.unwrap();
+
+    assert_eq!(response.status(), 400);
+
+    let rejections = rejections(&["/slow", "/versioned"]);
+
+    assert_eq!(rejections.len(), 2);
+    assert!(rejections[0].contains("reason=\"concurrency_limited\""));
+    assert!(rejections[0].contains("status=503"));
+    assert!(rejections[0].contains("path=\"/slow\""));
+    assert!(rejections[0].contains("route=\"/slow\""));
+    assert!(rejections[1].contains("reason=\"missing_required_header\""));
+    assert!(rejections[1].contains("status=400"));
+}
+
+#[tokio::test]
+#[cfg(all(feature = "http1", not(feature = "http2")))]
+async fn it_logs_payload_too_large_while_streaming_body() {
+    use http_body_util::BodyExt;
+    use tokio::{io::{AsyncReadExt, AsyncWriteExt}, net::TcpStream};
+    use volga::{HttpRequest, Results};
+
+    logs();
+
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7929")
+            .with_body_limit(4);
+        app.map_post("/upload/{id}", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            Results::text(&String::from_utf8_lossy(&body))
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let mut stream = TcpStream::connect("127.0.0.1:7929").await.unwrap();
+    let request = "POST /upload/1 HTTP/1.1\r\nhost: localhost\r\ntransfer-encoding: chunked\r\nconnection: close\r\n\r\nd\r\nHello, World!\r\n0\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+    let response = String::from_utf8_lossy(&response).to_lowercase();
+
+    assert!(response.starts_with("http/1.1 413"));
+
+    let rejections = rejections(&["/upload"]);
+
+    assert_eq!(rejections.len(), 1);
+    assert!(rejections[0].contains("reason=\"payload_too_large\""));
+    assert!(rejections[0].contains("status=413"));
+    assert!(rejections[0].contains("path=\"/upload/1\""));
+    assert!(rejections[0].contains("route=\"/upload/{id}\""));
+}
@@ -1,6 +1,6 @@
 ﻿use std::collections::HashMap;
 use serde::Deserialize;
-use volga::{App, Results, Query};
+use volga::{App, Results, Query, RawQuery};
 
 #[derive(Deserialize)]
 struct User {
@@ -35,6 +35,70 @@ async fn it_reads_route_params() {
     assert_eq!(response.text().await.unwrap(), "My name is: John, I'm 35 years old");
 }
 
+#[tokio::test]
+async fn it_matches_constrained_route_params() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7986");
+
+        app.map_get("/test/{age:integer}", |age: u32| async move {
+            Results::text(&format!("Age: {age}"))
+        });
+        app.map_get("/test/{name:alpha}", |name: String| async move {
+            Results::text(&format!("Name: {name}"))
+        });
+
+        app.run().await
+    });
+
+    let (age, name, not_found) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let age = client.get("http://127.0.0.1:7986/test/35").send().await.unwrap();
+        let name = client.get("http://127.0.0.1:7986/test/John").send().await.unwrap();
+        let not_found = client.get("http://127.0.0.1:7986/test/John35").send().await.unwrap();
+        (age, name, not_found)
+    }).await.unwrap();
+
+    assert_eq!(age.text().await.unwrap(), "Age: 35");
+    assert_eq!(name.text().await.unwrap(), "Name: John");
+    assert_eq!(not_found.status(), 404);
+}
+
+#[tokio::test]
+async fn it_reads_catch_all_route_params() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7987");
+
+        app.map_get("/files/{*path}", |path: String| async move {
+            Results::text(&format!("File: {path}"))
+        });
+        app.map_get("/files/readme", || async {
+            Results::text("Readme")
+        });
+
+        app.run().await
+    });
+
+    let (nested, encoded, readme) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let nested = client.get("http://127.0.0.1:7987/files/a/b/c").send().await.unwrap();
+        let encoded = client.get("http://127.0.0.1:7987/files/my%20docs/report%231.txt").send().await.unwrap();
+        let readme = client.get("http://127.0.0.1:7987/files/readme").send().await.unwrap();
+        (nested, encoded, readme)
+    }).await.unwrap();
+
+    assert_eq!(nested.text().await.unwrap(), "File: a/b/c");
+    assert_eq!(encoded.text().await.unwrap(), "File: my docs/report#1.txt");
+    assert_eq!(readme.text().await.unwrap(), "Readme");
+}
+
 #[tokio::test]
 async fn it_reads_query_params() {
     tokio::spawn(async {
@@ -89,4 +153,29 @@ async fn it_reads_query_as_hash_map_params() {
 
     assert!(response.status().is_success());
     assert_eq!(response.text().await.unwrap(), "My name is: John, I'm 35 years old");
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn it_reads_raw_query() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7931");
+
+        app.map_get("/test", |query: RawQuery| async move {
+            query.into_inner().unwrap_or_default()
+        });
+
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7931/test?a=1&b=2").send().await
+    }).await.unwrap().unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(response.text().await.unwrap(), "a=1&b=2");
+}
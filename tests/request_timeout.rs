@@ -0,0 +1,117 @@
+﻿use std::{sync::{Arc, atomic::{AtomicBool, Ordering}}, time::Duration};
+use bytes::Bytes;
+use futures_util::stream;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use volga::{App, CancellationToken, stream, error::Error};
+
+fn events() -> http_body_util::combinators::BoxBody<Bytes, Error> {
+    let events = stream::unfold(0, |i| async move {
+        if i == 5 {
+            return None;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        Some((Ok::<_, Error>(Frame::data(Bytes::from(format!("data: {i}\n\n")))), i + 1))
+    });
+    BodyExt::boxed(StreamBody::new(events))
+}
+
+#[tokio::test]
+async fn it_applies_request_timeouts() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7947")
+            .with_request_timeout(Duration::from_millis(250));
+        app.map_get("/slow", || async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            "Pass!"
+        });
+        app.map_get("/sse", || async {
+            stream!(events(), [("content-type", "text/event-stream")])
+        })
+        .no_timeout();
+        app.map_get("/ttfb", || async {
+            stream!(events(), [("content-type", "text/event-stream")])
+        })
+        .ttfb_timeout(Duration::from_millis(100));
+        app.map_get("/stream", || async {
+            stream!(events(), [("content-type", "text/event-stream")])
+        })
+        .timeout(Duration::from_millis(250));
+        app.map_get("/default", || async {
+            stream!(events(), [("content-type", "text/event-stream")])
+        });
+        app.run().await
+    });
+
+    let (slow, sse, ttfb, stream, default) = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let slow = client.get("http://127.0.0.1:7947/slow").send().await.unwrap();
+        let sse = client.get("http://127.0.0.1:7947/sse").send().await.unwrap();
+        let ttfb = client.get("http://127.0.0.1:7947/ttfb").send().await.unwrap();
+        let stream = client.get("http://127.0.0.1:7947/stream").send().await.unwrap();
+        let default = client.get("http://127.0.0.1:7947/default").send().await.unwrap();
+        (slow, sse, ttfb, stream, default)
+    }).await.unwrap();
+    
+    let expected = (0..5)
+        .map(|i| format!("data: {i}\n\n"))
+        .collect::<String>();
+
+    assert_eq!(slow.status(), 504);
+    
+    assert_eq!(sse.status(), 200);
+    assert_eq!(sse.text().await.unwrap(), expected);
+    
+    assert_eq!(ttfb.status(), 200);
+    assert_eq!(ttfb.text().await.unwrap(), expected);
+    
+    assert_eq!(stream.status(), 200);
+    assert!(stream.text().await.is_err());
+    
+    assert_eq!(default.status(), 200);
+    assert_eq!(default.text().await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn it_cancels_timed_out_request() {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let observed = cancelled.clone();
+    
+    tokio::spawn(async move {
+        let mut app = App::new()
+            .bind("127.0.0.1:7991")
+            .with_request_timeout(Duration::from_millis(100));
+        app.map_get("/slow", move |token: CancellationToken| {
+            let cancelled = cancelled.clone();
+            async move {
+                let token = token.into_inner();
+                tokio::spawn(async move {
+                    token.cancelled().await;
+                    cancelled.store(true, Ordering::SeqCst);
+                });
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "Unreachable!"
+            }
+        });
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7991/slow").send().await
+    }).await.unwrap().unwrap();
+
+    assert_eq!(response.status(), 504);
+    
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(observed.load(Ordering::SeqCst));
+}
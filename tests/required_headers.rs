@@ -0,0 +1,71 @@
+﻿use volga::{App, ok};
+
+#[tokio::test]
+async fn it_rejects_request_without_app_required_header() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7975")
+            .with_required_headers(["x-api-version"]);
+        app.map_get("/test", || async { ok!() });
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client.get("http://127.0.0.1:7975/test").send().await.unwrap();
+
+    assert_eq!(response.status(), 400);
+    assert!(response.text().await.unwrap().contains("Missing required header: x-api-version"));
+
+    let response = client
+        .get("http://127.0.0.1:7975/test")
+        .header("x-api-version", "2")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn it_rejects_request_without_route_required_header() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7976");
+        app.map_get("/required", || async { ok!() })
+            .require_headers(["x-tenant-id"]);
+        app.map_get("/optional", || async { ok!() });
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client.get("http://127.0.0.1:7976/required").send().await.unwrap();
+
+    assert_eq!(response.status(), 400);
+    assert!(response.text().await.unwrap().contains("x-tenant-id"));
+
+    let response = client.get("http://127.0.0.1:7976/optional").send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .get("http://127.0.0.1:7976/required")
+        .header("x-tenant-id", "acme")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+}
@@ -0,0 +1,77 @@
+﻿use std::time::Duration;
+use volga::{App, error::ErrorFormat, headers::HeaderValue, status};
+
+#[tokio::test]
+async fn it_maps_all_responses() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7952");
+        app.map_response(|mut resp| async move {
+            resp.headers_mut().insert("x-api-version", HeaderValue::from_static("1.0"));
+            resp
+        });
+        app.map_get("/ok", || async { "Pass!" });
+        app.map_get("/error", || async { status!(400) });
+        app.map_get("/fail", || async { Err::<(), _>(std::io::Error::other("some error")) });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    for (path, status) in [("/ok", 200), ("/error", 400), ("/fail", 500), ("/missing", 404)] {
+        let response = client
+            .get(format!("http://127.0.0.1:7952{path}"))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status);
+        assert_eq!(response.headers().get("x-api-version").unwrap(), "1.0");
+    }
+}
+
+#[tokio::test]
+async fn it_handles_mapper_errors_with_configured_error_format() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7895")
+            .with_error_format(ErrorFormat::Json);
+        app.map_response(|resp| async move {
+            if resp.headers().contains_key("x-fail") {
+                Err(std::io::Error::other("mapper failed"))
+            } else {
+                Ok(resp)
+            }
+        });
+        app.map_response(|mut resp| async move {
+            resp.headers_mut().insert("x-api-version", HeaderValue::from_static("1.0"));
+            resp
+        });
+        app.map_get("/fail", || async { status!(200, [("x-fail", "true")]) });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7895/fail")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 500);
+    assert_eq!(response.headers().get("content-type").unwrap(), "application/json");
+    assert_eq!(response.headers().get("x-api-version").unwrap(), "1.0");
+    assert!(response.text().await.unwrap().contains("mapper failed"));
+}
@@ -0,0 +1,57 @@
+﻿use std::time::Duration;
+use volga::{App, error::Error};
+
+#[tokio::test]
+async fn it_adds_response_time_header() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7950");
+        app.use_response_time_header("x-response-time");
+        app.map_get("/test", || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            "Pass!"
+        });
+        app.map_get("/error", || async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Err::<&str, _>(Error::client_error("Invalid input"))
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7950/test")
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_success());
+    assert!((20..5000).contains(&elapsed(&response)));
+
+    let response = client
+        .get("http://127.0.0.1:7950/error")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 400);
+    assert!((20..5000).contains(&elapsed(&response)));
+}
+
+fn elapsed(response: &reqwest::Response) -> u64 {
+    response.headers()
+        .get("x-response-time")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .strip_suffix("ms")
+        .unwrap()
+        .parse::<u64>()
+        .unwrap()
+}
@@ -0,0 +1,90 @@
+﻿use std::time::Duration;
+use volga::{App, di::Dc, http::sse::SseChannel, sse_from_channel};
+
+#[tokio::test]
+async fn it_streams_messages_from_channel() {
+    let channel = SseChannel::new();
+    let sender = channel.sender();
+    tokio::spawn(async move {
+        let mut app = App::new().bind("127.0.0.1:7958");
+        app.add_singleton(channel);
+        app.map_get("/events", |channel: Dc<SseChannel>| async move {
+            sse_from_channel!(channel.subscribe())
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let mut response = client
+        .get("http://127.0.0.1:7958/events")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.headers().get("content-type").unwrap(), "text/event-stream");
+
+    assert_eq!(sender.send("first"), 1);
+    assert_eq!(sender.send("second"), 1);
+
+    let mut received = String::new();
+    while !received.ends_with("data: second\n\n") {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        received.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+
+    assert_eq!(received, "data: first\n\ndata: second\n\n");
+
+    drop(response);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(sender.send("third"), 0);
+}
+
+#[tokio::test]
+async fn it_answers_head_without_streaming_body() {
+    let channel = SseChannel::new();
+    tokio::spawn(async move {
+        let mut app = App::new().bind("127.0.0.1:7980");
+        app.add_singleton(channel);
+        app.map_get("/events", |channel: Dc<SseChannel>| async move {
+            sse_from_channel!(channel.subscribe())
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = tokio::time::timeout(Duration::from_secs(5), async {
+        let response = client
+            .head("http://127.0.0.1:7980/events")
+            .send()
+            .await
+            .unwrap();
+        let headers = response.headers().clone();
+        let body = response.bytes().await.unwrap();
+        (headers, body)
+    })
+    .await;
+
+    let (headers, body) = response.unwrap();
+
+    assert_eq!(headers.get("content-type").unwrap(), "text/event-stream");
+    assert!(body.is_empty());
+}
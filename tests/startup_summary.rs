@@ -0,0 +1,34 @@
+﻿use std::time::Duration;
+use volga::App;
+use common::logs;
+
+mod common;
+
+#[tokio::test]
+async fn it_logs_startup_summary() {
+    logs();
+    
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7942");
+        app.map_post("/test", || async { "Pass!" });
+        app.map_put("/test", || async { "Pass!" });
+        app.host("api.example.com", |api| {
+            api.map_post("/test", || async { "Pass!" });
+        });
+        app.use_middleware(|ctx, next| async move { next(ctx).await });
+        app.use_response_time_header("x-response-time");
+        app.run().await
+    });
+    
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    
+    let logs = logs().contents();
+    let summary = logs
+        .lines()
+        .find(|line| line.contains("startup configuration"))
+        .unwrap();
+    
+    assert!(summary.contains("address=127.0.0.1:7942"));
+    assert!(summary.contains("routes=3"));
+    assert!(summary.contains("middlewares=[\"custom\", \"response_time\"]"));
+}
@@ -0,0 +1,30 @@
+﻿use volga::{App, static_response};
+
+#[tokio::test]
+async fn it_responds_with_static_response() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7982");
+        app.map_get("/ping", static_response!(200, "pong"));
+        app.map_get("/legacy", static_response!(410, [("x-reason", "removed")]));
+        app.run().await
+    });
+
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client.get("http://127.0.0.1:7982/ping").send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "\"pong\"");
+
+    let response = client.get("http://127.0.0.1:7982/legacy").send().await.unwrap();
+
+    assert_eq!(response.status(), 410);
+    assert_eq!(response.headers().get("x-reason").unwrap(), "removed");
+    assert!(response.text().await.unwrap().is_empty());
+}
@@ -0,0 +1,93 @@
+﻿use std::sync::Arc;
+use bytes::Bytes;
+use http_body_util::{BodyExt, StreamBody};
+use hyper::body::Frame;
+use tokio::sync::{mpsc, Notify};
+use volga::{App, stream};
+
+#[tokio::test]
+async fn it_tracks_in_flight_and_total_requests() {
+    let started = Arc::new(Notify::new());
+    let release = Arc::new(Notify::new());
+    
+    let mut app = App::new().bind("127.0.0.1:7930");
+    let stats = app.stats_handle();
+    
+    let (handler_started, handler_release) = (started.clone(), release.clone());
+    app.map_get("/slow", move || {
+        let (started, release) = (handler_started.clone(), handler_release.clone());
+        async move {
+            started.notify_one();
+            release.notified().await;
+            "done"
+        }
+    });
+    
+    tokio::spawn(async {
+        app.run().await
+    });
+
+    let request = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7930/slow").send().await
+    });
+    
+    started.notified().await;
+    
+    assert_eq!(stats.in_flight(), 1);
+    assert_eq!(stats.total(), 0);
+    
+    release.notify_one();
+    let response = request.await.unwrap().unwrap();
+
+    assert!(response.status().is_success());
+    assert_eq!(stats.in_flight(), 0);
+    assert_eq!(stats.total(), 1);
+}
+
+#[tokio::test]
+async fn it_tracks_request_in_flight_while_response_is_streamed() {
+    let (tx, rx) = mpsc::channel::<Bytes>(1);
+    let rx = Arc::new(tokio::sync::Mutex::new(Some(rx)));
+    
+    let mut app = App::new().bind("127.0.0.1:7998");
+    let stats = app.stats_handle();
+    
+    app.map_get("/stream", move || {
+        let rx = rx.clone();
+        async move {
+            let rx = rx.lock().await.take().unwrap();
+            let chunks = futures_util::stream::unfold(rx, |mut rx| async move {
+                rx.recv().await.map(|chunk| (Ok::<_, volga::error::Error>(Frame::data(chunk)), rx))
+            });
+            stream!(BodyExt::boxed(StreamBody::new(chunks)))
+        }
+    });
+    
+    tokio::spawn(async {
+        app.run().await
+    });
+
+    let response = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        client.get("http://127.0.0.1:7998/stream").send().await.unwrap()
+    }).await.unwrap();
+    
+    assert_eq!(stats.in_flight(), 1);
+    assert_eq!(stats.total(), 0);
+    
+    tx.send(Bytes::from_static(b"done")).await.unwrap();
+    drop(tx);
+    
+    assert_eq!(response.text().await.unwrap(), "done");
+    assert_eq!(stats.in_flight(), 0);
+    assert_eq!(stats.total(), 1);
+}
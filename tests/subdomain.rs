@@ -0,0 +1,39 @@
+﻿use std::time::Duration;
+use volga::{App, Subdomain, ok};
+
+#[tokio::test]
+async fn it_reads_subdomain() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7949")
+            .with_base_domain("example.com");
+        app.map_get("/tenant", |tenant: Subdomain| async move {
+            ok!(tenant.as_str().unwrap_or("none"))
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let tenant = client
+        .get("http://127.0.0.1:7949/tenant")
+        .header("host", "t1.example.com")
+        .send()
+        .await
+        .unwrap();
+    let base = client
+        .get("http://127.0.0.1:7949/tenant")
+        .header("host", "example.com")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(tenant.text().await.unwrap(), "\"t1\"");
+    assert_eq!(base.text().await.unwrap(), "\"none\"");
+}
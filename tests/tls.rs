@@ -1,6 +1,61 @@
-﻿use volga::App;
+﻿use std::sync::OnceLock;
+use volga::App;
 use volga::tls::TlsConfig;
 use reqwest::{Certificate, Identity};
+use rcgen::{
+    BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose,
+    IsCa, KeyPair, KeyUsagePurpose
+};
+
+/// Paths to the CA, server and client certificates generated for the test run
+struct TestCerts {
+    ca: String,
+    server_cert: String,
+    server_key: String,
+    client_cert: String,
+    client_key: String,
+}
+
+/// Generates a CA and the server and client certificates signed by it once per test binary
+fn certs() -> &'static TestCerts {
+    static CERTS: OnceLock<TestCerts> = OnceLock::new();
+    CERTS.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("volga-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, contents: String| {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path.to_str().unwrap().to_owned()
+        };
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.distinguished_name.push(DnType::CommonName, "Volga Test CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+
+        let server_key = KeyPair::generate().unwrap();
+        let mut server_params = CertificateParams::new(vec!["localhost".into()]).unwrap();
+        server_params.distinguished_name.push(DnType::CommonName, "localhost");
+        server_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let server = server_params.signed_by(&server_key, &ca, &ca_key).unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = CertificateParams::new(vec!["client".into()]).unwrap();
+        client_params.distinguished_name.push(DnType::CommonName, "client");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client = client_params.signed_by(&client_key, &ca, &ca_key).unwrap();
+
+        TestCerts {
+            ca: write("ca.pem", ca.pem()),
+            server_cert: write("server.pem", server.pem()),
+            server_key: write("server.key", server_key.serialize_pem()),
+            client_cert: write("client.pem", client.pem()),
+            client_key: write("client.key", client_key.serialize_pem()),
+        }
+    })
+}
 
 #[tokio::test]
 async fn it_works_with_tls_with_no_auth() {
@@ -8,8 +63,8 @@ async fn it_works_with_tls_with_no_auth() {
         let mut app = App::new()
             .bind("127.0.0.1:7921")
             .with_tls(TlsConfig::from_pem_files(
-                "tests/tls/server.pem",
-                "tests/tls/server.key"));
+                &certs().server_cert,
+                &certs().server_key));
         
         app.map_get("/tls", || async {
             "Pass!"
@@ -18,8 +73,8 @@ async fn it_works_with_tls_with_no_auth() {
     });
 
     let response = tokio::spawn(async {
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
         
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder().http1_only().add_root_certificate(ca_certificate).build().unwrap()
@@ -43,9 +98,9 @@ async fn it_works_with_tls_with_required_auth_authenticated() {
         let mut app = App::new()
             .bind("127.0.0.1:7922")
             .with_tls(TlsConfig::new()
-                .with_cert_path("tests/tls/server.pem")
-                .with_key_path("tests/tls/server.key")
-                .with_required_client_auth("tests/tls/ca.pem"));
+                .with_cert_path(&certs().server_cert)
+                .with_key_path(&certs().server_key)
+                .with_required_client_auth(&certs().ca));
         
         app.map_get("/tls", || async {
             "Pass!"
@@ -54,13 +109,13 @@ async fn it_works_with_tls_with_required_auth_authenticated() {
     });
 
     let response = tokio::spawn(async {
-        let cert = include_bytes!("tls/client.pem");
-        let key = include_bytes!("tls/client.key");
+        let cert = std::fs::read(&certs().client_cert).unwrap();
+        let key = std::fs::read(&certs().client_key).unwrap();
         
-        let identity = Identity::from_pkcs8_pem(cert, key).unwrap();
+        let identity = Identity::from_pkcs8_pem(&cert, &key).unwrap();
 
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
         
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder()
@@ -94,9 +149,9 @@ async fn it_works_with_tls_with_required_auth_unauthenticated() {
         let mut app = App::new()
             .bind("127.0.0.1:7923")
             .with_tls(TlsConfig::from_pem_files(
-                "tests/tls/server.pem",
-                "tests/tls/server.key")
-                .with_required_client_auth("tests/tls/ca.pem"));
+                &certs().server_cert,
+                &certs().server_key)
+                .with_required_client_auth(&certs().ca));
         
         app.map_get("/tls", || async {
             "Pass!"
@@ -105,8 +160,8 @@ async fn it_works_with_tls_with_required_auth_unauthenticated() {
     });
 
     let response = tokio::spawn(async {
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
 
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder()
@@ -138,9 +193,9 @@ async fn it_works_with_tls_with_optional_auth_authenticated() {
         let mut app = App::new()
             .bind("127.0.0.1:7924")
             .with_tls(TlsConfig::from_pem_files(
-                "tests/tls/server.pem",
-                "tests/tls/server.key")
-                .with_optional_client_auth("tests/tls/ca.pem"));
+                &certs().server_cert,
+                &certs().server_key)
+                .with_optional_client_auth(&certs().ca));
         
         app.map_get("/tls", || async {
             "Pass!"
@@ -149,13 +204,13 @@ async fn it_works_with_tls_with_optional_auth_authenticated() {
     });
 
     let response = tokio::spawn(async {
-        let cert = include_bytes!("tls/client.pem");
-        let key = include_bytes!("tls/client.key");
+        let cert = std::fs::read(&certs().client_cert).unwrap();
+        let key = std::fs::read(&certs().client_key).unwrap();
 
-        let identity = Identity::from_pkcs8_pem(cert, key).unwrap();
+        let identity = Identity::from_pkcs8_pem(&cert, &key).unwrap();
 
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
 
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder()
@@ -189,9 +244,9 @@ async fn it_works_with_tls_with_optional_auth_unauthenticated() {
         let mut app = App::new()
             .bind("127.0.0.1:7925")
             .with_tls(TlsConfig::default()
-                .with_cert_path("tests/tls/server.pem")
-                .with_key_path("tests/tls/server.key")
-                .with_optional_client_auth("tests/tls/ca.pem"));
+                .with_cert_path(&certs().server_cert)
+                .with_key_path(&certs().server_key)
+                .with_optional_client_auth(&certs().ca));
 
         app.map_get("/tls", || async {
             "Pass!"
@@ -200,8 +255,8 @@ async fn it_works_with_tls_with_optional_auth_unauthenticated() {
     });
 
     let response = tokio::spawn(async {
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
         
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder()
@@ -233,8 +288,8 @@ async fn it_works_with_tls_with_required_auth_authenticated_and_https_redirectio
         let mut app = App::new()
             .bind(([127,0,0,1], 7926))
             .with_tls(TlsConfig::from_pem_files(
-                "tests/tls/server.pem",
-                "tests/tls/server.key")
+                &certs().server_cert,
+                &certs().server_key)
                 .with_https_redirection()
                 .with_http_port(7927));
 
@@ -250,8 +305,8 @@ async fn it_works_with_tls_with_required_auth_authenticated_and_https_redirectio
         // Giving a little more time for the task spawned above
         sleep(Duration::from_millis(10)).await;
         
-        let ca_cert = include_bytes!("tls/ca.pem");
-        let ca_certificate = Certificate::from_pem(ca_cert).unwrap();
+        let ca_cert = std::fs::read(&certs().ca).unwrap();
+        let ca_certificate = Certificate::from_pem(&ca_cert).unwrap();
         
         let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
             reqwest::Client::builder()
@@ -0,0 +1,92 @@
+﻿use std::{path::PathBuf, time::Duration};
+use rcgen::{CertificateParams, KeyPair};
+use time::OffsetDateTime;
+use volga::{App, tls::TlsConfig};
+use common::logs;
+
+mod common;
+
+fn warnings() -> Vec<String> {
+    logs().contents()
+        .lines()
+        .filter(|line| line.contains("WARN"))
+        .map(String::from)
+        .collect()
+}
+
+/// Generates a self-signed certificate that expires at `not_after`
+/// and returns the paths to the certificate and the private key
+fn generate_cert(name: &str, not_after: OffsetDateTime) -> (String, String) {
+    let mut params = CertificateParams::new(vec!["localhost".into()]).unwrap();
+    params.not_before = not_after - Duration::from_secs(365 * 24 * 60 * 60);
+    params.not_after = not_after;
+
+    let key = KeyPair::generate().unwrap();
+    let cert = params.self_signed(&key).unwrap();
+
+    let dir: PathBuf = std::env::temp_dir().join(format!("volga-tls-cert-expiry-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let cert_path = dir.join(format!("{name}.pem"));
+    let key_path = dir.join(format!("{name}.key"));
+    std::fs::write(&cert_path, cert.pem()).unwrap();
+    std::fs::write(&key_path, key.serialize_pem()).unwrap();
+
+    (cert_path.to_str().unwrap().into(), key_path.to_str().unwrap().into())
+}
+
+#[tokio::test]
+async fn it_warns_about_expiring_cert() {
+    logs();
+    let not_after = OffsetDateTime::now_utc() + Duration::from_secs(3 * 24 * 60 * 60 + 60 * 60);
+    let (cert, key) = generate_cert("expiring", not_after);
+
+    tokio::spawn(async move {
+        let app = App::new()
+            .bind("127.0.0.1:7953")
+            .with_tls(TlsConfig::from_pem_files(&cert, &key)
+                .with_cert_expiry_warning(7));
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert!(warnings()
+        .iter()
+        .any(|line| line.ends_with("TLS certificate expires in 3 day(s)")));
+}
+
+#[tokio::test]
+async fn it_warns_about_expired_cert() {
+    logs();
+    let not_after = OffsetDateTime::now_utc() - Duration::from_secs(24 * 60 * 60);
+    let (cert, key) = generate_cert("expired_warning", not_after);
+
+    tokio::spawn(async move {
+        let app = App::new()
+            .bind("127.0.0.1:7968")
+            .with_tls(TlsConfig::from_pem_files(&cert, &key));
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert!(warnings()
+        .iter()
+        .any(|line| line.ends_with("TLS certificate has expired")));
+}
+
+#[tokio::test]
+async fn it_refuses_to_start_with_expired_cert() {
+    let not_after = OffsetDateTime::now_utc() - Duration::from_secs(24 * 60 * 60);
+    let (cert, key) = generate_cert("expired", not_after);
+
+    let app = App::new()
+        .bind("127.0.0.1:7954")
+        .with_tls(TlsConfig::from_pem_files(&cert, &key)
+            .with_expired_cert_rejection(true));
+
+    let result = app.run().await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,43 @@
+﻿use std::time::Duration;
+use http_body_util::BodyExt;
+use volga::{App, HttpRequest, ok};
+
+#[tokio::test]
+async fn it_verifies_upload_checksum() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7964");
+        app.verify_upload_checksum("x-content-sha256");
+        app.map_post("/upload", |req: HttpRequest| async move {
+            let body = req.into_body().collect().await?.to_bytes();
+            ok!("received {} bytes", body.len())
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let valid = client
+        .post("http://127.0.0.1:7964/upload")
+        .header("x-content-sha256", "7f83b1657ff1fc53b92dc18148a1d65dfc2d4b1fa3d677284addd200126d9069")
+        .body("Hello World!")
+        .send()
+        .await
+        .unwrap();
+    let invalid = client
+        .post("http://127.0.0.1:7964/upload")
+        .header("x-content-sha256", "0000000000000000000000000000000000000000000000000000000000000000")
+        .body("Hello World!")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(valid.status(), 200);
+    assert_eq!(valid.text().await.unwrap(), "\"received 12 bytes\"");
+    assert_eq!(invalid.status(), 400);
+}
@@ -0,0 +1,101 @@
+﻿use std::time::Duration;
+use volga::{App, HttpRequest, UriParts, ok, status};
+
+#[tokio::test]
+async fn it_reads_uri_parts() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7961");
+        app.map_get("/uri", |uri: UriParts| async move {
+            let parts = vec![
+                uri.scheme().to_owned(),
+                uri.host().unwrap_or_default().to_owned(),
+                uri.port().unwrap_or_default().to_string(),
+                uri.path().to_owned(),
+                uri.query().unwrap_or_default().to_owned()
+            ];
+            ok!(parts)
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7961/uri?page=1")
+        .header("x-forwarded-proto", "https")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text().await.unwrap(), "[\"http\",\"127.0.0.1\",\"7961\",\"/uri\",\"page=1\"]");
+}
+
+#[tokio::test]
+async fn it_reads_uri_parts_from_forwarded_headers_if_proxy_is_trusted() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7962")
+            .with_trusted_proxy();
+        app.map_get("/uri", |uri: UriParts| async move {
+            ok!("{}://{}{}", uri.scheme(), uri.authority().unwrap_or_default(), uri.path())
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .get("http://127.0.0.1:7962/uri")
+        .header("x-forwarded-proto", "https")
+        .header("x-forwarded-host", "example.com")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.text().await.unwrap(), "\"https://example.com/uri\"");
+}
+
+#[tokio::test]
+async fn it_builds_absolute_url_from_forwarded_headers_if_proxy_is_trusted() {
+    tokio::spawn(async {
+        let mut app = App::new()
+            .bind("127.0.0.1:7963")
+            .with_trusted_proxy();
+        app.map_post("/users", |req: HttpRequest| async move {
+            let location = req.absolute_url("/users/1");
+            status!(201, [("location", location)])
+        });
+        app.run().await
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+        reqwest::Client::builder().http1_only().build().unwrap()
+    } else {
+        reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+    };
+
+    let response = client
+        .post("http://127.0.0.1:7963/users")
+        .header("x-forwarded-proto", "https")
+        .header("x-forwarded-host", "example.com")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.headers().get("location").unwrap(), "https://example.com/users/1");
+}
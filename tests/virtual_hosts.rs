@@ -0,0 +1,61 @@
+﻿use volga::{App, ok};
+
+#[tokio::test]
+async fn it_routes_requests_by_host() {
+    tokio::spawn(async {
+        let mut app = App::new().bind("127.0.0.1:7997");
+        app.host("api.example.com", |api| {
+            api.map_get("/users", || async { ok!("api users") });
+        });
+        app.map_get("/", || async { ok!("home") });
+        app.run().await
+    });
+
+    let responses = tokio::spawn(async {
+        let client = if cfg!(all(feature = "http1", not(feature = "http2"))) {
+            reqwest::Client::builder().http1_only().build().unwrap()
+        } else {
+            reqwest::Client::builder().http2_prior_knowledge().build().unwrap()
+        };
+        let mut responses = Vec::new();
+        for (host, path) in [
+            ("api.example.com", "/users"),
+            ("API.example.com:7997", "/users"),
+            ("admin.example.com", "/users"),
+            ("admin.example.com", "/"),
+            ("api.example.com", "/"),
+        ] {
+            let response = client
+                .get(format!("http://127.0.0.1:7997{path}"))
+                .header("host", host)
+                .send()
+                .await.unwrap();
+            responses.push(response.status().as_u16());
+        }
+        responses
+    }).await.unwrap();
+
+    assert_eq!(responses, [200, 200, 404, 200, 404]);
+}
+
+#[test]
+fn it_lists_host_routes() {
+    let mut app = App::new();
+    app.host("API.example.com", |api| {
+        api.map_get("/users", || async { ok!("api users") });
+    });
+    app.map_get("/users", || async { ok!("users") });
+
+    let routes = app
+        .routes()
+        .into_iter()
+        .map(|route| (route.host, route.method.to_string(), route.pattern))
+        .collect::<Vec<_>>();
+
+    assert_eq!(routes, vec![
+        (None, "GET".to_string(), "/users".to_string()),
+        (None, "HEAD".to_string(), "/users".to_string()),
+        (Some("api.example.com".to_string()), "GET".to_string(), "/users".to_string()),
+        (Some("api.example.com".to_string()), "HEAD".to_string(), "/users".to_string()),
+    ]);
+}
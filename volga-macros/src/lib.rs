@@ -0,0 +1,80 @@
+﻿//! Procedural macros for the Volga web framework
+//!
+//! Use them through the `macros` feature of the `volga` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use syn::{parse_macro_input, ItemFn, LitStr, Visibility};
+
+macro_rules! route_attribute {
+    ($name:ident, $map:literal, $method:literal) => {
+        #[doc = concat!("Maps the async function as a `", $method, "` request handler to the route pattern")]
+        ///
+        /// The function is turned into a unit struct of the same name that is mapped 
+        /// to the `App` with the `register_handlers!` macro. Path params and extractors 
+        /// work the same way as for closures passed to the `App`.
+        ///
+        /// # Example
+        /// ```ignore
+        /// use volga::{App, HttpResult, ok, register_handlers};
+        /// use volga::routing::get;
+        ///
+        /// #[get("/hello/{name}")]
+        /// async fn hello(name: String) -> HttpResult {
+        ///     ok!("Hello {name}!")
+        /// }
+        ///
+        /// let mut app = App::new();
+        /// register_handlers!(app, hello);
+        /// ```
+        #[proc_macro_attribute]
+        pub fn $name(args: TokenStream, item: TokenStream) -> TokenStream {
+            expand($map, args, item)
+        }
+    };
+}
+
+route_attribute!(get, "map_get", "GET");
+route_attribute!(post, "map_post", "POST");
+route_attribute!(put, "map_put", "PUT");
+route_attribute!(patch, "map_patch", "PATCH");
+route_attribute!(delete, "map_delete", "DELETE");
+route_attribute!(head, "map_head", "HEAD");
+route_attribute!(options, "map_options", "OPTIONS");
+route_attribute!(trace, "map_trace", "TRACE");
+route_attribute!(connect, "map_connect", "CONNECT");
+
+fn expand(map: &str, args: TokenStream, item: TokenStream) -> TokenStream {
+    let pattern = parse_macro_input!(args as LitStr);
+    let mut handler = parse_macro_input!(item as ItemFn);
+
+    if handler.sig.asyncness.is_none() {
+        return syn::Error::new_spanned(handler.sig.fn_token, "route handler must be an async function")
+            .to_compile_error()
+            .into();
+    }
+
+    let vis = std::mem::replace(&mut handler.vis, Visibility::Inherited);
+    let (docs, attrs) = handler.attrs
+        .into_iter()
+        .partition::<Vec<_>, _>(|attr| attr.path().is_ident("doc"));
+    handler.attrs = attrs;
+
+    let name = &handler.sig.ident;
+    let map = Ident::new(map, Span::call_site());
+
+    quote! {
+        #(#docs)*
+        #[allow(non_camel_case_types)]
+        #vis struct #name;
+
+        impl ::volga::routing::MapRoute for #name {
+            fn map(self, app: &mut ::volga::App) {
+                #handler
+                app.#map(#pattern, #name);
+            }
+        }
+    }
+    .into()
+}